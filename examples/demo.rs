@@ -2,34 +2,33 @@
 //!
 //! Architecture:
 //!   Full-res 3D Camera (layer 0) → window         (terrain, standard PBR comparison)
-//!   Low-res 3D Camera  (layer 1) → 320×180 texture (pixel art + holdout + EdgeDetection)
+//!   Low-res 3D Camera  (layer 1) → 320×180 texture (pixel art + holdout + PixelArtOutline)
 //!   UI ImageNode                  → canvas overlay  (nearest upscale on top of full-res scene)
 //!
 //! Controls: left-drag = orbit, right-drag = pan, scroll = zoom
 //!
 //! Run:  cargo run --example demo
 
-use bevy::camera::visibility::RenderLayers;
 use bevy::camera::RenderTarget;
 use bevy::image::ImageSampler;
 use bevy::pbr::ExtendedMaterial;
 use bevy::prelude::*;
 use bevy::render::render_resource::TextureFormat;
-use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin, EdgeOperator};
 use bevy_egui::{
     EguiContext, EguiContexts, EguiGlobalSettings, EguiPlugin, EguiPrimaryContextPass,
     PrimaryEguiContext, egui,
 };
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use bevy_pixel_art_shader::{
-    HoldoutExtension, HoldoutMaterial, LowResPixelArtCamera, PixelArtCompositor,
-    PixelArtCompositorPlugin, PixelArtExtension, PixelArtMaterial, PixelArtShaderParams,
-    PixelArtShaderPlugin, default_pixel_art_palette,
+    DebugStage, HoldoutExtension, HoldoutMaterial, LowResPixelArtCamera, PixelArtCompositor,
+    PixelArtCompositorPlugin, PixelArtExtension, PixelArtInstanceParams, PixelArtLayers,
+    PixelArtMaterial, PixelArtMaterials, PixelArtOutline, PixelArtOutlinePlugin,
+    PixelArtSettings, PixelArtShaderParams, PixelArtShaderPlugin, PixelLayerOnly,
+    default_pixel_art_palette,
 };
 
 const RES_WIDTH: u32 = 320;
 const RES_HEIGHT: u32 = 180;
-const PIXEL_ART_LAYER: RenderLayers = RenderLayers::layer(1);
 
 fn main() {
     App::new()
@@ -41,17 +40,18 @@ fn main() {
             }),
             ..default()
         }))
-        .add_plugins(PixelArtShaderPlugin)
+        .add_plugins(PixelArtShaderPlugin::default())
         .add_plugins(PixelArtCompositorPlugin)
-        .add_plugins(EdgeDetectionPlugin::default())
+        .add_plugins(PixelArtOutlinePlugin)
         .add_plugins(PanOrbitCameraPlugin)
         .add_plugins(EguiPlugin::default())
         .insert_resource(EguiGlobalSettings {
             auto_create_primary_context: false,
             ..default()
         })
+        .init_resource::<PixelArtSettings>()
         .add_systems(Startup, setup)
-        .add_systems(Update, (rotate_models, swap_glb_materials, sync_pixel_art_camera))
+        .add_systems(Update, (rotate_models, sync_pixel_art_camera))
         .add_systems(EguiPrimaryContextPass, debug_ui)
         .run();
 }
@@ -59,9 +59,6 @@ fn main() {
 #[derive(Component)]
 struct Spinning;
 
-#[derive(Component)]
-struct NeedsMaterialSwap;
-
 #[derive(Component)]
 struct PixelArtCamera;
 
@@ -76,7 +73,7 @@ fn setup(
     mut std_materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    let (palette, palette_count) = default_pixel_art_palette();
+    let (_, palette_count) = default_pixel_art_palette();
 
     let mut canvas_image = Image::new_target_texture(
         RES_WIDTH,
@@ -87,24 +84,22 @@ fn setup(
     canvas_image.sampler = ImageSampler::nearest();
     let image_handle = images.add(canvas_image);
 
-    let make_pixel_mat = |mats: &mut Assets<PixelArtMaterial>, color: Color| {
-        let linear = color.to_linear();
-        mats.add(ExtendedMaterial {
-            base: StandardMaterial {
-                perceptual_roughness: 1.0,
-                reflectance: 0.0,
+    // One shared material; per-object colors come from PixelArtInstanceParams
+    // so all pixel art shapes batch together.
+    let pixel_mat = pixel_materials.add(ExtendedMaterial {
+        base: StandardMaterial {
+            perceptual_roughness: 1.0,
+            reflectance: 0.0,
+            ..default()
+        },
+        extension: PixelArtExtension {
+            params: PixelArtShaderParams {
+                palette_count,
                 ..default()
             },
-            extension: PixelArtExtension {
-                params: PixelArtShaderParams {
-                    base_tint: Vec4::new(linear.red, linear.green, linear.blue, 1.0),
-                    palette_colors: palette,
-                    palette_count,
-                    ..default()
-                },
-            },
-        })
-    };
+            ..default()
+        },
+    });
 
     let sphere_mesh = meshes.add(Sphere::new(1.0).mesh().ico(5).unwrap());
     let cube_mesh = meshes.add(Cuboid::new(1.5, 1.5, 1.5));
@@ -152,13 +147,13 @@ fn setup(
 
     let holdout_mat = holdout_materials.add(ExtendedMaterial {
         base: StandardMaterial::default(),
-        extension: HoldoutExtension {},
+        extension: HoldoutExtension::default(),
     });
     commands.spawn((
         Name::new("Holdout Ground"),
         Mesh3d(ground_mesh),
         MeshMaterial3d(holdout_mat),
-        PIXEL_ART_LAYER,
+        PixelLayerOnly,
     ));
 
     // Sphere cluster — far left
@@ -177,10 +172,11 @@ fn setup(
         commands.spawn((
             Name::new(format!("Sphere {i}")),
             Mesh3d(sphere_mesh.clone()),
-            MeshMaterial3d(make_pixel_mat(&mut pixel_materials, *color)),
+            MeshMaterial3d(pixel_mat.clone()),
+            PixelArtInstanceParams::tint(*color),
             Transform::from_translation(*pos + cluster_offset).with_scale(Vec3::splat(*scale)),
             Spinning,
-            PIXEL_ART_LAYER,
+            PixelLayerOnly,
         ));
     }
 
@@ -194,10 +190,11 @@ fn setup(
         commands.spawn((
             Name::new(*name),
             Mesh3d(mesh.clone()),
-            MeshMaterial3d(make_pixel_mat(&mut pixel_materials, *color)),
+            MeshMaterial3d(pixel_mat.clone()),
+            PixelArtInstanceParams::tint(*color),
             Transform::from_translation(*pos),
             Spinning,
-            PIXEL_ART_LAYER,
+            PixelLayerOnly,
         ));
     }
 
@@ -211,7 +208,7 @@ fn setup(
             ..default()
         },
         Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.5, 0.0)),
-        RenderLayers::layer(0).with(1),
+        PixelArtLayers::default().both(),
     ));
 
     // ================================================================
@@ -231,13 +228,8 @@ fn setup(
         RenderTarget::Image(image_handle.clone().into()),
         Msaa::Off,
         cam_transform,
-        PIXEL_ART_LAYER,
-        EdgeDetection {
-            operator: EdgeOperator::PixelArt,
-            silhouette_color: Some(Color::srgba(0.0, 0.0, 0.0, 0.9)),
-            crease_color: Some(Color::srgba(0.0, 0.0, 0.0, 0.9)),
-            ..default()
-        },
+        PixelLayerOnly,
+        PixelArtOutline::default(),
         PixelArtCamera,
         LowResPixelArtCamera,
     ));
@@ -255,6 +247,7 @@ fn setup(
         PixelArtCompositor {
             lowres_image: image_handle.clone(),
             depth_bias: 0.01,
+            ..default()
         },
         PanOrbitCamera {
             focus: Vec3::new(3.0, 0.5, -1.5),
@@ -288,76 +281,16 @@ fn rotate_models(time: Res<Time>, mut q: Query<&mut Transform, With<Spinning>>)
     }
 }
 
-fn swap_glb_materials(
-    mut commands: Commands,
-    query: Query<(Entity, &Children), With<NeedsMaterialSwap>>,
-    children_query: Query<&Children>,
-    mat_query: Query<&MeshMaterial3d<StandardMaterial>>,
-    mut pixel_materials: ResMut<Assets<PixelArtMaterial>>,
-) {
-    let (palette, palette_count) = default_pixel_art_palette();
-
-    for (entity, top_children) in query.iter() {
-        let mut swapped_any = false;
-        let mut stack: Vec<Entity> = top_children.iter().collect();
-        while let Some(child) = stack.pop() {
-            if mat_query.get(child).is_ok() {
-                let tint = Color::srgb(0.8, 0.5, 0.2);
-                let linear = tint.to_linear();
-                commands
-                    .entity(child)
-                    .remove::<MeshMaterial3d<StandardMaterial>>()
-                    .insert((
-                        MeshMaterial3d(pixel_materials.add(ExtendedMaterial {
-                            base: StandardMaterial {
-                                perceptual_roughness: 1.0,
-                                reflectance: 0.0,
-                                ..default()
-                            },
-                            extension: PixelArtExtension {
-                                params: PixelArtShaderParams {
-                                    base_tint: Vec4::new(
-                                        linear.red,
-                                        linear.green,
-                                        linear.blue,
-                                        1.0,
-                                    ),
-                                    palette_colors: palette,
-                                    palette_count,
-                                    ..default()
-                                },
-                            },
-                        })),
-                        PIXEL_ART_LAYER,
-                    ));
-                swapped_any = true;
-            }
-            if let Ok(grandchildren) = children_query.get(child) {
-                stack.extend(grandchildren.iter());
-            }
-        }
-        if swapped_any {
-            commands.entity(entity).remove::<NeedsMaterialSwap>();
-        }
-    }
-}
 
 // ============================================================================
 //  Debug UI
 // ============================================================================
 
-const STAGE_LABELS: &[&str] = &[
-    "0: Full Pipeline",
-    "1: PBR Only",
-    "2: PBR + Toon",
-    "3: PBR + Toon + Palette",
-    "4: PBR + Toon + Palette + Dither",
-];
-
 fn debug_ui(
     mut contexts: EguiContexts,
-    mut pixel_materials: ResMut<Assets<PixelArtMaterial>>,
-    mut edge_q: Query<&mut EdgeDetection, With<PixelArtCamera>>,
+    mut pixel_materials: PixelArtMaterials,
+    mut settings: ResMut<PixelArtSettings>,
+    mut outline_q: Query<&mut PixelArtOutline, With<PixelArtCamera>>,
     mut compositor_q: Query<&mut PixelArtCompositor, With<WindowCamera>>,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else {
@@ -371,34 +304,24 @@ fn debug_ui(
         .show(ctx, |ui| {
             ui.heading("Pipeline Stage");
 
-            let handles: Vec<_> = pixel_materials
-                .iter()
-                .map(|(id, _)| AssetId::from(id))
-                .collect();
-
-            let current_stage = handles
+            let current_stage = pixel_materials
                 .first()
-                .and_then(|id| pixel_materials.get(*id))
-                .map(|m| m.extension.params.debug_stage)
-                .unwrap_or(0);
+                .and_then(|p| DebugStage::from_u32(p.debug_stage))
+                .unwrap_or_default();
 
             let mut selected = current_stage;
-            for (i, label) in STAGE_LABELS.iter().enumerate() {
-                ui.radio_value(&mut selected, i as u32, *label);
+            for stage in DebugStage::ALL {
+                let label = format!("{}: {}", stage as u32, stage.label());
+                ui.radio_value(&mut selected, stage, label);
             }
             if selected != current_stage {
-                for id in &handles {
-                    if let Some(mat) = pixel_materials.get_mut(*id) {
-                        mat.extension.params.debug_stage = selected;
-                    }
-                }
+                pixel_materials.update_all(|p| p.debug_stage = selected.into());
             }
 
-            if let Ok(mut ed) = edge_q.single_mut() {
-                let mut edges_on = ed.enable_depth || ed.enable_normal;
+            if let Ok(mut outline) = outline_q.single_mut() {
+                let mut edges_on = outline.color.alpha() > 0.0;
                 if ui.checkbox(&mut edges_on, "Edge Detection Outlines").changed() {
-                    ed.enable_depth = edges_on;
-                    ed.enable_normal = edges_on;
+                    outline.color.set_alpha(if edges_on { 0.9 } else { 0.0 });
                 }
             }
 
@@ -413,183 +336,65 @@ fn debug_ui(
             ui.separator();
 
             ui.collapsing("Pixel Art Params", |ui| {
-                if let Some(first_id) = handles.first() {
-                    let params = pixel_materials
-                        .get(*first_id)
-                        .unwrap()
-                        .extension
-                        .params
-                        .clone();
-
-                    let mut toon_bands = params.toon_bands;
-                    let mut toon_softness = params.toon_softness;
-                    let mut toon_shadow_floor = params.toon_shadow_floor;
-                    let mut dither_density = params.dither_density;
-                    let mut palette_strength = params.palette_strength;
-                    let mut dither_strength = params.dither_strength;
+                if let Some(params) = pixel_materials.first().cloned() {
+                    let mut edited = settings.clone();
                     let mut palette_count = params.palette_count;
 
-                    let mut changed = false;
-                    changed |= ui
-                        .add(egui::Slider::new(&mut toon_bands, 1.0..=10.0).text("Bands"))
-                        .changed();
-                    changed |= ui
-                        .add(egui::Slider::new(&mut toon_softness, 0.0..=0.5).text("Softness"))
-                        .changed();
-                    changed |= ui
-                        .add(
-                            egui::Slider::new(&mut toon_shadow_floor, 0.0..=1.0)
-                                .text("Shadow Floor"),
-                        )
-                        .changed();
+                    ui.add(egui::Slider::new(&mut edited.toon_bands, 1.0..=10.0).text("Bands"));
+                    ui.add(
+                        egui::Slider::new(&mut edited.toon_softness, 0.0..=0.5).text("Softness"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut edited.toon_shadow_floor, 0.0..=1.0)
+                            .text("Shadow Floor"),
+                    );
                     ui.separator();
-                    changed |= ui
+                    if ui
                         .add(
                             egui::Slider::new(&mut palette_count, 0..=64).text("Palette Colors"),
                         )
-                        .changed();
-                    changed |= ui
-                        .add(
-                            egui::Slider::new(&mut palette_strength, 0.0..=1.0)
-                                .text("Palette Strength"),
-                        )
-                        .changed();
-                    changed |= ui
-                        .add(
-                            egui::Slider::new(&mut dither_density, 1.0..=32.0)
-                                .text("Dither Density"),
-                        )
-                        .changed();
-                    changed |= ui
-                        .add(
-                            egui::Slider::new(&mut dither_strength, 0.0..=1.0)
-                                .text("Dither Strength"),
-                        )
-                        .changed();
-
-                    if changed {
-                        for id in &handles {
-                            if let Some(mat) = pixel_materials.get_mut(*id) {
-                                mat.extension.params.toon_bands = toon_bands;
-                                mat.extension.params.toon_softness = toon_softness;
-                                mat.extension.params.toon_shadow_floor = toon_shadow_floor;
-                                mat.extension.params.dither_density = dither_density;
-                                mat.extension.params.palette_strength = palette_strength;
-                                mat.extension.params.dither_strength = dither_strength;
-                                mat.extension.params.palette_count = palette_count;
-                            }
-                        }
+                        .changed()
+                    {
+                        pixel_materials.update_all(|p| p.palette_count = palette_count);
                     }
-                }
-            });
-
-            ui.collapsing("Edge Detection Params", |ui| {
-                if let Ok(mut ed) = edge_q.single_mut() {
-                    ui.horizontal(|ui| {
-                        ui.label("Operator:");
-                        if ui
-                            .selectable_label(
-                                ed.operator == EdgeOperator::Sobel,
-                                "Sobel",
-                            )
-                            .clicked()
-                        {
-                            ed.operator = EdgeOperator::Sobel;
-                        }
-                        if ui
-                            .selectable_label(
-                                ed.operator == EdgeOperator::RobertsCross,
-                                "Roberts",
-                            )
-                            .clicked()
-                        {
-                            ed.operator = EdgeOperator::RobertsCross;
-                        }
-                        if ui
-                            .selectable_label(
-                                ed.operator == EdgeOperator::PixelArt,
-                                "PixelArt",
-                            )
-                            .clicked()
-                        {
-                            ed.operator = EdgeOperator::PixelArt;
-                        }
-                    });
-                    ui.separator();
-                    ui.add(
-                        egui::Slider::new(&mut ed.depth_threshold, 0.0..=5.0)
-                            .text("Depth Threshold"),
-                    );
-                    ui.add(
-                        egui::Slider::new(&mut ed.normal_threshold, 0.0..=2.0)
-                            .text("Normal Threshold"),
-                    );
                     ui.add(
-                        egui::Slider::new(&mut ed.depth_thickness, 0.0..=5.0)
-                            .text("Depth Thickness"),
+                        egui::Slider::new(&mut edited.palette_strength, 0.0..=1.0)
+                            .text("Palette Strength"),
                     );
                     ui.add(
-                        egui::Slider::new(&mut ed.normal_thickness, 0.0..=5.0)
-                            .text("Normal Thickness"),
+                        egui::Slider::new(&mut edited.dither_density, 1.0..=32.0)
+                            .text("Dither Density"),
                     );
                     ui.add(
-                        egui::Slider::new(&mut ed.steep_angle_threshold, 0.0..=1.0)
-                            .text("Steep Threshold"),
-                    );
-                    ui.add(
-                        egui::Slider::new(&mut ed.steep_angle_multiplier, 0.0..=5.0)
-                            .text("Steep Multiplier"),
+                        egui::Slider::new(&mut edited.dither_strength, 0.0..=1.0)
+                            .text("Dither Strength"),
                     );
+
+                    // PixelArtSettings pushes the values to every material.
+                    if edited != *settings {
+                        *settings = edited;
+                    }
+                }
+            });
+
+            ui.collapsing("Outline Params", |ui| {
+                if let Ok(mut outline) = outline_q.single_mut() {
                     ui.add(
-                        egui::Slider::new(&mut ed.block_pixel, 1..=4).text("Block Pixel"),
+                        egui::Slider::new(&mut outline.depth_threshold, 0.0..=0.5)
+                            .text("Depth Threshold"),
                     );
                     ui.add(
-                        egui::Slider::new(&mut ed.flat_rejection_threshold, 0.0..=1.0)
-                            .text("Flat Rejection"),
+                        egui::Slider::new(&mut outline.normal_threshold, 0.0..=2.0)
+                            .text("Normal Threshold"),
                     );
+                    ui.checkbox(&mut outline.snap_to_palette, "Snap To Palette");
 
-                    let mut color = [
-                        ed.edge_color.to_srgba().red,
-                        ed.edge_color.to_srgba().green,
-                        ed.edge_color.to_srgba().blue,
-                    ];
+                    let srgba = outline.color.to_srgba();
+                    let mut color = [srgba.red, srgba.green, srgba.blue];
                     if ui.color_edit_button_rgb(&mut color).changed() {
-                        ed.edge_color = Color::srgb(color[0], color[1], color[2]);
+                        outline.color = Color::srgba(color[0], color[1], color[2], srgba.alpha);
                     }
-                    ui.label("Edge Color");
-
-                    ui.separator();
-                    let fallback = ed.edge_color;
-
-                    let mut use_sil = ed.silhouette_color.is_some();
-                    ui.horizontal(|ui| {
-                        ui.checkbox(&mut use_sil, "");
-                        if use_sil {
-                            let sil = ed.silhouette_color.get_or_insert(fallback);
-                            let mut sc = [sil.to_srgba().red, sil.to_srgba().green, sil.to_srgba().blue];
-                            if ui.color_edit_button_rgb(&mut sc).changed() {
-                                *sil = Color::srgb(sc[0], sc[1], sc[2]);
-                            }
-                        } else {
-                            ed.silhouette_color = None;
-                        }
-                        ui.label("Silhouette Color");
-                    });
-
-                    let mut use_cre = ed.crease_color.is_some();
-                    ui.horizontal(|ui| {
-                        ui.checkbox(&mut use_cre, "");
-                        if use_cre {
-                            let cre = ed.crease_color.get_or_insert(fallback);
-                            let mut cc = [cre.to_srgba().red, cre.to_srgba().green, cre.to_srgba().blue];
-                            if ui.color_edit_button_rgb(&mut cc).changed() {
-                                *cre = Color::srgb(cc[0], cc[1], cc[2]);
-                            }
-                        } else {
-                            ed.crease_color = None;
-                        }
-                        ui.label("Crease Color");
-                    });
+                    ui.label("Outline Color");
                 }
             });
         });
@@ -0,0 +1,30 @@
+//! Outdoor showcase scene built from `bevy_pixel_art_shader::showcase`.
+//!
+//! Run:  cargo run --example outdoor --features showcase
+
+use bevy::prelude::*;
+use bevy_pixel_art_shader::showcase::{Showcase, ShowcasePlugin};
+use bevy_pixel_art_shader::{PixelArtCompositorPlugin, PixelArtShaderPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            PixelArtShaderPlugin::default(),
+            PixelArtCompositorPlugin,
+            ShowcasePlugin,
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut showcase: Showcase) {
+    showcase.spawn_cameras(
+        UVec2::new(320, 180),
+        Transform::from_xyz(10.0, 8.0, 14.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Vec3::ZERO,
+        0.2,
+    );
+    showcase.spawn_sun(8000.0);
+    showcase.spawn_outdoor_scene(Vec3::ZERO);
+}
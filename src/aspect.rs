@@ -0,0 +1,96 @@
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::render_resource::Extent3d;
+use bevy::window::{PrimaryWindow, WindowResized};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Keeps the low-res camera's target at the primary window's aspect ratio.
+///
+/// The compositor stretches the low-res image over the whole window, so a
+/// fixed 320×180 target distorts pixels on non-16:9 windows. With this
+/// component the target keeps `height` rows and its width follows the window
+/// (divided by `pixel_aspect`), re-sized whenever the window is. Do not combine
+/// it with `PhysicalPixelSize`, which sizes the same target.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LowResAspect {
+    /// Target height in low-res pixels (default: 180).
+    pub height: u32,
+    /// Width / height of one low-res pixel on screen (default: 1.0, square).
+    pub pixel_aspect: f32,
+    /// Upper bound for the derived width (default: 1920).
+    pub max_width: u32,
+}
+
+impl Default for LowResAspect {
+    fn default() -> Self {
+        Self {
+            height: 180,
+            pixel_aspect: 1.0,
+            max_width: 1920,
+        }
+    }
+}
+
+impl LowResAspect {
+    /// `height` rows of square pixels.
+    pub fn from_height(height: u32) -> Self {
+        Self {
+            height,
+            ..default()
+        }
+    }
+
+    /// Target resolution for a window of `window_size` physical pixels.
+    pub fn resolution_for(&self, window_size: UVec2) -> UVec2 {
+        let height = self.height.max(1);
+        let aspect = window_size.x.max(1) as f32 / window_size.y.max(1) as f32;
+        let width = (height as f32 * aspect / self.pixel_aspect.max(1e-3)).round() as u32;
+        UVec2::new(width.clamp(1, self.max_width.max(1)), height)
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Resizes the target of every `LowResAspect` camera when the primary window
+/// is resized or the component changes.
+pub fn track_window_aspect(
+    mut resized: MessageReader<WindowResized>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut images: ResMut<Assets<Image>>,
+    cameras: Query<(Ref<LowResAspect>, &RenderTarget)>,
+) {
+    let window_resized = resized.read().count() > 0;
+    let Ok(window) = window.single() else {
+        return;
+    };
+
+    for (aspect, target) in &cameras {
+        if !window_resized && !aspect.is_changed() {
+            continue;
+        }
+        let RenderTarget::Image(target) = target else {
+            continue;
+        };
+
+        let size = aspect.resolution_for(window.physical_size());
+        let Some(image) = images.get(&target.handle) else {
+            continue;
+        };
+        if image.size() == size {
+            continue;
+        }
+        if let Some(image) = images.get_mut(&target.handle) {
+            image.resize(Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            });
+        }
+    }
+}
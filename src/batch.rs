@@ -0,0 +1,110 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::{PixelArtExtension, PixelArtMaterial, PixelArtShaderParams};
+
+/// System param for editing the parameters of many `PixelArtMaterial`s at once.
+///
+/// Each edit runs the closure on a copy of the params and only writes back —
+/// marking the asset modified exactly once — when something actually changed,
+/// so unchanged materials are never re-uploaded.
+#[derive(SystemParam)]
+pub struct PixelArtMaterials<'w> {
+    materials: ResMut<'w, Assets<PixelArtMaterial>>,
+}
+
+impl PixelArtMaterials<'_> {
+    /// Params of an arbitrary material (useful for seeding UI state).
+    pub fn first(&self) -> Option<&PixelArtShaderParams> {
+        self.materials
+            .iter()
+            .next()
+            .map(|(_, mat)| &mat.extension.params)
+    }
+
+    /// Params of a single material.
+    pub fn get(&self, id: impl Into<AssetId<PixelArtMaterial>>) -> Option<&PixelArtShaderParams> {
+        self.materials.get(id).map(|mat| &mat.extension.params)
+    }
+
+    /// Extension (params, palette, flags) of a single material.
+    pub fn extension(
+        &self,
+        id: impl Into<AssetId<PixelArtMaterial>>,
+    ) -> Option<&PixelArtExtension> {
+        self.materials.get(id).map(|mat| &mat.extension)
+    }
+
+    /// Number of loaded pixel art materials.
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    /// Whether no pixel art materials are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+
+    /// Apply `f` to one material's params.
+    pub fn update(
+        &mut self,
+        id: impl Into<AssetId<PixelArtMaterial>>,
+        f: impl FnOnce(&mut PixelArtShaderParams),
+    ) {
+        let id = id.into();
+        let Some(mat) = self.materials.get(id) else {
+            return;
+        };
+        let mut params = mat.extension.params.clone();
+        f(&mut params);
+        if params != mat.extension.params
+            && let Some(mat) = self.materials.get_mut(id)
+        {
+            mat.extension.params = params;
+        }
+    }
+
+    /// Apply `f` to every material's params.
+    pub fn update_all(&mut self, f: impl FnMut(&mut PixelArtShaderParams)) {
+        self.update_where(|_| true, f);
+    }
+
+    /// Apply `f` to the params of every material whose extension matches `filter`.
+    pub fn update_where(
+        &mut self,
+        mut filter: impl FnMut(&PixelArtExtension) -> bool,
+        mut f: impl FnMut(&mut PixelArtShaderParams),
+    ) {
+        let changed: Vec<_> = self
+            .materials
+            .iter()
+            .filter(|(_, mat)| filter(&mat.extension))
+            .filter_map(|(id, mat)| {
+                let mut params = mat.extension.params.clone();
+                f(&mut params);
+                (params != mat.extension.params).then_some((id, params))
+            })
+            .collect();
+
+        for (id, params) in changed {
+            if let Some(mat) = self.materials.get_mut(id) {
+                mat.extension.params = params;
+            }
+        }
+    }
+
+    /// Mark every material whose extension matches `filter` as modified without
+    /// changing it, forcing its bind group to be rebuilt (e.g. after one of its
+    /// buffers was re-uploaded).
+    pub fn touch_where(&mut self, mut filter: impl FnMut(&PixelArtExtension) -> bool) {
+        let ids: Vec<_> = self
+            .materials
+            .iter()
+            .filter(|(_, mat)| filter(&mat.extension))
+            .map(|(id, _)| id)
+            .collect();
+        for id in ids {
+            self.materials.get_mut(id);
+        }
+    }
+}
@@ -0,0 +1,501 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{
+            Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel,
+        },
+        render_resource::{
+            binding_types::{sampler, texture_2d, uniform_buffer},
+            *,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+    },
+};
+
+use crate::pass_order::order_low_res_passes;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Low-res-native bloom for the pixel art camera.
+///
+/// Runs on the camera's own (e.g. 320×180) target with one or two half-res
+/// mips, upsampled with nearest filtering and optionally stepped into bands,
+/// so halos stay blocky instead of the smooth glow of full-res bloom. Put it
+/// on the `LowResPixelArtCamera` instead of Bevy's `Bloom`.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtBloom {
+    /// Luminance above which pixels bloom. With a quantized palette this picks
+    /// which palette entries glow (default: 0.8).
+    pub threshold: f32,
+    /// Bloom strength added back onto the image (default: 0.6).
+    pub intensity: f32,
+    /// Number of half-res mips, 1 or 2 (default: 2).
+    pub mip_levels: u32,
+    /// Quantize the bloom contribution into this many steps (0 = smooth, default: 3).
+    pub bands: f32,
+}
+
+impl Default for PixelArtBloom {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            intensity: 0.6,
+            mip_levels: 2,
+            bands: 3.0,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct PixelArtBloomUniform {
+    pub threshold: f32,
+    pub intensity: f32,
+    pub bands: f32,
+    pub mip_levels: u32,
+}
+
+impl ExtractComponent for PixelArtBloomUniform {
+    type QueryData = &'static PixelArtBloom;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        bloom: bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        Some(PixelArtBloomUniform {
+            threshold: bloom.threshold,
+            intensity: bloom.intensity,
+            bands: bloom.bands,
+            mip_levels: bloom.mip_levels.clamp(1, 2),
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtBloomPlugin;
+
+impl Plugin for PixelArtBloomPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "bloom_downsample.wgsl");
+        embedded_asset!(app, "bloom_composite.wgsl");
+
+        app.register_type::<PixelArtBloom>();
+        app.add_plugins((
+            ExtractComponentPlugin::<PixelArtBloomUniform>::default(),
+            UniformComponentPlugin::<PixelArtBloomUniform>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<BloomPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_bloom_pipelines.in_set(RenderSystems::Prepare),
+                    prepare_bloom_textures.in_set(RenderSystems::PrepareResources),
+                ),
+            )
+            .add_render_graph_node::<BloomNode>(Core3d, PixelArtBloomLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (Node3d::Tonemapping, PixelArtBloomLabel, Node3d::Fxaa),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp).init_resource::<BloomPipeline>();
+        order_low_res_passes(app);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view half-res and quarter-res bloom mips.
+#[derive(Component)]
+pub struct BloomTextures {
+    pub mip1: CachedTexture,
+    pub mip2: CachedTexture,
+}
+
+/// Per-view cached pipeline ids.
+#[derive(Component, Clone, Copy)]
+pub struct BloomPipelineIds {
+    pub first_downsample: CachedRenderPipelineId,
+    pub downsample: CachedRenderPipelineId,
+    pub composite: CachedRenderPipelineId,
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtBloomLabel;
+
+/// Format of the intermediate bloom mips.
+const BLOOM_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct BloomPipeline {
+    pub downsample_shader: Handle<Shader>,
+    pub composite_shader: Handle<Shader>,
+    pub linear_sampler: Sampler,
+    pub nearest_sampler: Sampler,
+    pub downsample_layout: BindGroupLayoutDescriptor,
+    pub composite_layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for BloomPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let downsample_shader = load_embedded_asset!(world, "bloom_downsample.wgsl");
+        let composite_shader = load_embedded_asset!(world, "bloom_composite.wgsl");
+
+        let downsample_layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_bloom: downsample_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: input
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: linear sampler
+                    sampler(SamplerBindingType::Filtering),
+                    // 2: bloom uniform
+                    uniform_buffer::<PixelArtBloomUniform>(true),
+                ),
+            ),
+        );
+
+        let composite_layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_bloom: composite_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: half-res mip
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 2: quarter-res mip
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 3: nearest sampler
+                    sampler(SamplerBindingType::NonFiltering),
+                    // 4: bloom uniform
+                    uniform_buffer::<PixelArtBloomUniform>(true),
+                ),
+            ),
+        );
+
+        let render_device = world.resource::<RenderDevice>();
+        let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("pixel_art_bloom linear sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+        let nearest_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("pixel_art_bloom nearest sampler"),
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..default()
+        });
+
+        Self {
+            downsample_shader,
+            composite_shader,
+            linear_sampler,
+            nearest_sampler,
+            downsample_layout,
+            composite_layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BloomKey {
+    /// Downsample into a bloom mip; `first` applies the brightness threshold.
+    Downsample { first: bool },
+    /// Add the mips back onto the view target.
+    Composite { hdr: bool },
+}
+
+impl SpecializedRenderPipeline for BloomPipeline {
+    type Key = BloomKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (label, layout, shader, shader_defs, format) = match key {
+            BloomKey::Downsample { first } => (
+                "pixel_art_bloom: downsample_pipeline",
+                self.downsample_layout.clone(),
+                self.downsample_shader.clone(),
+                if first {
+                    vec!["FIRST_DOWNSAMPLE".into()]
+                } else {
+                    vec![]
+                },
+                BLOOM_TEXTURE_FORMAT,
+            ),
+            BloomKey::Composite { hdr } => (
+                "pixel_art_bloom: composite_pipeline",
+                self.composite_layout.clone(),
+                self.composite_shader.clone(),
+                vec![],
+                if hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
+                } else {
+                    TextureFormat::bevy_default()
+                },
+            ),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some(label.into()),
+            layout: vec![layout],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader,
+                shader_defs,
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare systems
+// ──────────────────────────────────────────────
+
+pub fn prepare_bloom_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BloomPipeline>>,
+    bloom_pipeline: Res<BloomPipeline>,
+    query: Query<(Entity, &ViewTarget), With<PixelArtBloomUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let mut specialize =
+            |key| pipelines.specialize(&pipeline_cache, &bloom_pipeline, key);
+        let ids = BloomPipelineIds {
+            first_downsample: specialize(BloomKey::Downsample { first: true }),
+            downsample: specialize(BloomKey::Downsample { first: false }),
+            composite: specialize(BloomKey::Composite {
+                hdr: view_target.is_hdr(),
+            }),
+        };
+        commands.entity(entity).insert(ids);
+    }
+}
+
+pub fn prepare_bloom_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(Entity, &ExtractedCamera), With<PixelArtBloomUniform>>,
+) {
+    for (entity, camera) in &query {
+        let Some(size) = camera.physical_viewport_size else {
+            continue;
+        };
+
+        let mut mip = |label: &'static str, divisor: u32| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size: Extent3d {
+                        width: (size.x / divisor).max(1),
+                        height: (size.y / divisor).max(1),
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: BLOOM_TEXTURE_FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            )
+        };
+
+        let textures = BloomTextures {
+            mip1: mip("pixel_art_bloom mip 1", 2),
+            mip2: mip("pixel_art_bloom mip 2", 4),
+        };
+        commands.entity(entity).insert(textures);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct BloomNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static BloomTextures,
+        &'static BloomPipelineIds,
+        &'static PixelArtBloomUniform,
+        &'static DynamicUniformIndex<PixelArtBloomUniform>,
+    )>,
+}
+
+impl FromWorld for BloomNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for BloomNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.view_entity();
+
+        let Ok((view_target, textures, pipeline_ids, settings, uniform_index)) =
+            self.view_query.get_manual(world, view_entity)
+        else {
+            return Ok(());
+        };
+
+        let bloom_pipeline = world.resource::<BloomPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let (Some(first_downsample), Some(downsample), Some(composite)) = (
+            pipeline_cache.get_render_pipeline(pipeline_ids.first_downsample),
+            pipeline_cache.get_render_pipeline(pipeline_ids.downsample),
+            pipeline_cache.get_render_pipeline(pipeline_ids.composite),
+        ) else {
+            return Ok(());
+        };
+
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<PixelArtBloomUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+        let downsample_layout =
+            pipeline_cache.get_bind_group_layout(&bloom_pipeline.downsample_layout);
+
+        // Downsample passes: source → mip1 (thresholded) → mip2.
+        let mut passes = vec![(first_downsample, post_process.source, &textures.mip1)];
+        if settings.mip_levels >= 2 {
+            passes.push((downsample, &textures.mip1.default_view, &textures.mip2));
+        }
+        for (pipeline, input, target) in passes {
+            let bind_group = render_context.render_device().create_bind_group(
+                "pixel_art_bloom_downsample_bind_group",
+                &downsample_layout,
+                &BindGroupEntries::sequential((
+                    input,
+                    &bloom_pipeline.linear_sampler,
+                    uniform_binding.clone(),
+                )),
+            );
+
+            let mut render_pass =
+                render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                    label: Some("pixel_art_bloom_downsample_pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: &target.default_view,
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // Composite: source + nearest-upsampled mips → destination.
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_bloom_composite_bind_group",
+            &pipeline_cache.get_bind_group_layout(&bloom_pipeline.composite_layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &textures.mip1.default_view,
+                &textures.mip2.default_view,
+                &bloom_pipeline.nearest_sampler,
+                uniform_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_bloom_composite_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(composite);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+//! Pixel art bloom composite: adds the nearest-upsampled bloom mips back onto
+//! the low-res image, optionally stepped into bands so halos stay blocky.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+@group(0) @binding(1) var bloom_mip1: texture_2d<f32>;
+@group(0) @binding(2) var bloom_mip2: texture_2d<f32>;
+@group(0) @binding(3) var nearest_sampler: sampler;
+
+struct BloomSettings {
+    threshold: f32,
+    intensity: f32,
+    bands: f32,
+    mip_levels: u32,
+}
+@group(0) @binding(4) var<uniform> settings: BloomSettings;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(source_color, nearest_sampler, in.uv);
+    let mip1 = textureSample(bloom_mip1, nearest_sampler, in.uv).rgb;
+    let mip2 = textureSample(bloom_mip2, nearest_sampler, in.uv).rgb;
+
+    var bloom = mip1;
+    if (settings.mip_levels >= 2u) {
+        bloom = (mip1 + mip2) * 0.5;
+    }
+    bloom *= settings.intensity;
+
+    // Stepped halo: quantize bloom brightness into bands.
+    if (settings.bands > 0.0) {
+        let peak = max(bloom.r, max(bloom.g, bloom.b));
+        if (peak > 0.0) {
+            let stepped = floor(peak * settings.bands + 0.5) / settings.bands;
+            bloom *= stepped / peak;
+        }
+    }
+
+    // Halos over the transparent clear color must stay visible to the compositor.
+    let halo_alpha = clamp(max(bloom.r, max(bloom.g, bloom.b)), 0.0, 1.0);
+    return vec4<f32>(base.rgb + bloom, max(base.a, halo_alpha));
+}
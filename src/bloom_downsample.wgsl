@@ -0,0 +1,35 @@
+//! Pixel art bloom downsample: 4-tap box filter into a half-size mip.
+//! The first downsample also applies the brightness threshold.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var input_texture: texture_2d<f32>;
+@group(0) @binding(1) var linear_sampler: sampler;
+
+struct BloomSettings {
+    threshold: f32,
+    intensity: f32,
+    bands: f32,
+    mip_levels: u32,
+}
+@group(0) @binding(2) var<uniform> settings: BloomSettings;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Four bilinear taps at ±1 texel cover a 4x4 box of the input.
+    let texel = 1.0 / vec2<f32>(textureDimensions(input_texture));
+    var sum = vec3<f32>(0.0);
+    sum += textureSample(input_texture, linear_sampler, in.uv + vec2<f32>(-1.0, -1.0) * texel).rgb;
+    sum += textureSample(input_texture, linear_sampler, in.uv + vec2<f32>( 1.0, -1.0) * texel).rgb;
+    sum += textureSample(input_texture, linear_sampler, in.uv + vec2<f32>(-1.0,  1.0) * texel).rgb;
+    sum += textureSample(input_texture, linear_sampler, in.uv + vec2<f32>( 1.0,  1.0) * texel).rgb;
+    var color = sum * 0.25;
+
+#ifdef FIRST_DOWNSAMPLE
+    // Bright pass: keep only the part of each pixel above the threshold.
+    let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color *= max(luminance - settings.threshold, 0.0) / max(luminance, 1e-4);
+#endif
+
+    return vec4<f32>(color, 1.0);
+}
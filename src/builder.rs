@@ -0,0 +1,234 @@
+use bevy::pbr::ExtendedMaterial;
+use bevy::prelude::*;
+use bevy::render::storage::ShaderStorageBuffer;
+
+use crate::{
+    DebugStage, DitherAnchor, PixelArtExtension, PixelArtMaterial, PixelArtShaderParams,
+    QuantizationSpace, ShadowEdge, WindWeight,
+};
+
+// ──────────────────────────────────────────────
+//  Params
+// ──────────────────────────────────────────────
+
+impl PixelArtShaderParams {
+    /// Default params, for chaining `with_*` calls.
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Set `base_tint` from a color.
+    pub fn with_tint(mut self, color: Color) -> Self {
+        self.base_tint = color.to_linear().to_vec4();
+        self
+    }
+
+    /// Set `toon_bands`.
+    pub fn with_toon_bands(mut self, bands: f32) -> Self {
+        self.toon_bands = bands;
+        self
+    }
+
+    /// Set `toon_softness`.
+    pub fn with_toon_softness(mut self, softness: f32) -> Self {
+        self.toon_softness = softness;
+        self
+    }
+
+    /// Set `toon_shadow_floor`.
+    pub fn with_shadow_floor(mut self, floor: f32) -> Self {
+        self.toon_shadow_floor = floor;
+        self
+    }
+
+    /// Set `dither_strength`.
+    pub fn with_dither_strength(mut self, strength: f32) -> Self {
+        self.dither_strength = strength;
+        self
+    }
+
+    /// Set `dither_density`.
+    pub fn with_dither_density(mut self, density: f32) -> Self {
+        self.dither_density = density;
+        self
+    }
+
+    /// Set `bayer_size` (2, 4 or 8).
+    pub fn with_bayer_size(mut self, size: u32) -> Self {
+        self.bayer_size = size;
+        self
+    }
+
+    /// Set `dither_anchor`.
+    pub fn with_dither_anchor(mut self, anchor: DitherAnchor) -> Self {
+        self.dither_anchor = anchor as u32;
+        self
+    }
+
+    /// Set `palette_strength`.
+    pub fn with_palette_strength(mut self, strength: f32) -> Self {
+        self.palette_strength = strength;
+        self
+    }
+
+    /// Set `palette_count`.
+    pub fn with_palette_count(mut self, count: u32) -> Self {
+        self.palette_count = count;
+        self
+    }
+
+    /// Set `quantization_space`.
+    pub fn with_quantization_space(mut self, space: QuantizationSpace) -> Self {
+        self.quantization_space = space as u32;
+        self
+    }
+
+    /// Set `shadow_edge`.
+    pub fn with_shadow_edge(mut self, edge: ShadowEdge) -> Self {
+        self.shadow_edge = edge as u32;
+        self
+    }
+
+    /// Set `outline_participation` (0.0 opts out of edge detection).
+    pub fn with_outline_participation(mut self, weight: f32) -> Self {
+        self.outline_participation = weight;
+        self
+    }
+
+    /// Set `debug_stage`.
+    pub fn with_debug_stage(mut self, stage: DebugStage) -> Self {
+        self.debug_stage = stage as u32;
+        self
+    }
+
+    /// Set `rim_strength`, `rim_width` and `rim_color`.
+    pub fn with_rim(mut self, strength: f32, width: f32, color: Color) -> Self {
+        self.rim_strength = strength;
+        self.rim_width = width;
+        self.rim_color = color.to_linear().to_vec4();
+        self
+    }
+
+    /// Set `wind_strength`, `wind_frequency` and `wind_weight`.
+    pub fn with_wind(mut self, strength: f32, frequency: f32, weight: WindWeight) -> Self {
+        self.wind_strength = strength;
+        self.wind_frequency = frequency;
+        self.wind_weight = weight as u32;
+        self
+    }
+
+    /// Set `flipbook_tiles_x`, `flipbook_tiles_y` and `flipbook_fps`.
+    pub fn with_flipbook(mut self, tiles_x: u32, tiles_y: u32, fps: f32) -> Self {
+        self.flipbook_tiles_x = tiles_x;
+        self.flipbook_tiles_y = tiles_y;
+        self.flipbook_fps = fps;
+        self
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Extension
+// ──────────────────────────────────────────────
+
+impl PixelArtExtension {
+    /// Extension with `params` and the default palette.
+    pub fn new(params: PixelArtShaderParams) -> Self {
+        Self {
+            params,
+            ..default()
+        }
+    }
+
+    /// Bind `palette` (see `PixelArtPalette::to_storage_buffer`) with `count`
+    /// active colors.
+    pub fn with_palette(mut self, palette: Handle<ShaderStorageBuffer>, count: u32) -> Self {
+        self.palette = palette;
+        self.params.palette_count = count;
+        self
+    }
+
+    /// Set the sub-material mask texture.
+    pub fn with_sub_material_mask(mut self, mask: Handle<Image>) -> Self {
+        self.sub_material_mask = Some(mask);
+        self
+    }
+
+    /// Keep this material out of `PixelArtSettings` syncing.
+    pub fn ignoring_settings(mut self) -> Self {
+        self.ignore_settings = true;
+        self
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Material constructors
+// ──────────────────────────────────────────────
+
+/// Constructors for `PixelArtMaterial` (a type alias of Bevy's
+/// `ExtendedMaterial`, so they live on a trait; it is in the prelude).
+pub trait PixelArtMaterialExt {
+    /// Matte material (roughness 1, no specular) with `color` as base color
+    /// and default pixel art params.
+    fn simple(color: Color) -> Self;
+
+    /// Unlit particle / VFX material: `color` goes straight to palette and
+    /// dither without PBR lighting, alpha-blended, or added onto the scene
+    /// when `additive`. Add `NotShadowCaster` to the entity to keep it out of
+    /// shadow maps.
+    fn particle(color: Color, additive: bool) -> Self;
+
+    /// Pixel art material on top of an existing `StandardMaterial`, with
+    /// default pixel art params.
+    fn from_standard(base: StandardMaterial) -> Self;
+
+    /// Replace the pixel art params.
+    fn with_params(self, params: PixelArtShaderParams) -> Self;
+
+    /// Replace the whole extension (params, palette, mask).
+    fn with_extension(self, extension: PixelArtExtension) -> Self;
+}
+
+impl PixelArtMaterialExt for PixelArtMaterial {
+    fn simple(color: Color) -> Self {
+        Self::from_standard(StandardMaterial {
+            base_color: color,
+            perceptual_roughness: 1.0,
+            reflectance: 0.0,
+            ..default()
+        })
+    }
+
+    fn particle(color: Color, additive: bool) -> Self {
+        Self::from_standard(StandardMaterial {
+            base_color: color,
+            unlit: true,
+            alpha_mode: if additive {
+                AlphaMode::Add
+            } else {
+                AlphaMode::Blend
+            },
+            ..default()
+        })
+        .with_params(PixelArtShaderParams {
+            unlit: 1,
+            ..default()
+        })
+    }
+
+    fn from_standard(base: StandardMaterial) -> Self {
+        ExtendedMaterial {
+            base,
+            extension: PixelArtExtension::default(),
+        }
+    }
+
+    fn with_params(mut self, params: PixelArtShaderParams) -> Self {
+        self.extension.params = params;
+        self
+    }
+
+    fn with_extension(mut self, extension: PixelArtExtension) -> Self {
+        self.extension = extension;
+        self
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::PixelArtPalette;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Letterbox bars drawn into the low-res target.
+///
+/// Put it on the low-res camera. Two UI bars targeting that camera are
+/// rendered at its resolution, so they sit on the pixel grid and get upscaled
+/// with the rest of the pixel layer. Bar height animates toward `shown` at
+/// `speed` and is rounded to whole texels while sliding.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CinematicBars {
+    /// Height of each bar in low-res texels when fully shown (default: 22).
+    pub height: u32,
+    /// Bar color; use a palette entry to stay on-palette (default: black).
+    pub color: Color,
+    /// Whether the bars are sliding in (true) or out (false).
+    pub shown: bool,
+    /// Slide speed in full heights per second (0 = instant, default: 2.0).
+    pub speed: f32,
+    /// Current slide progress (0 = hidden, 1 = fully shown).
+    pub progress: f32,
+}
+
+impl Default for CinematicBars {
+    fn default() -> Self {
+        Self {
+            height: 22,
+            color: Color::BLACK,
+            shown: true,
+            speed: 2.0,
+            progress: 0.0,
+        }
+    }
+}
+
+impl CinematicBars {
+    /// Bars in palette entry `index` (falls back to black if out of range).
+    pub fn from_palette(palette: &PixelArtPalette, index: usize, height: u32) -> Self {
+        let color = palette
+            .colors
+            .get(index)
+            .map(|c| Color::linear_rgb(c.x, c.y, c.z))
+            .unwrap_or(Color::BLACK);
+        Self {
+            height,
+            color,
+            ..default()
+        }
+    }
+
+    /// Current bar height in whole texels.
+    pub fn visible_texels(&self) -> u32 {
+        (self.progress.clamp(0.0, 1.0) * self.height as f32).round() as u32
+    }
+}
+
+/// One of the two bar nodes spawned for a `CinematicBars` camera.
+#[derive(Component, Clone, Copy)]
+pub struct CinematicBar {
+    pub camera: Entity,
+    pub top: bool,
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Spawns the bar nodes for new `CinematicBars` cameras and despawns bars
+/// whose camera lost the component.
+pub fn spawn_cinematic_bars(
+    mut commands: Commands,
+    added: Query<Entity, Added<CinematicBars>>,
+    bars: Query<(Entity, &CinematicBar)>,
+    cameras: Query<(), With<CinematicBars>>,
+) {
+    for camera in &added {
+        for top in [true, false] {
+            commands.spawn((
+                Name::new("Cinematic Bar"),
+                CinematicBar { camera, top },
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    top: if top { Val::Px(0.0) } else { Val::Auto },
+                    bottom: if top { Val::Auto } else { Val::Px(0.0) },
+                    height: Val::Px(0.0),
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                GlobalZIndex(i32::MAX),
+                UiTargetCamera(camera),
+            ));
+        }
+    }
+
+    for (entity, bar) in &bars {
+        if cameras.get(bar.camera).is_err() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Advances the slide animation and sizes the bars in whole texels.
+pub fn animate_cinematic_bars(
+    time: Res<Time>,
+    mut cameras: Query<&mut CinematicBars>,
+    mut bars: Query<(&CinematicBar, &mut Node, &mut BackgroundColor)>,
+) {
+    for mut cinematic in &mut cameras {
+        let target = if cinematic.shown { 1.0 } else { 0.0 };
+        if cinematic.progress != target {
+            let step = if cinematic.speed > 0.0 {
+                cinematic.speed * time.delta_secs()
+            } else {
+                1.0
+            };
+            cinematic.progress += (target - cinematic.progress).clamp(-step, step);
+        }
+    }
+
+    for (bar, mut node, mut background) in &mut bars {
+        let Ok(cinematic) = cameras.get(bar.camera) else {
+            continue;
+        };
+        let height = Val::Px(cinematic.visible_texels() as f32);
+        if node.height != height {
+            node.height = height;
+        }
+        if background.0 != cinematic.color {
+            background.0 = cinematic.color;
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::{
     asset::{embedded_asset, load_embedded_asset},
+    camera::RenderTarget,
     core_pipeline::{
         FullscreenShader,
         core_3d::{
@@ -9,6 +10,7 @@ use bevy::{
         prepass::{DepthPrepass, ViewPrepassTextures},
     },
     ecs::query::QueryState,
+    image::ImageSampler,
     prelude::*,
     render::{
         Extract, Render, RenderApp, RenderSystems,
@@ -31,6 +33,10 @@ use bevy::{
     },
 };
 
+use crate::decimate::{
+    self, LowResFrameRate, RetainLowResDepthLabel, RetainLowResDepthNode, RetainedLowResDepth,
+};
+
 // ──────────────────────────────────────────────
 //  Public components
 // ──────────────────────────────────────────────
@@ -41,16 +47,138 @@ use bevy::{
 #[reflect(Component)]
 pub struct LowResPixelArtCamera;
 
+/// Compositing order of a `LowResPixelArtCamera` when a compositor has several
+/// low-res layers. Lower orders are composited first, higher ones over them
+/// (default: 0).
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+#[reflect(Component)]
+pub struct LowResLayer(pub i32);
+
+/// Turns a `LowResPixelArtCamera` into a sub-layer of the primary low-res
+/// camera, for drawing selected entities with chunkier pixels than the rest
+/// of the scene (e.g. a boss at 2× pixel size).
+///
+/// Spawn it as a child of the camera rendering to `lowres_image`, render it to
+/// a smaller `lowres_target` (half the resolution for 2× pixels) listed in
+/// `extra_layers` with a higher `LowResLayer`, and move the selected entities
+/// (and the lights that should reach them) to its own `RenderLayers`. Its
+/// `Projection` follows the parent's, and the compositor depth-tests it
+/// against the primary layer as well as the full-res scene, so coarse
+/// entities sort correctly against the primary layer's geometry.
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component)]
+#[require(LowResPixelArtCamera)]
+pub struct LowResSubLayer;
+
 /// Attach to the full-res camera to enable depth-aware compositing.
 /// Automatically requires `DepthPrepass` on the same entity.
+///
+/// The target image is the link to the low-res camera: the compositor only
+/// composites `LowResPixelArtCamera`s whose `RenderTarget` is `lowres_image`
+/// (or one of `extra_layers`). For split-screen, give each player's low-res
+/// camera its own image and point that player's full-res camera at it.
+///
+/// Compositing is by depth only; the holdout mask in the low-res normal
+/// prepass (`HOLDOUT_MASK_ALPHA`) is read by passes on the low-res camera,
+/// not here.
 #[derive(Component, Clone, Reflect)]
 #[reflect(Component)]
 #[require(DepthPrepass)]
 pub struct PixelArtCompositor {
+    /// Target of the low-res camera to composite.
     pub lowres_image: Handle<Image>,
+    /// Further low-res layers (e.g. a 160×90 background or a 640×360 VFX
+    /// layer), each the target of its own `LowResPixelArtCamera`. All layers,
+    /// `lowres_image` included, are depth-tested against the full-res scene
+    /// separately and composited in `LowResLayer` order.
+    pub extra_layers: Vec<Handle<Image>>,
     /// Depth bias for the lowres vs fullres comparison.
     /// Compensates for precision mismatch between the two depth buffers.
     pub depth_bias: f32,
+    /// Anti-aliasing of the boundary between low-res pixels and the full-res scene.
+    pub feather: CompositorFeather,
+    /// How low-res color is resampled to the screen.
+    pub filter: CompositorFilter,
+    /// Silhouette tint for low-res pixels hidden behind full-res geometry by the
+    /// depth test. Alpha is the tint opacity (0 = hide occluded pixels, default).
+    /// Pick a palette color to keep the silhouette on-palette.
+    pub occluded_tint: Color,
+    /// Extra low-res texels rendered on each side of the visible area (default: 0).
+    /// Applies to every layer, in that layer's texels.
+    /// Render the low-res camera this much larger (target and projection) and
+    /// the compositor crops it back, leaving room for `subpixel_offset`.
+    pub overscan: u32,
+    /// Fractional shift of the low-res image in low-res texels (+x right, +y up
+    /// in camera space). Set from `SnapToPixelGrid::residual` when `overscan`
+    /// is non-zero, so the camera scrolls smoothly while objects stay snapped.
+    pub subpixel_offset: Vec2,
+}
+
+impl Default for PixelArtCompositor {
+    fn default() -> Self {
+        Self {
+            lowres_image: Handle::default(),
+            extra_layers: Vec::new(),
+            depth_bias: 0.01,
+            feather: CompositorFeather::Off,
+            filter: CompositorFilter::Nearest,
+            occluded_tint: Color::NONE,
+            overscan: 0,
+            subpixel_offset: Vec2::ZERO,
+        }
+    }
+}
+
+/// How the compositor treats the edge where the depth test flips between the
+/// low-res layer and the full-res scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum CompositorFeather {
+    /// Hard per-pixel depth cut (stair-steps along slanted full-res geometry).
+    #[default]
+    Off,
+    /// Blend by the low-res coverage of a one-texel footprint.
+    Alpha,
+    /// Same coverage, resolved with a screen-space Bayer pattern (no partial alpha).
+    Dithered,
+}
+
+/// How the compositor resamples low-res color to the screen. Depth is always
+/// sampled nearest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum CompositorFilter {
+    /// Nearest texel. Crisp, but at non-integer scales (e.g. 2.25×) texels
+    /// alternate between 2 and 3 screen pixels wide.
+    #[default]
+    Nearest,
+    /// Nearest inside each texel with a one-screen-pixel bilinear blend at
+    /// texel borders, so every texel looks the same width at any scale.
+    SharpBilinear,
+    /// xBR-style (level 1) edge interpolation: texel corners along diagonal
+    /// edges are cut and filled with the neighboring color, smoothing
+    /// staircases while keeping flat areas and orthogonal edges crisp.
+    Xbr,
+}
+
+/// Render target for a `LowResPixelArtCamera`, with nearest sampling.
+///
+/// `hdr = false` gives an sRGB `Rgba8Unorm` target. `hdr = true` gives
+/// `Rgba16Float` for cameras with `Hdr`: the material keeps overbright values
+/// through palette quantization, so bloom on the low-res camera picks up
+/// emissive pixel art; that camera's `Tonemapping` maps the result (use
+/// `Tonemapping::None` to keep palette colors exact).
+pub fn lowres_target(resolution: UVec2, hdr: bool) -> Image {
+    let mut image = if hdr {
+        Image::new_target_texture(resolution.x, resolution.y, TextureFormat::Rgba16Float, None)
+    } else {
+        Image::new_target_texture(
+            resolution.x,
+            resolution.y,
+            TextureFormat::Rgba8Unorm,
+            Some(TextureFormat::Rgba8UnormSrgb),
+        )
+    };
+    image.sampler = ImageSampler::nearest();
+    image
 }
 
 // ──────────────────────────────────────────────
@@ -60,6 +188,16 @@ pub struct PixelArtCompositor {
 #[derive(Component, Clone, Copy, ShaderType)]
 pub struct CompositorUniform {
     pub depth_bias: f32,
+    /// 0 = off, 1 = alpha, 2 = dithered.
+    pub feather_mode: u32,
+    /// 0 = nearest, 1 = sharp bilinear, 2 = xBR.
+    pub filter_mode: u32,
+    /// Linear RGB tint + opacity for occluded low-res pixels.
+    pub occluded_tint: Vec4,
+    /// Low-res texel shift applied when sampling the low-res layer.
+    pub subpixel_offset: Vec2,
+    /// Low-res texels cropped from each side.
+    pub overscan: f32,
 }
 
 impl ExtractComponent for CompositorUniform {
@@ -72,6 +210,11 @@ impl ExtractComponent for CompositorUniform {
     ) -> Option<Self::Out> {
         Some(CompositorUniform {
             depth_bias: compositor.depth_bias,
+            feather_mode: compositor.feather as u32,
+            filter_mode: compositor.filter as u32,
+            occluded_tint: compositor.occluded_tint.to_linear().to_vec4(),
+            subpixel_offset: compositor.subpixel_offset,
+            overscan: compositor.overscan as f32,
         })
     }
 }
@@ -88,10 +231,21 @@ impl Plugin for PixelArtCompositorPlugin {
 
         app.register_type::<PixelArtCompositor>();
         app.register_type::<LowResPixelArtCamera>();
+        app.register_type::<LowResLayer>();
+        app.register_type::<LowResSubLayer>();
         app.add_plugins((
             ExtractComponentPlugin::<CompositorUniform>::default(),
             UniformComponentPlugin::<CompositorUniform>::default(),
         ));
+        app.register_type::<LowResFrameRate>();
+        app.add_systems(
+            PostUpdate,
+            (
+                disable_compositor_msaa,
+                sync_sub_layer_projection,
+                decimate::decimate_low_res_cameras,
+            ),
+        );
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -99,16 +253,37 @@ impl Plugin for PixelArtCompositorPlugin {
 
         render_app
             .init_resource::<SpecializedRenderPipelines<CompositorPipeline>>()
+            .init_resource::<RetainedLowResDepth>()
             .add_systems(ExtractSchedule, extract_compositor)
             .add_systems(
                 Render,
-                prepare_compositor_pipelines.in_set(RenderSystems::Prepare),
+                (
+                    prepare_compositor_pipelines.in_set(RenderSystems::Prepare),
+                    decimate::prepare_retained_lowres_depth
+                        .in_set(RenderSystems::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<RetainLowResDepthNode>(Core3d, RetainLowResDepthLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndPrepasses,
+                    RetainLowResDepthLabel,
+                    Node3d::StartMainPass,
+                ),
             )
             .add_render_graph_node::<CompositorNode>(Core3d, CompositorLabel)
+            // After anti-aliasing: TAA, FXAA and SMAA all run on the full-res
+            // scene only, so they neither blur nor ghost the low-res pixels.
             .add_render_graph_edges(
                 Core3d,
-                (Node3d::PostProcessing, CompositorLabel, Node3d::Fxaa),
-            );
+                (
+                    Node3d::Fxaa,
+                    CompositorLabel,
+                    Node3d::EndMainPassPostProcessing,
+                ),
+            )
+            .add_render_graph_edge(Core3d, Node3d::Smaa, CompositorLabel);
     }
 
     fn finish(&self, app: &mut App) {
@@ -117,6 +292,47 @@ impl Plugin for PixelArtCompositorPlugin {
     }
 }
 
+// ──────────────────────────────────────────────
+//  Main-world system
+// ──────────────────────────────────────────────
+
+/// Low-res and compositing cameras.
+type PixelArtCameraFilter = Or<(With<LowResPixelArtCamera>, With<PixelArtCompositor>)>;
+
+/// Forces `Msaa::Off` on low-res and compositing cameras.
+///
+/// The compositor binds both depth prepasses as single-sampled textures; with
+/// MSAA they are multisampled and compositing silently stops.
+pub fn disable_compositor_msaa(
+    mut cameras: Query<(Entity, &mut Msaa), (PixelArtCameraFilter, Changed<Msaa>)>,
+) {
+    for (entity, mut msaa) in &mut cameras {
+        if *msaa != Msaa::Off {
+            warn!(
+                "Pixel art camera {entity} had {:?}; the compositor needs single-sampled depth, switching to Msaa::Off",
+                *msaa
+            );
+            *msaa = Msaa::Off;
+        }
+    }
+}
+
+/// Copies the parent camera's `Projection` to `LowResSubLayer` cameras, so
+/// the coarse layer lines up with the primary one.
+pub fn sync_sub_layer_projection(
+    parents: Query<Ref<Projection>, (With<LowResPixelArtCamera>, Without<LowResSubLayer>)>,
+    mut sub_layers: Query<(Ref<LowResSubLayer>, &ChildOf, &mut Projection)>,
+) {
+    for (sub_layer, child_of, mut projection) in &mut sub_layers {
+        let Ok(parent) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        if parent.is_changed() || sub_layer.is_added() {
+            *projection = parent.clone();
+        }
+    }
+}
+
 // ──────────────────────────────────────────────
 //  Render-world types
 // ──────────────────────────────────────────────
@@ -124,12 +340,29 @@ impl Plugin for PixelArtCompositorPlugin {
 /// Extracted each frame from `PixelArtCompositor`.
 #[derive(Component, Clone)]
 pub struct ExtractedCompositor {
-    pub lowres_image: Handle<Image>,
+    /// `lowres_image` followed by `extra_layers`.
+    pub lowres_images: Vec<Handle<Image>>,
 }
 
-/// Per-view cached pipeline id.
+/// Extracted each frame from a `LowResPixelArtCamera`: the image it renders to
+/// and its compositing order.
+#[derive(Component, Clone)]
+pub struct ExtractedLowResLayer {
+    pub image: Handle<Image>,
+    pub order: LowResLayer,
+    /// The camera has a `LowResFrameRate`: composite with the retained depth.
+    pub retain_depth: bool,
+    /// The camera is a `LowResSubLayer`: also depth-test against the primary layer.
+    pub sub_layer: bool,
+}
+
+/// Per-view cached pipeline ids.
 #[derive(Component, Clone, Copy)]
-pub struct CompositorPipelineId(CachedRenderPipelineId);
+pub struct CompositorPipelineId {
+    pub layer: CachedRenderPipelineId,
+    /// Variant depth-testing against the primary layer, for `LowResSubLayer`s.
+    pub sub_layer: CachedRenderPipelineId,
+}
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
 pub struct CompositorLabel;
@@ -142,6 +375,7 @@ pub struct CompositorLabel;
 pub struct CompositorPipeline {
     pub shader: Handle<Shader>,
     pub nearest_sampler: Sampler,
+    pub linear_sampler: Sampler,
     pub layout: BindGroupLayoutDescriptor,
     pub fullscreen_shader: FullscreenShader,
 }
@@ -167,6 +401,10 @@ impl FromWorld for CompositorPipeline {
                     sampler(SamplerBindingType::NonFiltering),
                     // 5: compositor uniform
                     uniform_buffer::<CompositorUniform>(true),
+                    // 6: linear sampler (filtered upscaling)
+                    sampler(SamplerBindingType::Filtering),
+                    // 7: primary layer depth (sub-layers only)
+                    texture_depth_2d(),
                 ),
             ),
         );
@@ -178,10 +416,17 @@ impl FromWorld for CompositorPipeline {
             min_filter: FilterMode::Nearest,
             ..default()
         });
+        let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("pixel_art_compositor linear sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
 
         Self {
             shader,
             nearest_sampler,
+            linear_sampler,
             layout,
             fullscreen_shader: world.resource::<FullscreenShader>().clone(),
         }
@@ -195,6 +440,8 @@ impl FromWorld for CompositorPipeline {
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CompositorKey {
     pub hdr: bool,
+    /// Depth-test against the primary layer too (`LowResSubLayer`).
+    pub sub_layer: bool,
 }
 
 impl SpecializedRenderPipeline for CompositorPipeline {
@@ -207,13 +454,18 @@ impl SpecializedRenderPipeline for CompositorPipeline {
             TextureFormat::bevy_default()
         };
 
+        let mut shader_defs = vec![];
+        if key.sub_layer {
+            shader_defs.push("PRIMARY_DEPTH_TEST".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("pixel_art_compositor: pipeline".into()),
             layout: vec![self.layout.clone()],
             vertex: self.fullscreen_shader.to_vertex_state(),
             fragment: Some(FragmentState {
                 shader: self.shader.clone(),
-                shader_defs: vec![],
+                shader_defs,
                 entry_point: Some("fragment".into()),
                 targets: vec![Some(ColorTargetState {
                     format,
@@ -234,10 +486,19 @@ impl SpecializedRenderPipeline for CompositorPipeline {
 //  Extract system
 // ──────────────────────────────────────────────
 
+/// Low-res camera target, layer and pass markers read by `extract_compositor`.
+type LowResCameraData = (
+    RenderEntity,
+    &'static RenderTarget,
+    Option<&'static LowResLayer>,
+    Has<LowResFrameRate>,
+    Has<LowResSubLayer>,
+);
+
 pub fn extract_compositor(
     mut commands: Commands,
     compositor_query: Extract<Query<(RenderEntity, &PixelArtCompositor)>>,
-    lowres_query: Extract<Query<RenderEntity, With<LowResPixelArtCamera>>>,
+    lowres_query: Extract<Query<LowResCameraData, With<LowResPixelArtCamera>>>,
 ) {
     if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
         info_once!(
@@ -247,19 +508,39 @@ pub fn extract_compositor(
     }
 
     for (entity, compositor) in compositor_query.iter() {
+        let paired = lowres_query.iter().any(|(_, target, _, _, _)| {
+            matches!(target, RenderTarget::Image(target) if target.handle == compositor.lowres_image)
+        });
+        if !paired {
+            warn_once!(
+                "PixelArtCompositor: no LowResPixelArtCamera renders to `lowres_image`; nothing is composited"
+            );
+        }
+
         commands
             .get_entity(entity)
             .expect("Compositor entity wasn't synced.")
             .insert(ExtractedCompositor {
-                lowres_image: compositor.lowres_image.clone(),
+                lowres_images: std::iter::once(&compositor.lowres_image)
+                    .chain(&compositor.extra_layers)
+                    .cloned()
+                    .collect(),
             });
     }
 
-    for entity in lowres_query.iter() {
-        commands
+    for (entity, target, order, decimated, sub_layer) in lowres_query.iter() {
+        let mut entity = commands
             .get_entity(entity)
-            .expect("LowRes camera entity wasn't synced.")
-            .insert(LowResPixelArtCamera);
+            .expect("LowRes camera entity wasn't synced.");
+        entity.insert(LowResPixelArtCamera);
+        if let RenderTarget::Image(target) = target {
+            entity.insert(ExtractedLowResLayer {
+                image: target.handle.clone(),
+                order: order.copied().unwrap_or_default(),
+                retain_depth: decimated,
+                sub_layer,
+            });
+        }
     }
 }
 
@@ -276,12 +557,35 @@ pub fn prepare_compositor_pipelines(
 ) {
     for (entity, view_target) in &query {
         let hdr = view_target.is_hdr();
-        let id = pipelines.specialize(
-            &pipeline_cache,
-            &compositor_pipeline,
-            CompositorKey { hdr },
-        );
-        commands.entity(entity).insert(CompositorPipelineId(id));
+        let mut specialize = |sub_layer| {
+            pipelines.specialize(
+                &pipeline_cache,
+                &compositor_pipeline,
+                CompositorKey { hdr, sub_layer },
+            )
+        };
+        let ids = CompositorPipelineId {
+            layer: specialize(false),
+            sub_layer: specialize(true),
+        };
+        commands.entity(entity).insert(ids);
+    }
+}
+
+/// Depth prepass of a low-res layer. Decimated layers skip rendering on most
+/// frames; their last depth prepass is retained alongside the last rendered
+/// image.
+fn layer_depth<'a>(
+    retained_depth: &'a RetainedLowResDepth,
+    prepass: Option<&'a ViewPrepassTextures>,
+    layer: &ExtractedLowResLayer,
+) -> Option<&'a TextureView> {
+    if layer.retain_depth {
+        retained_depth.view(layer.image.id())
+    } else {
+        prepass
+            .and_then(|prepass| prepass.depth.as_ref())
+            .map(|depth| &depth.texture.default_view)
     }
 }
 
@@ -297,7 +601,13 @@ pub struct CompositorNode {
         &'static CompositorPipelineId,
         &'static DynamicUniformIndex<CompositorUniform>,
     )>,
-    lowres_query: QueryState<&'static ViewPrepassTextures, With<LowResPixelArtCamera>>,
+    lowres_query: QueryState<
+        (
+            Option<&'static ViewPrepassTextures>,
+            &'static ExtractedLowResLayer,
+        ),
+        With<LowResPixelArtCamera>,
+    >,
 }
 
 impl FromWorld for CompositorNode {
@@ -323,7 +633,7 @@ impl Node for CompositorNode {
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.view_entity();
 
-        let Ok((view_target, fullres_prepass, extracted, pipeline_id, uniform_index)) =
+        let Ok((view_target, fullres_prepass, extracted, pipeline_ids, uniform_index)) =
             self.view_query.get_manual(world, view_entity)
         else {
             return Ok(());
@@ -332,31 +642,11 @@ impl Node for CompositorNode {
         let compositor_pipeline = world.resource::<CompositorPipeline>();
         let pipeline_cache = world.resource::<PipelineCache>();
 
-        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
-            return Ok(());
-        };
-
         // Full-res depth
         let Some(fullres_depth) = &fullres_prepass.depth else {
             return Ok(());
         };
 
-        // Low-res camera prepass textures
-        let Some(lowres_prepass) = self.lowres_query.iter_manual(world).next() else {
-            return Ok(());
-        };
-        let Some(lowres_depth) = &lowres_prepass.depth else {
-            return Ok(());
-        };
-
-        // Low-res color image (the render-to-texture target)
-        let Some(lowres_image) = world
-            .resource::<RenderAssets<GpuImage>>()
-            .get(&extracted.lowres_image)
-        else {
-            return Ok(());
-        };
-
         // Compositor uniform buffer
         let Some(uniform_binding) = world
             .resource::<ComponentUniforms<CompositorUniform>>()
@@ -366,44 +656,81 @@ impl Node for CompositorNode {
             return Ok(());
         };
 
-        let post_process = view_target.post_process_write();
-
-        let bind_group = render_context.render_device().create_bind_group(
-            "pixel_art_compositor_bind_group",
-            &pipeline_cache
-                .get_bind_group_layout(&compositor_pipeline.layout),
-            &BindGroupEntries::sequential((
-                // 0: fullres color (current camera output)
-                post_process.source,
-                // 1: fullres depth
-                &fullres_depth.texture.default_view,
-                // 2: lowres color (pixel art render target)
-                &lowres_image.texture_view,
-                // 3: lowres depth
-                &lowres_depth.texture.default_view,
-                // 4: nearest sampler
-                &compositor_pipeline.nearest_sampler,
-                // 5: compositor uniform
-                uniform_binding,
-            )),
-        );
+        // Low-res layers shown by this compositor, in compositing order
+        let mut layers: Vec<_> = self
+            .lowres_query
+            .iter_manual(world)
+            .filter(|(_, layer)| extracted.lowres_images.contains(&layer.image))
+            .collect();
+        layers.sort_by_key(|(_, layer)| layer.order);
+
+        let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+        let retained_depth = world.resource::<RetainedLowResDepth>();
+        // Sub-layers are depth-tested against the layer rendering to `lowres_image`.
+        let primary_depth = layers
+            .iter()
+            .find(|(_, layer)| !layer.sub_layer && layer.image == extracted.lowres_images[0])
+            .and_then(|(prepass, layer)| layer_depth(retained_depth, *prepass, layer));
+
+        for (lowres_prepass, layer) in layers {
+            let Some(lowres_depth) = layer_depth(retained_depth, lowres_prepass, layer) else {
+                continue;
+            };
+            let (pipeline_id, primary_depth) = match primary_depth {
+                Some(primary_depth) if layer.sub_layer => (pipeline_ids.sub_layer, primary_depth),
+                _ => (pipeline_ids.layer, lowres_depth),
+            };
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id) else {
+                continue;
+            };
+            // Low-res color image (the render-to-texture target)
+            let Some(lowres_image) = gpu_images.get(&layer.image) else {
+                continue;
+            };
+
+            // Each layer reads the previous layer's output.
+            let post_process = view_target.post_process_write();
+
+            let bind_group = render_context.render_device().create_bind_group(
+                "pixel_art_compositor_bind_group",
+                &pipeline_cache.get_bind_group_layout(&compositor_pipeline.layout),
+                &BindGroupEntries::sequential((
+                    // 0: fullres color (current camera output)
+                    post_process.source,
+                    // 1: fullres depth
+                    &fullres_depth.texture.default_view,
+                    // 2: lowres color (pixel art render target)
+                    &lowres_image.texture_view,
+                    // 3: lowres depth
+                    lowres_depth,
+                    // 4: nearest sampler
+                    &compositor_pipeline.nearest_sampler,
+                    // 5: compositor uniform
+                    uniform_binding.clone(),
+                    // 6: linear sampler
+                    &compositor_pipeline.linear_sampler,
+                    // 7: primary layer depth
+                    primary_depth,
+                )),
+            );
 
-        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
-            label: Some("pixel_art_compositor_pass"),
-            color_attachments: &[Some(RenderPassColorAttachment {
-                view: post_process.destination,
-                depth_slice: None,
-                resolve_target: None,
-                ops: Operations::default(),
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("pixel_art_compositor_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: post_process.destination,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
 
-        render_pass.set_render_pipeline(pipeline);
-        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
-        render_pass.draw(0..3, 0..1);
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+            render_pass.draw(0..3, 0..1);
+        }
 
         Ok(())
     }
@@ -8,15 +8,108 @@
 
 struct CompositorSettings {
     depth_bias: f32,
+    feather_mode: u32,             // 0=off, 1=alpha, 2=dithered
+    filter_mode: u32,              // 0=nearest, 1=sharp bilinear, 2=xbr
+    occluded_tint: vec4<f32>,      // rgb = tint, a = opacity (0 = off)
+    subpixel_offset: vec2<f32>,    // low-res texels, +y up
+    overscan: f32,                 // low-res texels cropped per side
 }
 @group(0) @binding(5) var<uniform> settings: CompositorSettings;
+@group(0) @binding(6) var linear_sampler: sampler;
+// Depth of the primary low-res layer, for `LowResSubLayer`s.
+@group(0) @binding(7) var primary_depth: texture_depth_2d;
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let fr_color = textureSample(fullres_color, nearest_sampler, in.uv);
-    let fr_depth = textureSample(fullres_depth, nearest_sampler, in.uv);
-    let lr_color = textureSample(lowres_color, nearest_sampler, in.uv);
-    let lr_depth = textureSample(lowres_depth, nearest_sampler, in.uv);
+// Low-res color at `uv`, whether it wins the depth test there, and whether an
+// opaque low-res pixel lost it (occluded by full-res geometry). 1.0 / 0.0.
+struct LayerSample {
+    color: vec4<f32>,
+    wins: f32,
+    occluded: f32,
+}
+
+// Screen pixels per low-res texel.
+fn upscale_factor() -> vec2<f32> {
+    let size = vec2<f32>(textureDimensions(lowres_color));
+    let visible = max(size - 2.0 * settings.overscan, vec2<f32>(1.0));
+    return vec2<f32>(textureDimensions(fullres_color)) / visible;
+}
+
+// Sharp bilinear: nearest inside the texel, with a linear ramp only in the
+// last screen pixel before each texel border.
+fn sharp_bilinear_uv(lr_uv: vec2<f32>) -> vec2<f32> {
+    let size = vec2<f32>(textureDimensions(lowres_color));
+    let scale = max(upscale_factor(), vec2<f32>(1.0));
+    let texel = lr_uv * size;
+    let center_dist = fract(texel) - 0.5;
+    let region = 0.5 - 0.5 / scale;
+    let f = (center_dist - clamp(center_dist, -region, region)) * scale + 0.5;
+    return (floor(texel) + f) / size;
+}
+
+fn load_lowres(texel: vec2<i32>) -> vec4<f32> {
+    let size = vec2<i32>(textureDimensions(lowres_color));
+    return textureLoad(lowres_color, clamp(texel, vec2<i32>(0), size - 1), 0);
+}
+
+// Perceptual color distance (luma-weighted, alpha counts as well).
+fn color_distance(a: vec4<f32>, b: vec4<f32>) -> f32 {
+    let d = abs(a - b);
+    return dot(d.rgb, vec3<f32>(0.299, 0.587, 0.114)) + d.a;
+}
+
+// xBR-style level-1 upscale: each texel corner whose two side neighbors match
+// each other better than the center matches the diagonal is cut along the
+// diagonal and filled with the side color, anti-aliased over one screen pixel.
+fn xbr_color(lr_uv: vec2<f32>) -> vec4<f32> {
+    let size = vec2<f32>(textureDimensions(lowres_color));
+    let texel = lr_uv * size;
+    let cell = vec2<i32>(floor(texel));
+    let fp = fract(texel);
+    let center = load_lowres(cell);
+
+    // Corner of the texel this pixel is nearest to.
+    let dir = vec2<i32>(select(-1, 1, fp.x >= 0.5), select(-1, 1, fp.y >= 0.5));
+    let side_h = load_lowres(cell + vec2<i32>(dir.x, 0));
+    let side_v = load_lowres(cell + vec2<i32>(0, dir.y));
+    let diagonal = load_lowres(cell + dir);
+    let opposite_h = load_lowres(cell - vec2<i32>(dir.x, 0));
+    let opposite_v = load_lowres(cell - vec2<i32>(0, dir.y));
+
+    // Edge across the corner: the side neighbors belong together and differ
+    // from the center, and neither continues through the center to the
+    // opposite side (which would be a straight line, not a diagonal).
+    let sides = color_distance(side_h, side_v);
+    let edge = sides < color_distance(center, diagonal)
+        && sides < color_distance(center, side_h)
+        && color_distance(side_v, opposite_h) > sides
+        && color_distance(side_h, opposite_v) > sides;
+    if (!edge) {
+        return center;
+    }
+
+    // Cut the corner along the anti-diagonal: past Manhattan distance 0.5 from
+    // the texel center.
+    let toward = abs(fp - 0.5);
+    let aa = 1.0 / max(max(upscale_factor().x, upscale_factor().y), 1.0);
+    let cut = smoothstep(0.5 - aa * 0.5, 0.5 + aa * 0.5, toward.x + toward.y);
+    let fill = select(side_v, side_h, color_distance(center, side_h) < color_distance(center, side_v));
+    return mix(center, fill, cut);
+}
+
+fn sample_lowres_color(lr_uv: vec2<f32>) -> vec4<f32> {
+    if (settings.filter_mode == 2u) {
+        return xbr_color(lr_uv);
+    }
+    if (settings.filter_mode == 1u) {
+        return textureSample(lowres_color, linear_sampler, sharp_bilinear_uv(lr_uv));
+    }
+    return textureSample(lowres_color, nearest_sampler, lr_uv);
+}
+
+fn sample_layer(uv: vec2<f32>, lr_uv: vec2<f32>) -> LayerSample {
+    let fr_depth = textureSample(fullres_depth, nearest_sampler, uv);
+    let lr_color = sample_lowres_color(lr_uv);
+    let lr_depth = textureSample(lowres_depth, nearest_sampler, lr_uv);
 
     // Bevy reversed-Z: 1.0 = near, 0.0 = far
     // Scale bias by depth — near objects (d≈1) get full bias,
@@ -27,13 +120,97 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     // Only composite low-res pixel if it's substantially opaque AND closer to camera.
     // Higher alpha threshold prevents edge bleed from nearest upscale.
     // Particles (alpha-blended, no depth write) remain visible in fullres pass.
-    if lr_color.a > 0.1 && lr_depth >= fr_depth - effective_bias {
+    var out: LayerSample;
+    out.color = lr_color;
+    let opaque = lr_color.a > 0.1;
+    var closer = lr_depth >= fr_depth - effective_bias;
+#ifdef PRIMARY_DEPTH_TEST
+    // Sub-layer: also lose to primary-layer geometry in front.
+    let primary = textureSample(primary_depth, nearest_sampler, primary_uv(uv));
+    closer = closer && lr_depth >= primary - settings.depth_bias * primary;
+#endif
+    out.wins = select(0.0, 1.0, opaque && closer);
+    out.occluded = select(0.0, 1.0, opaque && !closer);
+    return out;
+}
+
+// Screen UV to low-res UV: crop the overscan border and shift by the
+// sub-pixel offset (texture Y points down, camera Y up).
+fn lowres_uv(uv: vec2<f32>) -> vec2<f32> {
+    let size = vec2<f32>(textureDimensions(lowres_color));
+    let visible = max(size - 2.0 * settings.overscan, vec2<f32>(1.0));
+    let offset = vec2<f32>(settings.subpixel_offset.x, -settings.subpixel_offset.y);
+    return (settings.overscan + uv * visible + offset) / size;
+}
+
+// Screen UV to primary-layer UV, as `lowres_uv` in that layer's texels.
+fn primary_uv(uv: vec2<f32>) -> vec2<f32> {
+    let size = vec2<f32>(textureDimensions(primary_depth));
+    let visible = max(size - 2.0 * settings.overscan, vec2<f32>(1.0));
+    let offset = vec2<f32>(settings.subpixel_offset.x, -settings.subpixel_offset.y);
+    return (settings.overscan + uv * visible + offset) / size;
+}
+
+fn bayer4x4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) % 4u;
+    let y = u32(pos.y) % 4u;
+    var matrix = array<array<f32, 4>, 4>(
+        array<f32, 4>( 0.0/16.0,  8.0/16.0,  2.0/16.0, 10.0/16.0),
+        array<f32, 4>(12.0/16.0,  4.0/16.0, 14.0/16.0,  6.0/16.0),
+        array<f32, 4>( 3.0/16.0, 11.0/16.0,  1.0/16.0,  9.0/16.0),
+        array<f32, 4>(15.0/16.0,  7.0/16.0, 13.0/16.0,  5.0/16.0),
+    );
+    return matrix[y][x];
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let fr_color = textureSample(fullres_color, nearest_sampler, in.uv);
+    let lr_uv = lowres_uv(in.uv);
+
+    var lr_color: vec4<f32>;
+    var coverage: f32;
+    var occluded: f32;
+    if (settings.feather_mode == 0u) {
+        let s = sample_layer(in.uv, lr_uv);
+        lr_color = s.color;
+        coverage = s.wins;
+        occluded = s.occluded;
+    } else {
+        // Feather: depth-test a one-texel footprint (4 half-texel taps) and
+        // use the fraction that wins as coverage.
+        let lr_size = vec2<f32>(textureDimensions(lowres_color));
+        let half_texel = 0.5 / lr_size;
+        let screen_half_texel = 0.5 / max(lr_size - 2.0 * settings.overscan, vec2<f32>(1.0));
+        var sum_color = vec4<f32>(0.0);
+        coverage = 0.0;
+        occluded = 0.0;
+        for (var i = 0u; i < 4u; i++) {
+            let offset = vec2<f32>(f32(i & 1u) * 2.0 - 1.0, f32(i >> 1u) * 2.0 - 1.0);
+            let s = sample_layer(in.uv + offset * screen_half_texel, lr_uv + offset * half_texel);
+            sum_color += s.color * s.wins;
+            coverage += s.wins * 0.25;
+            occluded += s.occluded * 0.25;
+        }
+        lr_color = sum_color / max(coverage * 4.0, 1.0);
+        if (settings.feather_mode == 2u) {
+            coverage = select(0.0, 1.0, bayer4x4(in.position.xy) < coverage);
+        }
+    }
+
+    if (coverage > 0.0) {
         // Blend rather than replace: preserves fullres particles/effects behind pixel art models
-        let blend = lr_color.a;
+        let blend = lr_color.a * coverage;
         return vec4<f32>(
             mix(fr_color.rgb, lr_color.rgb, blend),
-            max(fr_color.a, lr_color.a),
+            max(fr_color.a, blend),
         );
     }
+
+    // Occluded silhouette: tint the full-res pixel where pixel art is hidden.
+    if (occluded > 0.0 && settings.occluded_tint.a > 0.0) {
+        let blend = settings.occluded_tint.a * occluded;
+        return vec4<f32>(mix(fr_color.rgb, settings.occluded_tint.rgb, blend), fr_color.a);
+    }
     return fr_color;
 }
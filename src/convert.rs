@@ -0,0 +1,96 @@
+use bevy::camera::visibility::RenderLayers;
+use bevy::pbr::ExtendedMaterial;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::{PixelArtExtension, PixelArtMaterial, PixelLayerOnly};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Converts a spawned scene (e.g. a glTF `SceneRoot`) to pixel art.
+///
+/// Every descendant with a `MeshMaterial3d<StandardMaterial>` gets a
+/// `PixelArtMaterial` whose base is a copy of its `StandardMaterial` (base
+/// color, textures, roughness, …) and whose extension is `extension`, and is
+/// moved to `layers` (or marked `PixelLayerOnly`). Each source material is
+/// converted once and shared. The component stays on the root, so meshes
+/// spawned later under it are converted too.
+#[derive(Component, Clone, Default)]
+pub struct ConvertToPixelArt {
+    /// Extension (params, palette, mask) for every converted material.
+    pub extension: PixelArtExtension,
+    /// Render layers for converted meshes (default: `PixelLayerOnly`).
+    pub layers: Option<RenderLayers>,
+    /// Converted material per source material.
+    converted: HashMap<AssetId<StandardMaterial>, Handle<PixelArtMaterial>>,
+}
+
+impl ConvertToPixelArt {
+    /// Convert with `extension`, on the pixel art layer.
+    pub fn new(extension: PixelArtExtension) -> Self {
+        Self {
+            extension,
+            ..default()
+        }
+    }
+
+    /// Put converted meshes on `layers` instead of the pixel art layer.
+    pub fn with_layers(mut self, layers: RenderLayers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Swaps the `StandardMaterial`s under every `ConvertToPixelArt` root for
+/// pixel art materials.
+pub fn convert_to_pixel_art(
+    mut commands: Commands,
+    mut roots: Query<(Entity, &mut ConvertToPixelArt)>,
+    children: Query<&Children>,
+    standard: Query<&MeshMaterial3d<StandardMaterial>>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut pixel_materials: ResMut<Assets<PixelArtMaterial>>,
+) {
+    for (root, mut convert) in &mut roots {
+        for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+            let Ok(material) = standard.get(entity) else {
+                continue;
+            };
+            let source = material.0.id();
+            let converted = match convert.converted.get(&source) {
+                Some(converted) => converted.clone(),
+                None => {
+                    // Wait for the glTF material to load.
+                    let Some(base) = standard_materials.get(source) else {
+                        continue;
+                    };
+                    let converted = pixel_materials.add(ExtendedMaterial {
+                        base: base.clone(),
+                        extension: convert.extension.clone(),
+                    });
+                    convert.converted.insert(source, converted.clone());
+                    converted
+                }
+            };
+
+            let mut entity = commands.entity(entity);
+            entity
+                .remove::<MeshMaterial3d<StandardMaterial>>()
+                .insert(MeshMaterial3d(converted));
+            match &convert.layers {
+                Some(layers) => {
+                    entity.insert(layers.clone());
+                }
+                None => {
+                    entity.insert(PixelLayerOnly);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{sampler, texture_2d, uniform_buffer},
+            *,
+        },
+        renderer::{RenderContext, RenderDevice},
+        view::ViewTarget,
+    },
+};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// CRT presentation pass on the window camera.
+///
+/// Runs after the compositor and all other post-processing on the final image: barrel
+/// curvature, horizontal scanlines, an RGB slot mask and a vignette. Add or
+/// remove the component to toggle it per camera; every effect is off at 0.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtCrt {
+    /// Number of scanlines over the screen height; match the low-res target
+    /// height for one line per pixel row (default: 180).
+    pub scanlines: u32,
+    /// Darkening between scanlines (0..=1, default: 0.3).
+    pub scanline_intensity: f32,
+    /// Strength of the RGB slot mask, one triad per 3 screen pixels
+    /// (0..=1, default: 0.15).
+    pub mask_intensity: f32,
+    /// Barrel distortion; the area outside the curved screen is black
+    /// (default: 0.05).
+    pub curvature: f32,
+    /// Darkening toward the corners (0..=1, default: 0.25).
+    pub vignette: f32,
+}
+
+impl Default for PixelArtCrt {
+    fn default() -> Self {
+        Self {
+            scanlines: 180,
+            scanline_intensity: 0.3,
+            mask_intensity: 0.15,
+            curvature: 0.05,
+            vignette: 0.25,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct CrtUniform {
+    pub scanlines: f32,
+    pub scanline_intensity: f32,
+    pub mask_intensity: f32,
+    pub curvature: f32,
+    pub vignette: f32,
+}
+
+impl ExtractComponent for CrtUniform {
+    type QueryData = &'static PixelArtCrt;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        crt: bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        Some(CrtUniform {
+            scanlines: crt.scanlines as f32,
+            scanline_intensity: crt.scanline_intensity.clamp(0.0, 1.0),
+            mask_intensity: crt.mask_intensity.clamp(0.0, 1.0),
+            curvature: crt.curvature.max(0.0),
+            vignette: crt.vignette.clamp(0.0, 1.0),
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtCrtPlugin;
+
+impl Plugin for PixelArtCrtPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "crt.wgsl");
+
+        app.register_type::<PixelArtCrt>();
+        app.add_plugins((
+            ExtractComponentPlugin::<CrtUniform>::default(),
+            UniformComponentPlugin::<CrtUniform>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<CrtPipeline>>()
+            .add_systems(Render, prepare_crt_pipelines.in_set(RenderSystems::Prepare))
+            .add_render_graph_node::<CrtNode>(Core3d, PixelArtCrtLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndMainPassPostProcessing,
+                    PixelArtCrtLabel,
+                    Node3d::Upscaling,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp).init_resource::<CrtPipeline>();
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct CrtPipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtCrtLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct CrtPipeline {
+    pub shader: Handle<Shader>,
+    pub linear_sampler: Sampler,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for CrtPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "crt.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_crt: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: linear sampler (curvature resampling)
+                    sampler(SamplerBindingType::Filtering),
+                    // 2: crt uniform
+                    uniform_buffer::<CrtUniform>(true),
+                ),
+            ),
+        );
+
+        let render_device = world.resource::<RenderDevice>();
+        let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("pixel_art_crt linear sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        Self {
+            shader,
+            linear_sampler,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CrtKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for CrtPipeline {
+    type Key = CrtKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_crt: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_crt_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<CrtPipeline>>,
+    crt_pipeline: Res<CrtPipeline>,
+    query: Query<(Entity, &ViewTarget), With<CrtUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &crt_pipeline,
+            CrtKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(CrtPipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct CrtNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static CrtPipelineId,
+        &'static DynamicUniformIndex<CrtUniform>,
+    )>,
+}
+
+impl FromWorld for CrtNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for CrtNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let crt_pipeline = world.resource::<CrtPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<CrtUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_crt_bind_group",
+            &pipeline_cache.get_bind_group_layout(&crt_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &crt_pipeline.linear_sampler,
+                uniform_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_crt_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+@group(0) @binding(1) var linear_sampler: sampler;
+
+struct CrtSettings {
+    scanlines: f32,
+    scanline_intensity: f32,
+    mask_intensity: f32,
+    curvature: f32,
+    vignette: f32,
+}
+@group(0) @binding(2) var<uniform> settings: CrtSettings;
+
+const TAU: f32 = 6.28318530718;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Barrel curvature: push the sample point outward with distance².
+    let centered = in.uv * 2.0 - 1.0;
+    let curved = centered * (1.0 + settings.curvature * dot(centered, centered));
+    let uv = curved * 0.5 + 0.5;
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    var color = textureSample(source_color, linear_sampler, uv);
+
+    // Scanlines: dark gaps between rows, following the curved image.
+    if (settings.scanlines > 0.0) {
+        let line = 0.5 + 0.5 * cos(uv.y * settings.scanlines * TAU);
+        color = vec4<f32>(color.rgb * mix(1.0, line, settings.scanline_intensity), color.a);
+    }
+
+    // Slot mask: R/G/B triads, staggered by half a triad every other row pair.
+    if (settings.mask_intensity > 0.0) {
+        let pixel = vec2<u32>(in.position.xy);
+        let column = (pixel.x + select(0u, 1u, (pixel.y / 2u) % 2u == 1u)) % 3u;
+        var mask = vec3<f32>(1.0 - settings.mask_intensity);
+        mask[column] = 1.0;
+        color = vec4<f32>(color.rgb * mask, color.a);
+    }
+
+    // Vignette on the curved coordinates.
+    let edge = curved * curved;
+    let falloff = 1.0 - settings.vignette * max(edge.x, edge.y);
+    return vec4<f32>(color.rgb * clamp(falloff, 0.0, 1.0), color.a);
+}
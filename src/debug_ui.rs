@@ -0,0 +1,367 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::{
+    DebugStage, DitherAnchor, PixelArtMaterial, PixelArtMaterials, PixelArtPalette,
+    PixelArtShaderParams, QuantizationSpace, ShadowEdge, SharedPalette,
+};
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+/// Drop-in egui window for tuning `PixelArtShaderParams` at runtime: debug
+/// stage selector, palette swatches and sliders for every parameter, applied
+/// to all pixel art materials or to one entity's material.
+///
+/// Needs `bevy_egui`'s `EguiPlugin` (with a primary context); enabled by the
+/// `debug-ui` feature.
+pub struct PixelArtDebugUiPlugin;
+
+impl Plugin for PixelArtDebugUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PixelArtDebugUi>()
+            .add_systems(EguiPrimaryContextPass, pixel_art_debug_ui);
+    }
+}
+
+/// State of the `PixelArtDebugUiPlugin` window.
+#[derive(Resource, Clone, Debug)]
+pub struct PixelArtDebugUi {
+    /// Whether the window is shown (default: true).
+    pub open: bool,
+    /// Entity whose material is edited (`None` = every pixel art material).
+    /// Edits apply to the material asset, so other entities sharing it
+    /// change too.
+    pub target: Option<Entity>,
+}
+
+impl Default for PixelArtDebugUi {
+    fn default() -> Self {
+        Self {
+            open: true,
+            target: None,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Parameter table
+// ──────────────────────────────────────────────
+
+struct FloatParam {
+    label: &'static str,
+    range: std::ops::RangeInclusive<f32>,
+    get: fn(&PixelArtShaderParams) -> f32,
+    set: fn(&mut PixelArtShaderParams, f32),
+}
+
+struct ChoiceParam {
+    label: &'static str,
+    options: &'static [(&'static str, u32)],
+    get: fn(&PixelArtShaderParams) -> u32,
+    set: fn(&mut PixelArtShaderParams, u32),
+}
+
+macro_rules! float_param {
+    ($label:literal, $field:ident, $range:expr) => {
+        FloatParam {
+            label: $label,
+            range: $range,
+            get: |p| p.$field,
+            set: |p, v| p.$field = v,
+        }
+    };
+}
+
+macro_rules! choice_param {
+    ($label:literal, $field:ident, $options:expr) => {
+        ChoiceParam {
+            label: $label,
+            options: $options,
+            get: |p| p.$field,
+            set: |p, v| p.$field = v,
+        }
+    };
+}
+
+/// Float params per collapsible section.
+fn float_sections() -> [(&'static str, Vec<FloatParam>); 7] {
+    [
+        (
+            "Toon",
+            vec![
+                float_param!("Bands", toon_bands, 1.0..=16.0),
+                float_param!("Softness", toon_softness, 0.0..=0.5),
+                float_param!("Shadow Floor", toon_shadow_floor, 0.0..=1.0),
+                float_param!("Exposure", exposure, 0.25..=4.0),
+                float_param!("Emissive Bands", emissive_bands, 0.0..=16.0),
+                float_param!("Normal Map Strength", normal_map_strength, 0.0..=1.0),
+            ],
+        ),
+        (
+            "Lights",
+            vec![
+                float_param!("Point Light Bands", point_light_bands, 0.0..=16.0),
+                float_param!("Spot Light Bands", spot_light_bands, 0.0..=16.0),
+                float_param!("Rim Strength", rim_strength, 0.0..=1.0),
+                float_param!("Rim Width", rim_width, 0.0..=1.0),
+            ],
+        ),
+        (
+            "Palette",
+            vec![
+                float_param!("Palette Strength", palette_strength, 0.0..=1.0),
+                float_param!("Lightness Weight", lightness_weight, 0.0..=4.0),
+                float_param!("Chroma Weight", chroma_weight, 0.0..=4.0),
+                float_param!("Stylize Strength", stylize_strength, 0.0..=1.0),
+            ],
+        ),
+        (
+            "Dither",
+            vec![
+                float_param!("Dither Strength", dither_strength, 0.0..=1.0),
+                float_param!("Dither Density", dither_density, 0.25..=8.0),
+                float_param!("Band Window", dither_band_window, 0.0..=0.5),
+                float_param!("Stretch", dither_stretch, 0.25..=4.0),
+                float_param!("Skew", dither_skew, -1.0..=1.0),
+                float_param!("Anchor Scale", dither_anchor_scale, 1.0..=64.0),
+            ],
+        ),
+        (
+            "Hue Shift",
+            vec![
+                float_param!("Shadow Hue Shift", shadow_hue_shift, -180.0..=180.0),
+                float_param!("Highlight Hue Shift", highlight_hue_shift, -180.0..=180.0),
+            ],
+        ),
+        (
+            "Outline",
+            vec![
+                float_param!("Depth Threshold", outline_depth_threshold, 0.0..=0.5),
+                float_param!("Normal Threshold", outline_normal_threshold, 0.0..=2.0),
+            ],
+        ),
+        (
+            "Wind",
+            vec![
+                float_param!("Strength", wind_strength, 0.0..=1.0),
+                float_param!("Frequency", wind_frequency, 0.0..=4.0),
+                float_param!("Wavelength", wind_wavelength, 0.5..=32.0),
+                float_param!("Steps per Second", wind_fps, 0.0..=30.0),
+                float_param!("Height", wind_height, 0.1..=8.0),
+            ],
+        ),
+    ]
+}
+
+fn choice_params() -> [ChoiceParam; 4] {
+    [
+        choice_param!(
+            "Color Space",
+            quantization_space,
+            &[
+                ("CIELAB", QuantizationSpace::CieLab as u32),
+                ("OKLab", QuantizationSpace::OkLab as u32),
+                ("Linear RGB", QuantizationSpace::LinearRgb as u32),
+                ("sRGB", QuantizationSpace::Srgb as u32),
+            ]
+        ),
+        choice_param!(
+            "Bayer Size",
+            bayer_size,
+            &[("2×2", 2), ("4×4", 4), ("8×8", 8)]
+        ),
+        choice_param!(
+            "Dither Anchor",
+            dither_anchor,
+            &[
+                ("Screen", DitherAnchor::Screen as u32),
+                ("Object", DitherAnchor::Object as u32),
+                ("World", DitherAnchor::World as u32),
+                ("UV", DitherAnchor::Uv as u32),
+            ]
+        ),
+        choice_param!(
+            "Shadow Edge",
+            shadow_edge,
+            &[
+                ("Soft", ShadowEdge::Soft as u32),
+                ("Hard", ShadowEdge::Hard as u32),
+                ("Dithered", ShadowEdge::Dithered as u32),
+            ]
+        ),
+    ]
+}
+
+// ──────────────────────────────────────────────
+//  System
+// ──────────────────────────────────────────────
+
+/// One edit from the window, applied to the target material(s) so untouched
+/// fields keep their per-material values.
+type Edit = Box<dyn Fn(&mut PixelArtShaderParams)>;
+
+fn linear_to_srgb(v: Vec4) -> [f32; 3] {
+    let c = Color::linear_rgb(v.x, v.y, v.z).to_srgba();
+    [c.red, c.green, c.blue]
+}
+
+fn srgb_to_linear(rgb: [f32; 3], alpha: f32) -> Vec4 {
+    let c = Color::srgb(rgb[0], rgb[1], rgb[2]).to_linear();
+    Vec4::new(c.red, c.green, c.blue, alpha)
+}
+
+fn pixel_art_debug_ui(
+    mut contexts: EguiContexts,
+    mut state: ResMut<PixelArtDebugUi>,
+    mut materials: PixelArtMaterials,
+    entities: Query<(Entity, Option<&Name>, &MeshMaterial3d<PixelArtMaterial>)>,
+    shared_palette: Option<Res<SharedPalette>>,
+) {
+    if !state.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    if state.target.is_some_and(|e| !entities.contains(e)) {
+        state.target = None;
+    }
+    let target_material = state
+        .target
+        .and_then(|e| entities.get(e).ok())
+        .map(|(_, _, m)| m.0.id());
+    let Some(params) = (match target_material {
+        Some(id) => materials.get(id),
+        None => materials.first(),
+    })
+    .cloned() else {
+        return;
+    };
+
+    let mut edits: Vec<Edit> = Vec::new();
+    let mut open = state.open;
+    egui::Window::new("Pixel Art Params")
+        .open(&mut open)
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            let label = |entity: Entity, name: Option<&Name>| {
+                name.map_or_else(|| format!("{entity}"), |n| n.to_string())
+            };
+            let selected = match state.target.and_then(|e| entities.get(e).ok()) {
+                Some((e, name, _)) => label(e, name),
+                None => "All materials".to_string(),
+            };
+            egui::ComboBox::from_label("Target")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut state.target, None, "All materials");
+                    for (entity, name, _) in &entities {
+                        ui.selectable_value(&mut state.target, Some(entity), label(entity, name));
+                    }
+                });
+
+            let current = DebugStage::from_u32(params.debug_stage).unwrap_or_default();
+            let mut stage = current;
+            egui::ComboBox::from_label("Debug Stage")
+                .selected_text(stage.label())
+                .show_ui(ui, |ui| {
+                    for s in DebugStage::ALL {
+                        ui.selectable_value(&mut stage, s, s.label());
+                    }
+                });
+            if stage != current {
+                edits.push(Box::new(move |p| p.debug_stage = stage as u32));
+            }
+
+            ui.separator();
+            ui.label("Palette");
+            let palette = shared_palette
+                .as_ref()
+                .map_or_else(PixelArtPalette::default, |shared| shared.0.clone());
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing = egui::vec2(1.0, 1.0);
+                for (i, c) in palette.colors.iter().enumerate() {
+                    let srgb = Color::linear_rgb(c.x, c.y, c.z).to_srgba().to_u8_array();
+                    let mut fill = egui::Color32::from_rgb(srgb[0], srgb[1], srgb[2]);
+                    if i as u32 >= params.palette_count {
+                        fill = fill.gamma_multiply(0.25);
+                    }
+                    let (rect, response) =
+                        ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 0.0, fill);
+                    response.on_hover_text(format!("{i}"));
+                }
+            });
+            let mut count = params.palette_count;
+            if ui
+                .add(egui::Slider::new(&mut count, 0..=palette.count()).text("Palette Colors"))
+                .changed()
+            {
+                edits.push(Box::new(move |p| p.palette_count = count));
+            }
+
+            ui.separator();
+            let mut tint = linear_to_srgb(params.base_tint);
+            ui.horizontal(|ui| {
+                if ui.color_edit_button_rgb(&mut tint).changed() {
+                    let alpha = params.base_tint.w;
+                    edits.push(Box::new(move |p| p.base_tint = srgb_to_linear(tint, alpha)));
+                }
+                ui.label("Base Tint");
+            });
+            let mut rim = linear_to_srgb(params.rim_color);
+            ui.horizontal(|ui| {
+                if ui.color_edit_button_rgb(&mut rim).changed() {
+                    edits.push(Box::new(move |p| p.rim_color = srgb_to_linear(rim, 1.0)));
+                }
+                ui.label("Rim Color");
+            });
+
+            for choice in choice_params() {
+                let current = (choice.get)(&params);
+                let mut value = current;
+                let text = choice
+                    .options
+                    .iter()
+                    .find(|(_, v)| *v == current)
+                    .map_or("?", |(name, _)| *name);
+                egui::ComboBox::from_label(choice.label)
+                    .selected_text(text)
+                    .show_ui(ui, |ui| {
+                        for (name, v) in choice.options {
+                            ui.selectable_value(&mut value, *v, *name);
+                        }
+                    });
+                if value != current {
+                    let set = choice.set;
+                    edits.push(Box::new(move |p| set(p, value)));
+                }
+            }
+
+            for (section, fields) in float_sections() {
+                ui.collapsing(section, |ui| {
+                    for field in fields {
+                        let mut value = (field.get)(&params);
+                        if ui
+                            .add(egui::Slider::new(&mut value, field.range).text(field.label))
+                            .changed()
+                        {
+                            let set = field.set;
+                            edits.push(Box::new(move |p| set(p, value)));
+                        }
+                    }
+                });
+            }
+        });
+    state.open = open;
+
+    for edit in edits {
+        match target_material {
+            Some(id) => materials.update(id, |p| edit(p)),
+            None => materials.update_all(|p| edit(p)),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use bevy::{
+    core_pipeline::{core_3d::CORE_3D_DEPTH_FORMAT, prepass::ViewPrepassTextures},
+    ecs::query::QueryState,
+    platform::collections::HashMap,
+    prelude::*,
+    render::{
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderLabel},
+        render_resource::*,
+        renderer::{RenderContext, RenderDevice},
+    },
+};
+
+use crate::compositor::ExtractedLowResLayer;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Re-renders a `LowResPixelArtCamera` only every n-th frame or at a fixed
+/// rate, while the full-res camera keeps running at display rate.
+///
+/// Gives the pixel layer a stop-motion look (e.g. 12 fps sprites over a 60 Hz
+/// world) and skips the low-res passes on the frames in between. The camera is
+/// deactivated on skipped frames; its render target keeps the last rendered
+/// image and the compositor reuses a retained copy of its depth prepass, so
+/// compositing stays stable.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LowResFrameRate {
+    /// Render every n-th frame (default: 2; 1 renders every frame).
+    pub every_nth_frame: u32,
+    /// Render at this rate in Hz instead, when > 0 (default: 0).
+    pub hz: f32,
+    #[reflect(ignore)]
+    frame: u32,
+    #[reflect(ignore)]
+    elapsed: f32,
+}
+
+impl Default for LowResFrameRate {
+    fn default() -> Self {
+        Self::every(2)
+    }
+}
+
+impl LowResFrameRate {
+    /// Render every `n`-th frame.
+    pub fn every(n: u32) -> Self {
+        Self {
+            every_nth_frame: n,
+            hz: 0.0,
+            frame: 0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Render `hz` times per second.
+    pub fn hz(hz: f32) -> Self {
+        Self {
+            hz,
+            ..Self::every(1)
+        }
+    }
+
+    /// Whether the camera renders this frame; advances the counters.
+    fn tick(&mut self, delta_secs: f32) -> bool {
+        if self.hz > 0.0 {
+            let interval = 1.0 / self.hz;
+            let first = self.frame == 0;
+            self.frame = 1;
+            self.elapsed += delta_secs;
+            if !first && self.elapsed < interval {
+                return false;
+            }
+            // Keep the remainder so the average rate matches `hz`, but drop
+            // backlog after a hitch instead of rendering several frames in a row.
+            self.elapsed = (self.elapsed - interval).clamp(0.0, interval);
+            true
+        } else {
+            let render = self.frame == 0;
+            self.frame = (self.frame + 1) % self.every_nth_frame.max(1);
+            render
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Main-world system
+// ──────────────────────────────────────────────
+
+/// Activates each `LowResFrameRate` camera on the frames it should render.
+pub fn decimate_low_res_cameras(
+    time: Res<Time>,
+    mut cameras: Query<(&mut LowResFrameRate, &mut Camera)>,
+) {
+    for (mut rate, mut camera) in &mut cameras {
+        let render = rate.tick(time.delta_secs());
+        if camera.is_active != render {
+            camera.is_active = render;
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render world: retained depth
+// ──────────────────────────────────────────────
+
+/// Copy of each decimated layer's low-res depth prepass, keyed by the layer's
+/// render target, written on the frames the layer renders.
+#[derive(Resource, Default)]
+pub struct RetainedLowResDepth {
+    textures: HashMap<AssetId<Image>, (Texture, TextureView)>,
+}
+
+impl RetainedLowResDepth {
+    /// Retained depth view for the layer rendering to `image`.
+    pub fn view(&self, image: AssetId<Image>) -> Option<&TextureView> {
+        self.textures.get(&image).map(|(_, view)| view)
+    }
+}
+
+/// (Re)creates the retained depth textures of decimated layers rendering this
+/// frame and drops those of removed layers.
+pub fn prepare_retained_lowres_depth(
+    render_device: Res<RenderDevice>,
+    mut retained: ResMut<RetainedLowResDepth>,
+    layers: Query<(&ExtractedLowResLayer, Option<&ViewPrepassTextures>)>,
+) {
+    retained.textures.retain(|image, _| {
+        layers
+            .iter()
+            .any(|(layer, _)| layer.retain_depth && layer.image.id() == *image)
+    });
+
+    for (layer, prepass) in &layers {
+        let Some(depth) = prepass.and_then(|p| p.depth.as_ref()) else {
+            continue;
+        };
+        if !layer.retain_depth {
+            continue;
+        }
+        let size = depth.texture.texture.size();
+        if retained
+            .textures
+            .get(&layer.image.id())
+            .is_some_and(|(texture, _)| texture.size() == size)
+        {
+            continue;
+        }
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("pixel_art_retained_lowres_depth"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: CORE_3D_DEPTH_FORMAT,
+            usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        retained.textures.insert(layer.image.id(), (texture, view));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct RetainLowResDepthLabel;
+
+/// Copies a decimated low-res view's depth prepass into its retained texture.
+pub struct RetainLowResDepthNode {
+    view_query: QueryState<(&'static ViewPrepassTextures, &'static ExtractedLowResLayer)>,
+}
+
+impl FromWorld for RetainLowResDepthNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for RetainLowResDepthNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((prepass, layer)) = self.view_query.get_manual(world, graph.view_entity()) else {
+            return Ok(());
+        };
+        if !layer.retain_depth {
+            return Ok(());
+        }
+        let Some(depth) = &prepass.depth else {
+            return Ok(());
+        };
+        let Some((retained, _)) = world
+            .resource::<RetainedLowResDepth>()
+            .textures
+            .get(&layer.image.id())
+        else {
+            return Ok(());
+        };
+
+        let size = depth.texture.texture.size();
+        if retained.size() != size {
+            return Ok(());
+        }
+        render_context.command_encoder().copy_texture_to_texture(
+            depth.texture.texture.as_image_copy(),
+            retained.as_image_copy(),
+            size,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+        prepass::{DepthPrepass, ViewPrepassTextures},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{texture_2d, texture_depth_2d, uniform_buffer},
+            *,
+        },
+        renderer::RenderContext,
+        view::ViewTarget,
+    },
+};
+
+use crate::pass_order::order_low_res_passes;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Mosaic depth of field for the low-res camera.
+///
+/// Instead of a Gaussian blur, out-of-focus pixels take the color of a larger
+/// block (2, 4, 8… texels) the farther they are from the focus band, and the
+/// step between block sizes is resolved with a Bayer pattern, so focus pulls
+/// stay blocky and on-palette. Runs after quantization and outlines. Requires
+/// the depth prepass.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[require(DepthPrepass)]
+pub struct PixelArtDepthOfField {
+    /// View distance (world units) that is in focus (default: 10.0).
+    pub focus_distance: f32,
+    /// Distance on either side of `focus_distance` that stays sharp
+    /// (default: 2.0).
+    pub focus_range: f32,
+    /// Distance past the focus band over which blocks grow to `max_block`
+    /// (default: 10.0).
+    pub blur_distance: f32,
+    /// Largest block size in texels, rounded down to a power of two
+    /// (1 = off, default: 4).
+    pub max_block: u32,
+    /// Bayer dither between neighboring block sizes (0 = hard steps,
+    /// default: 1.0).
+    pub dither: f32,
+    /// Blur nearer than the focus band too (default: true).
+    pub near_blur: bool,
+}
+
+impl Default for PixelArtDepthOfField {
+    fn default() -> Self {
+        Self {
+            focus_distance: 10.0,
+            focus_range: 2.0,
+            blur_distance: 10.0,
+            max_block: 4,
+            dither: 1.0,
+            near_blur: true,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct DepthOfFieldUniform {
+    pub focus_distance: f32,
+    pub focus_range: f32,
+    pub blur_distance: f32,
+    /// log2 of the largest block size.
+    pub max_level: f32,
+    pub dither: f32,
+    /// 1 = blur the near field.
+    pub near_blur: u32,
+    /// Terms of the camera's `clip_from_view` that map prepass depth back to
+    /// view distance: (z_axis.z, w_axis.z, z_axis.w, w_axis.w).
+    pub depth_to_view: Vec4,
+}
+
+impl ExtractComponent for DepthOfFieldUniform {
+    type QueryData = (&'static PixelArtDepthOfField, &'static Camera);
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        (dof, camera): bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        let clip_from_view = camera.clip_from_view();
+        Some(DepthOfFieldUniform {
+            focus_distance: dof.focus_distance,
+            focus_range: dof.focus_range.max(0.0),
+            blur_distance: dof.blur_distance.max(1e-3),
+            max_level: dof.max_block.max(1).ilog2().min(5) as f32,
+            dither: dof.dither.clamp(0.0, 1.0),
+            near_blur: dof.near_blur as u32,
+            depth_to_view: Vec4::new(
+                clip_from_view.z_axis.z,
+                clip_from_view.w_axis.z,
+                clip_from_view.z_axis.w,
+                clip_from_view.w_axis.w,
+            ),
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtDepthOfFieldPlugin;
+
+impl Plugin for PixelArtDepthOfFieldPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "depth_of_field.wgsl");
+
+        app.register_type::<PixelArtDepthOfField>();
+        app.add_plugins((
+            ExtractComponentPlugin::<DepthOfFieldUniform>::default(),
+            UniformComponentPlugin::<DepthOfFieldUniform>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<DepthOfFieldPipeline>>()
+            .add_systems(
+                Render,
+                prepare_depth_of_field_pipelines.in_set(RenderSystems::Prepare),
+            )
+            .add_render_graph_node::<DepthOfFieldNode>(Core3d, PixelArtDepthOfFieldLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (Node3d::Tonemapping, PixelArtDepthOfFieldLabel, Node3d::Fxaa),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<DepthOfFieldPipeline>();
+        order_low_res_passes(app);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct DepthOfFieldPipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtDepthOfFieldLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct DepthOfFieldPipeline {
+    pub shader: Handle<Shader>,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for DepthOfFieldPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "depth_of_field.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_depth_of_field: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: prepass depth
+                    texture_depth_2d(),
+                    // 2: depth of field uniform
+                    uniform_buffer::<DepthOfFieldUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            shader,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepthOfFieldKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for DepthOfFieldPipeline {
+    type Key = DepthOfFieldKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_depth_of_field: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_depth_of_field_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<DepthOfFieldPipeline>>,
+    dof_pipeline: Res<DepthOfFieldPipeline>,
+    query: Query<(Entity, &ViewTarget), With<DepthOfFieldUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &dof_pipeline,
+            DepthOfFieldKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(DepthOfFieldPipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct DepthOfFieldNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static ViewPrepassTextures,
+        &'static DepthOfFieldPipelineId,
+        &'static DynamicUniformIndex<DepthOfFieldUniform>,
+    )>,
+}
+
+impl FromWorld for DepthOfFieldNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for DepthOfFieldNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, prepass, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let dof_pipeline = world.resource::<DepthOfFieldPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(depth) = &prepass.depth else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<DepthOfFieldUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_depth_of_field_bind_group",
+            &pipeline_cache.get_bind_group_layout(&dof_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &depth.texture.default_view,
+                uniform_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_depth_of_field_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
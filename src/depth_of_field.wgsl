@@ -0,0 +1,66 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+@group(0) @binding(1) var prepass_depth: texture_depth_2d;
+
+struct DepthOfFieldSettings {
+    focus_distance: f32,
+    focus_range: f32,
+    blur_distance: f32,
+    max_level: f32,                // log2 of the largest block
+    dither: f32,
+    near_blur: u32,
+    depth_to_view: vec4<f32>,      // clip_from_view z/w terms for view distance
+}
+@group(0) @binding(2) var<uniform> settings: DepthOfFieldSettings;
+
+// View distance of a prepass depth: inverts ndc = (a·z + b) / (c·z + d).
+// Background (perspective depth 0) maps to a huge distance.
+fn view_distance(depth: f32) -> f32 {
+    let m = settings.depth_to_view;
+    let denom = depth * m.z - m.x;
+    if (abs(denom) < 1e-8) {
+        return 1e8;
+    }
+    return abs((m.y - depth * m.w) / denom);
+}
+
+fn bayer4x4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) % 4u;
+    let y = u32(pos.y) % 4u;
+    var matrix = array<array<f32, 4>, 4>(
+        array<f32, 4>( 0.0/16.0,  8.0/16.0,  2.0/16.0, 10.0/16.0),
+        array<f32, 4>(12.0/16.0,  4.0/16.0, 14.0/16.0,  6.0/16.0),
+        array<f32, 4>( 3.0/16.0, 11.0/16.0,  1.0/16.0,  9.0/16.0),
+        array<f32, 4>(15.0/16.0,  7.0/16.0, 13.0/16.0,  5.0/16.0),
+    );
+    return matrix[y][x];
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = vec2<i32>(in.position.xy);
+    let size = vec2<i32>(textureDimensions(source_color));
+
+    // Blur amount: 0 inside the focus band, 1 at blur_distance past it.
+    var offset = view_distance(textureLoad(prepass_depth, texel, 0)) - settings.focus_distance;
+    if (offset < 0.0 && settings.near_blur == 0u) {
+        offset = 0.0;
+    }
+    let blur = clamp((abs(offset) - settings.focus_range) / settings.blur_distance, 0.0, 1.0);
+
+    // Continuous block level, dithered to a whole power of two. The pattern
+    // runs over blocks of the larger size, so the transition is blocky too.
+    let level_f = blur * settings.max_level;
+    let coarse = f32(1u << u32(floor(level_f) + 1.0));
+    let threshold = mix(0.5, bayer4x4(floor(in.position.xy / coarse)), settings.dither);
+    let level = u32(min(floor(level_f + threshold), settings.max_level));
+    if (level == 0u) {
+        return textureLoad(source_color, texel, 0);
+    }
+
+    // Mosaic: the whole block takes the color of its center texel.
+    let block = i32(1u << level);
+    let center = (texel / block) * block + block / 2;
+    return textureLoad(source_color, clamp(center, vec2<i32>(0), size - 1), 0);
+}
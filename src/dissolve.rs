@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{DissolveFinished, PixelArtInstanceParams};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Dithered dissolve of one entity's `PixelArtMaterial`.
+///
+/// `amount` animates toward `target` at `speed` per second and is written
+/// through the entity's `PixelArtInstanceParams` (inserted if missing), so
+/// dissolving entities keep sharing one material asset; `DissolveFinished` is
+/// written when it arrives. Removing the component clears the override. Edge
+/// color and width come from the material's `dissolve_edge_*` params. Needs
+/// storage buffers (not on `webgl2`, where `dissolve_amount` has to be set on
+/// the material instead).
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Dissolve {
+    /// Current dissolve amount (0 = solid, 1 = fully dissolved).
+    pub amount: f32,
+    /// Amount `amount` animates toward.
+    pub target: f32,
+    /// Change of `amount` per second (0 = jump straight to `target`).
+    pub speed: f32,
+}
+
+impl Default for Dissolve {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            target: 1.0,
+            speed: 1.0,
+        }
+    }
+}
+
+impl Dissolve {
+    /// Dissolve from solid to gone over `seconds` (despawn effect).
+    pub fn vanish(seconds: f32) -> Self {
+        Self {
+            amount: 0.0,
+            target: 1.0,
+            speed: 1.0 / seconds.max(1e-3),
+        }
+    }
+
+    /// Dissolve from gone to solid over `seconds` (spawn / teleport-in effect).
+    pub fn appear(seconds: f32) -> Self {
+        Self {
+            amount: 1.0,
+            target: 0.0,
+            speed: 1.0 / seconds.max(1e-3),
+        }
+    }
+
+    /// Whether the amount has reached its target.
+    pub fn is_finished(&self) -> bool {
+        self.amount == self.target
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Advances `Dissolve` components and writes their amount into the entity's
+/// `PixelArtInstanceParams`, and clears the override when the component is
+/// removed.
+pub fn animate_dissolve(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dissolving: Query<(Entity, &mut Dissolve, Option<&mut PixelArtInstanceParams>)>,
+    mut finished: MessageWriter<DissolveFinished>,
+    mut removed: RemovedComponents<Dissolve>,
+    mut cleared: Query<&mut PixelArtInstanceParams, Without<Dissolve>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut dissolve, instance) in &mut dissolving {
+        if !dissolve.is_finished() {
+            let step = if dissolve.speed > 0.0 {
+                dissolve.speed * dt
+            } else {
+                f32::INFINITY
+            };
+            let delta = (dissolve.target - dissolve.amount).clamp(-step, step);
+            dissolve.amount += delta;
+            if dissolve.is_finished() {
+                finished.write(DissolveFinished { entity });
+            }
+        }
+
+        let amount = Some(dissolve.amount.clamp(0.0, 1.0));
+        match instance {
+            Some(mut instance) => {
+                if instance.dissolve_amount != amount {
+                    instance.dissolve_amount = amount;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(PixelArtInstanceParams {
+                    dissolve_amount: amount,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    for entity in removed.read() {
+        if let Ok(mut params) = cleared.get_mut(entity)
+            && params.dissolve_amount.is_some()
+        {
+            params.dissolve_amount = None;
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+// ──────────────────────────────────────────────
+//  Animation messages
+// ──────────────────────────────────────────────
+//
+// Emitted once when an animation subsystem reaches its end state, so gameplay
+// code can chain despawns or state changes to a visual effect with a
+// `MessageReader` instead of polling material parameters.
+
+/// A `PaletteReveal` count animation or a `SwapPalette` swap (including its
+/// crossfade) reached its target.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct PaletteTransitionCompleted {
+    /// Number of palette entries revealed, or in the swapped-in palette, at the
+    /// end of the transition.
+    pub count: u32,
+}
+
+/// A tint flash on `entity` finished and its material is back to its base tint.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TintFlashFinished {
+    pub entity: Entity,
+}
+
+/// A dissolve on `entity` finished; the mesh is fully dissolved (or fully
+/// restored when the dissolve ran in reverse).
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DissolveFinished {
+    pub entity: Entity,
+}
+
+// ──────────────────────────────────────────────
+//  Palette editing messages
+// ──────────────────────────────────────────────
+
+/// One `PaletteEditor` operation applied to the `SharedPalette`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaletteEdit {
+    /// A color was inserted at `index`; later entries shifted up by one.
+    Inserted { index: usize },
+    /// The color at `index` was removed; later entries shifted down by one.
+    Removed { index: usize },
+    /// The color at `from` was moved to `to`.
+    Moved { from: usize, to: usize },
+    /// The color at `index` was replaced.
+    Replaced { index: usize },
+    /// The whole palette was replaced.
+    Reset,
+}
+
+/// The `SharedPalette` was edited through `PaletteEditor`.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct PaletteEdited {
+    pub edit: PaletteEdit,
+    /// Palette size after the edit.
+    pub count: u32,
+}
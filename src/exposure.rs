@@ -0,0 +1,134 @@
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+
+use crate::PixelArtMaterials;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Readback-driven auto-exposure for the pixel layer.
+///
+/// Put it on the low-res camera (rendering to an 8-bit RGBA image). Every
+/// `interval` frames the target is read back, its average luminance over
+/// covered (non-transparent) pixels is measured, and `exposure` moves toward
+/// the value that would bring it to `target_luminance`. The result is written
+/// to every `PixelArtMaterial`'s `exposure` param, which scales the lit color
+/// before toon and palette quantization.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtAutoExposure {
+    /// Average linear luminance to aim for (default: 0.3).
+    pub target_luminance: f32,
+    /// Adaptation rate in 1/seconds (default: 1.5).
+    pub speed: f32,
+    /// Exposure clamp (default: 0.25..=4.0).
+    pub min_exposure: f32,
+    pub max_exposure: f32,
+    /// Frames between readbacks (default: 10).
+    pub interval: u32,
+    /// Current exposure.
+    pub exposure: f32,
+    /// Last measured average luminance.
+    pub measured: Option<f32>,
+    #[reflect(ignore)]
+    frames_until_readback: u32,
+    #[reflect(ignore)]
+    pending: bool,
+}
+
+impl Default for PixelArtAutoExposure {
+    fn default() -> Self {
+        Self {
+            target_luminance: 0.3,
+            speed: 1.5,
+            min_exposure: 0.25,
+            max_exposure: 4.0,
+            interval: 10,
+            exposure: 1.0,
+            measured: None,
+            frames_until_readback: 0,
+            pending: false,
+        }
+    }
+}
+
+/// Average linear luminance of covered pixels in sRGB-encoded RGBA8 data.
+/// Row padding is zero-filled and skipped along with transparent pixels.
+fn average_luminance(data: &[u8]) -> Option<f32> {
+    let (sum, count) = data
+        .chunks_exact(4)
+        .filter(|px| px[3] > 0)
+        .map(|px| {
+            let c = Color::srgb_u8(px[0], px[1], px[2]).to_linear();
+            0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+        })
+        .fold((0.0, 0u32), |(sum, count), l| (sum + l, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Spawns a one-shot readback of each auto-exposure camera's target every
+/// `interval` frames.
+pub fn schedule_exposure_readback(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut PixelArtAutoExposure, &RenderTarget)>,
+) {
+    for (camera, mut auto, target) in &mut cameras {
+        let RenderTarget::Image(target) = target else {
+            continue;
+        };
+        if auto.pending {
+            continue;
+        }
+        if auto.frames_until_readback > 0 {
+            auto.frames_until_readback -= 1;
+            continue;
+        }
+        auto.frames_until_readback = auto.interval;
+        auto.pending = true;
+
+        commands
+            .spawn(Readback::texture(target.handle.clone()))
+            .observe(
+                move |event: On<ReadbackComplete>,
+                      mut commands: Commands,
+                      mut cameras: Query<&mut PixelArtAutoExposure>| {
+                    if let Ok(mut auto) = cameras.get_mut(camera) {
+                        auto.pending = false;
+                        if let Some(luminance) = average_luminance(&event.data) {
+                            auto.measured = Some(luminance);
+                        }
+                    }
+                    commands.entity(event.entity).despawn();
+                },
+            );
+    }
+}
+
+/// Moves each camera's exposure toward its target and writes it to the
+/// materials. Exposure is rounded to 1/100 so materials are only re-uploaded
+/// when it visibly changes.
+pub fn apply_auto_exposure(
+    time: Res<Time>,
+    mut cameras: Query<&mut PixelArtAutoExposure>,
+    mut materials: PixelArtMaterials,
+) {
+    let Some(mut auto) = cameras.iter_mut().next() else {
+        return;
+    };
+    if let Some(measured) = auto.measured {
+        // The readback already includes the current exposure.
+        let wanted = (auto.exposure * auto.target_luminance / measured.max(1e-4))
+            .clamp(auto.min_exposure, auto.max_exposure.max(auto.min_exposure));
+        let t = 1.0 - (-auto.speed * time.delta_secs()).exp();
+        auto.exposure += (wanted - auto.exposure) * t;
+    }
+
+    let exposure = (auto.exposure * 100.0).round() / 100.0;
+    materials.update_all(|p| p.exposure = exposure);
+}
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy::shader::ShaderDefVal;
+use bytemuck::{Pod, Zeroable};
+
+use crate::PixelArtMaterial;
+
+/// Optional shader features of `PixelArtExtension`.
+///
+/// Every feature except `wind` is compiled in by default. Disabling the ones a
+/// project does not use strips their code from the pixel art shader via shader
+/// defs, which shrinks the pipeline cache and speeds up compilation on
+/// constrained platforms (web, mobile). Parameters of a disabled feature are
+/// ignored.
+///
+/// Set through `PixelArtShaderPlugin::features`, which inserts it as a
+/// resource; each app keeps its own selection, and changing the resource at
+/// runtime re-specializes the materials.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelArtFeatures {
+    /// `PaletteReveal` / `reveal_*` params.
+    pub palette_reveal: bool,
+    /// Grayscale/sepia `stylize_*` params.
+    pub stylize: bool,
+    /// OKLab, linear RGB and sRGB `quantization_space` (CIELAB is always available).
+    pub color_spaces: bool,
+    /// Mask/vertex-color `sub_params`.
+    pub sub_materials: bool,
+    /// `shadow_hue_shift` / `highlight_hue_shift`.
+    pub hue_shift: bool,
+    /// `dither_stretch` / `dither_skew`.
+    pub directional_dither: bool,
+    /// `toon_thresholds` / `toon_levels`.
+    pub explicit_bands: bool,
+    /// `rim_strength` / `rim_width` / `rim_color`.
+    pub rim_light: bool,
+    /// `point_light_bands` / `spot_light_bands` / `light_falloff_bands`.
+    pub local_light_bands: bool,
+    /// Hard / dithered `shadow_edge`.
+    pub shadow_edge: bool,
+    /// Object / world / UV `dither_anchor`.
+    pub dither_anchor: bool,
+    /// Secondary palette `palette_blend_source` / `palette_b_*`.
+    pub palette_blend: bool,
+    /// `wind_*` vertex sway. Opt-in: replaces Bevy's mesh vertex shader with
+    /// the pixel art one for every material (default: false).
+    pub wind: bool,
+}
+
+impl Default for PixelArtFeatures {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl PixelArtFeatures {
+    /// Every optional feature but the opt-in `wind`.
+    pub const DEFAULT: Self = Self {
+        wind: false,
+        ..Self::all()
+    };
+
+    /// Every optional feature enabled.
+    pub const fn all() -> Self {
+        Self {
+            palette_reveal: true,
+            stylize: true,
+            color_spaces: true,
+            sub_materials: true,
+            hue_shift: true,
+            directional_dither: true,
+            explicit_bands: true,
+            rim_light: true,
+            local_light_bands: true,
+            shadow_edge: true,
+            dither_anchor: true,
+            palette_blend: true,
+            wind: true,
+        }
+    }
+
+    /// Only the core pipeline: toon bands, CIELAB palette, Bayer dither.
+    pub const fn minimal() -> Self {
+        Self {
+            palette_reveal: false,
+            stylize: false,
+            color_spaces: false,
+            sub_materials: false,
+            hue_shift: false,
+            directional_dither: false,
+            explicit_bands: false,
+            rim_light: false,
+            local_light_bands: false,
+            shadow_edge: false,
+            dither_anchor: false,
+            palette_blend: false,
+            wind: false,
+        }
+    }
+
+    /// Shader defs enabling the selected features.
+    pub fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        self.flags()
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, def)| def.into())
+            .collect()
+    }
+
+    /// Features packed one bit per flag, in `flags` order.
+    pub fn to_bits(&self) -> u32 {
+        self.flags()
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (i, (enabled, _))| {
+                bits | (u32::from(*enabled) << i)
+            })
+    }
+
+    /// Inverse of `to_bits`.
+    pub fn from_bits(bits: u32) -> Self {
+        let mut features = Self::minimal();
+        for (i, flag) in features.flags_mut().into_iter().enumerate() {
+            *flag = bits & (1 << i) != 0;
+        }
+        features
+    }
+
+    fn flags(&self) -> [(bool, &'static str); 13] {
+        [
+            (self.palette_reveal, "PIXEL_ART_PALETTE_REVEAL"),
+            (self.stylize, "PIXEL_ART_STYLIZE"),
+            (self.color_spaces, "PIXEL_ART_COLOR_SPACES"),
+            (self.sub_materials, "PIXEL_ART_SUB_MATERIALS"),
+            (self.hue_shift, "PIXEL_ART_HUE_SHIFT"),
+            (self.directional_dither, "PIXEL_ART_DIRECTIONAL_DITHER"),
+            (self.explicit_bands, "PIXEL_ART_EXPLICIT_BANDS"),
+            (self.rim_light, "PIXEL_ART_RIM_LIGHT"),
+            (self.local_light_bands, "PIXEL_ART_LOCAL_LIGHT_BANDS"),
+            (self.shadow_edge, "PIXEL_ART_SHADOW_EDGE"),
+            (self.dither_anchor, "PIXEL_ART_DITHER_ANCHOR"),
+            (self.palette_blend, "PIXEL_ART_PALETTE_BLEND"),
+            (self.wind, "PIXEL_ART_WIND"),
+        ]
+    }
+
+    fn flags_mut(&mut self) -> [&mut bool; 13] {
+        [
+            &mut self.palette_reveal,
+            &mut self.stylize,
+            &mut self.color_spaces,
+            &mut self.sub_materials,
+            &mut self.hue_shift,
+            &mut self.directional_dither,
+            &mut self.explicit_bands,
+            &mut self.rim_light,
+            &mut self.local_light_bands,
+            &mut self.shadow_edge,
+            &mut self.dither_anchor,
+            &mut self.palette_blend,
+            &mut self.wind,
+        ]
+    }
+}
+
+/// Specialization key of `PixelArtExtension`: the features its pipelines are
+/// compiled with, from `PixelArtExtension::features`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Pod, Zeroable)]
+pub struct PixelArtExtensionKey {
+    pub features: u32,
+}
+
+// ──────────────────────────────────────────────
+//  System
+// ──────────────────────────────────────────────
+
+/// Copies the `PixelArtFeatures` resource into every material when it
+/// changes, and into newly added materials, so their pipelines specialize
+/// with this app's selection.
+pub fn sync_pixel_art_features(
+    features: Res<PixelArtFeatures>,
+    mut events: MessageReader<AssetEvent<PixelArtMaterial>>,
+    mut materials: ResMut<Assets<PixelArtMaterial>>,
+) {
+    let ids: Vec<_> = if features.is_changed() {
+        events.clear();
+        materials.ids().collect()
+    } else {
+        events
+            .read()
+            .filter_map(|event| match event {
+                AssetEvent::Added { id } => Some(*id),
+                _ => None,
+            })
+            .collect()
+    };
+
+    for id in ids {
+        let stale = materials
+            .get(id)
+            .is_some_and(|mat| mat.extension.features != *features);
+        if stale && let Some(mat) = materials.get_mut(id) {
+            mat.extension.features = *features;
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{PixelArtInstanceParams, TintFlashFinished};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Hit flash on one entity: its material's `flash_color` is drawn over the
+/// quantized result at full coverage, then fades out through the Bayer
+/// pattern over `duration` seconds.
+///
+/// Written through the entity's `PixelArtInstanceParams` (inserted if
+/// missing), so flashing entities keep sharing one material asset. The
+/// component removes itself and writes `TintFlashFinished` when done;
+/// inserting it again restarts the flash. Needs storage buffers (not on
+/// `webgl2`, where `flash_amount` has to be set on the material instead).
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TintFlash {
+    /// Flash color, snapped to the palette.
+    pub color: Color,
+    /// Fade-out time in seconds.
+    pub duration: f32,
+    /// Seconds since the flash started.
+    pub elapsed: f32,
+}
+
+impl Default for TintFlash {
+    fn default() -> Self {
+        Self::hit(0.15)
+    }
+}
+
+impl TintFlash {
+    /// Flash `color`, fading out over `seconds`.
+    pub fn new(color: Color, seconds: f32) -> Self {
+        Self {
+            color,
+            duration: seconds,
+            elapsed: 0.0,
+        }
+    }
+
+    /// White damage flash over `seconds`.
+    pub fn hit(seconds: f32) -> Self {
+        Self::new(Color::WHITE, seconds)
+    }
+
+    /// Current flash coverage (1 = solid, 0 = gone).
+    pub fn amount(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+/// Bayer thresholds are multiples of 1/64 (1/16 and 1/4 for the smaller
+/// matrices), so rounding the coverage up to them draws the same pixels.
+const FLASH_STEPS: f32 = 64.0;
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Advances `TintFlash` components and writes their coverage into the
+/// entity's `PixelArtInstanceParams`, only when the drawn coverage changes;
+/// clears the override and writes `TintFlashFinished` when a flash ends.
+pub fn animate_tint_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut TintFlash, Option<&mut PixelArtInstanceParams>)>,
+    mut finished: MessageWriter<TintFlashFinished>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut flash, instance) in &mut flashes {
+        flash.elapsed += dt;
+        let done = flash.elapsed >= flash.duration;
+        let amount = (!done).then(|| (flash.amount() * FLASH_STEPS).ceil() / FLASH_STEPS);
+        let color = flash.color.to_linear().to_vec4();
+
+        match instance {
+            Some(mut instance)
+                if instance.flash_amount != amount || instance.flash_color != color =>
+            {
+                instance.flash_amount = amount;
+                instance.flash_color = color;
+            }
+            None if !done => {
+                commands.entity(entity).insert(PixelArtInstanceParams {
+                    flash_color: color,
+                    flash_amount: amount,
+                    ..default()
+                });
+            }
+            _ => {}
+        }
+
+        if done {
+            commands.entity(entity).remove::<TintFlash>();
+            finished.write(TintFlashFinished { entity });
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{texture_2d, uniform_buffer},
+            *,
+        },
+        renderer::RenderContext,
+        view::ViewTarget,
+    },
+};
+
+use crate::palette::MAX_UNIFORM_PALETTE_COLORS;
+use crate::pass_order::order_low_res_passes;
+use crate::{PixelArtPalette, SharedPalette};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Glitch / datamosh pass on the low-res camera, for damage states and
+/// cyberpunk looks.
+///
+/// Runs last on the low-res image: bands of rows are displaced sideways,
+/// blocks repeat their left neighbor, and blocks have their palette indices
+/// shifted, so corrupted pixels stay on-palette (first 64 colors of the
+/// `SharedPalette`, or the default palette). The random pattern changes
+/// `rate` times per second. Every effect scales with `intensity`; animate it
+/// (e.g. from a damage timer) and the pass costs nothing at 0.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtGlitch {
+    /// Overall strength, multiplying every probability and the row shift
+    /// (0 = off, default: 1.0).
+    pub intensity: f32,
+    /// Pattern changes per second (default: 12.0).
+    pub rate: f32,
+    /// Height of displaced row bands in texels (default: 4).
+    pub row_height: u32,
+    /// Fraction of row bands displaced (default: 0.15).
+    pub row_probability: f32,
+    /// Largest sideways row shift in texels (default: 12.0).
+    pub row_shift: f32,
+    /// Block size in texels for repetition and palette corruption
+    /// (default: 8).
+    pub block_size: u32,
+    /// Fraction of blocks repeating their left neighbor (default: 0.05).
+    pub block_repeat: f32,
+    /// Fraction of blocks whose palette indices are shifted (default: 0.05).
+    pub palette_corruption: f32,
+    /// Seconds since the glitch started, advanced by `tick_pixel_art_glitch`.
+    pub elapsed: f32,
+    /// Palette colors maintained by `tick_pixel_art_glitch`.
+    #[reflect(ignore)]
+    palette: Vec<Vec4>,
+}
+
+impl Default for PixelArtGlitch {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            rate: 12.0,
+            row_height: 4,
+            row_probability: 0.15,
+            row_shift: 12.0,
+            block_size: 8,
+            block_repeat: 0.05,
+            palette_corruption: 0.05,
+            elapsed: 0.0,
+            palette: PixelArtPalette::default().colors,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct GlitchUniform {
+    /// Pattern seed: `floor(elapsed * rate)`.
+    pub step: f32,
+    pub row_height: u32,
+    pub row_probability: f32,
+    pub row_shift: f32,
+    pub block_size: u32,
+    pub block_repeat: f32,
+    pub palette_corruption: f32,
+    pub palette_count: u32,
+    pub palette_colors: [Vec4; MAX_UNIFORM_PALETTE_COLORS],
+}
+
+impl ExtractComponent for GlitchUniform {
+    type QueryData = &'static PixelArtGlitch;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        glitch: bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        // No uniform at zero intensity: the node skips the view.
+        if glitch.intensity <= 0.0 {
+            return None;
+        }
+        let intensity = glitch.intensity.min(1.0);
+        let mut palette_colors = [Vec4::ZERO; MAX_UNIFORM_PALETTE_COLORS];
+        let count = glitch.palette.len().min(MAX_UNIFORM_PALETTE_COLORS);
+        palette_colors[..count].copy_from_slice(&glitch.palette[..count]);
+        Some(GlitchUniform {
+            step: (glitch.elapsed * glitch.rate.max(0.0)).floor(),
+            row_height: glitch.row_height.max(1),
+            row_probability: glitch.row_probability * intensity,
+            row_shift: glitch.row_shift * intensity,
+            block_size: glitch.block_size.max(1),
+            block_repeat: glitch.block_repeat * intensity,
+            palette_corruption: glitch.palette_corruption * intensity,
+            palette_count: count as u32,
+            palette_colors,
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtGlitchPlugin;
+
+impl Plugin for PixelArtGlitchPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "glitch.wgsl");
+
+        app.register_type::<PixelArtGlitch>();
+        app.add_plugins((
+            ExtractComponentPlugin::<GlitchUniform>::default(),
+            UniformComponentPlugin::<GlitchUniform>::default(),
+        ));
+        app.add_systems(Update, tick_pixel_art_glitch);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<GlitchPipeline>>()
+            .add_systems(
+                Render,
+                prepare_glitch_pipelines.in_set(RenderSystems::Prepare),
+            )
+            .add_render_graph_node::<GlitchNode>(Core3d, PixelArtGlitchLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (Node3d::Tonemapping, PixelArtGlitchLabel, Node3d::Fxaa),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp).init_resource::<GlitchPipeline>();
+        order_low_res_passes(app);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Main-world system
+// ──────────────────────────────────────────────
+
+/// Advances `PixelArtGlitch::elapsed` and copies the active palette into each
+/// glitch when it or the palette changes.
+pub fn tick_pixel_art_glitch(
+    time: Res<Time>,
+    shared: Option<Res<SharedPalette>>,
+    mut glitches: Query<&mut PixelArtGlitch>,
+    mut default_palette: Local<Option<PixelArtPalette>>,
+) {
+    let palette_changed = shared.as_ref().is_some_and(|s| s.is_changed());
+    let palette = match &shared {
+        Some(shared) => &shared.0,
+        None => default_palette.get_or_insert_with(PixelArtPalette::default),
+    };
+
+    for mut glitch in &mut glitches {
+        if glitch.is_added() || palette_changed {
+            glitch.palette = palette.colors.clone();
+        }
+        if glitch.intensity > 0.0 {
+            glitch.elapsed += time.delta_secs();
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct GlitchPipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtGlitchLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct GlitchPipeline {
+    pub shader: Handle<Shader>,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for GlitchPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "glitch.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_glitch: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: glitch uniform
+                    uniform_buffer::<GlitchUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            shader,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlitchKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for GlitchPipeline {
+    type Key = GlitchKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_glitch: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_glitch_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<GlitchPipeline>>,
+    glitch_pipeline: Res<GlitchPipeline>,
+    query: Query<(Entity, &ViewTarget), With<GlitchUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &glitch_pipeline,
+            GlitchKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(GlitchPipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct GlitchNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static GlitchPipelineId,
+        &'static DynamicUniformIndex<GlitchUniform>,
+    )>,
+}
+
+impl FromWorld for GlitchNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for GlitchNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let glitch_pipeline = world.resource::<GlitchPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<GlitchUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_glitch_bind_group",
+            &pipeline_cache.get_bind_group_layout(&glitch_pipeline.layout),
+            &BindGroupEntries::sequential((post_process.source, uniform_binding)),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_glitch_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
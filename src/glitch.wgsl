@@ -0,0 +1,100 @@
+//! Glitch / datamosh pass on the low-res image: displaced row bands,
+//! repeated blocks and palette-index corruption, re-randomized every
+//! `step`.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+
+struct GlitchSettings {
+    step: f32,                     // pattern seed, changes `rate` times per second
+    row_height: u32,
+    row_probability: f32,
+    row_shift: f32,                // texels
+    block_size: u32,
+    block_repeat: f32,
+    palette_corruption: f32,
+    palette_count: u32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+@group(0) @binding(1) var<uniform> settings: GlitchSettings;
+
+// Hash of an integer cell and the pattern step to [0, 1).
+fn hash(cell: vec2<i32>, salt: u32) -> f32 {
+    var h = (bitcast<u32>(cell.x) * 0x8da6b343u) ^ (bitcast<u32>(cell.y) * 0xd8163841u);
+    h ^= u32(settings.step) * 0xcb1ab31fu + salt * 0x165667b1u;
+    h = (h ^ (h >> 15u)) * 0x2c1b3c6du;
+    h = (h ^ (h >> 12u)) * 0x297a2d39u;
+    h ^= h >> 15u;
+    return f32(h >> 8u) / 16777216.0;
+}
+
+// ============================================================================
+// Color spaces
+// ============================================================================
+
+fn lab_f(t: f32) -> f32 {
+    let delta: f32 = 6.0 / 29.0;
+    if (t > delta * delta * delta) {
+        return pow(t, 1.0 / 3.0);
+    } else {
+        return t / (3.0 * delta * delta) + 4.0 / 29.0;
+    }
+}
+
+fn linear_rgb_to_lab(rgb: vec3<f32>) -> vec3<f32> {
+    let x = dot(vec3<f32>(0.4124564, 0.3575761, 0.1804375), rgb) / 0.95047;
+    let y = dot(vec3<f32>(0.2126729, 0.7151522, 0.0721750), rgb);
+    let z = dot(vec3<f32>(0.0193339, 0.1191920, 0.9503041), rgb) / 1.08883;
+    let fx = lab_f(x);
+    let fy = lab_f(y);
+    let fz = lab_f(z);
+    return vec3<f32>(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz));
+}
+
+// Index of the palette entry nearest to `rgb` (CIELAB).
+fn nearest_index(rgb: vec3<f32>) -> u32 {
+    let lab = linear_rgb_to_lab(rgb);
+    var best = 0u;
+    var best_d = 1e10;
+    for (var i = 0u; i < settings.palette_count; i++) {
+        let d = distance(lab, linear_rgb_to_lab(settings.palette_colors[i].rgb));
+        if (d < best_d) {
+            best_d = d;
+            best = i;
+        }
+    }
+    return best;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(source_color));
+    var texel = vec2<i32>(in.position.xy);
+
+    // Row displacement: whole bands of rows slide sideways.
+    let band = vec2<i32>(0, texel.y / i32(settings.row_height));
+    if (hash(band, 1u) < settings.row_probability) {
+        let shift = (hash(band, 2u) * 2.0 - 1.0) * settings.row_shift;
+        texel.x = (texel.x + i32(round(shift)) + size.x) % size.x;
+    }
+
+    // Block repetition: a block copies one of its left neighbors.
+    let block_size = i32(settings.block_size);
+    let block = texel / block_size;
+    if (hash(block, 3u) < settings.block_repeat) {
+        let back = 1 + i32(hash(block, 4u) * 3.0);
+        texel.x = max(texel.x - back * block_size, texel.x % block_size);
+    }
+
+    var color = textureLoad(source_color, clamp(texel, vec2<i32>(0), size - 1), 0);
+
+    // Palette corruption: shift the block's palette indices, so corrupted
+    // pixels stay on-palette.
+    if (settings.palette_count > 1u && color.a > 0.0 && hash(block, 5u) < settings.palette_corruption) {
+        let offset = 1u + u32(hash(block, 6u) * f32(settings.palette_count - 1u));
+        let index = (nearest_index(color.rgb) + offset) % settings.palette_count;
+        color = vec4<f32>(settings.palette_colors[index].rgb, color.a);
+    }
+    return color;
+}
@@ -0,0 +1,63 @@
+use bevy::light::{NotShadowCaster, NotShadowReceiver};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+
+use crate::HoldoutMaterial;
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Holdout entity with its material and current shadow markers.
+type HoldoutShadowData = (
+    Entity,
+    Ref<'static, MeshMaterial3d<HoldoutMaterial>>,
+    Has<NotShadowCaster>,
+    Has<NotShadowReceiver>,
+);
+
+/// Mirrors `HoldoutExtension::cast_shadows` / `receive_shadows` onto each
+/// holdout entity as `NotShadowCaster` / `NotShadowReceiver`, when the entity
+/// gets a holdout material or that material changes.
+pub fn sync_holdout_shadows(
+    mut commands: Commands,
+    materials: Res<Assets<HoldoutMaterial>>,
+    mut events: MessageReader<AssetEvent<HoldoutMaterial>>,
+    holdouts: Query<HoldoutShadowData>,
+) {
+    let modified: HashSet<AssetId<HoldoutMaterial>> = events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, material, not_caster, not_receiver) in &holdouts {
+        if !material.is_changed() && !modified.contains(&material.0.id()) {
+            continue;
+        }
+        let Some(holdout) = materials.get(&material.0) else {
+            continue;
+        };
+        let mut entity = commands.entity(entity);
+        match (holdout.extension.cast_shadows, not_caster) {
+            (true, true) => {
+                entity.remove::<NotShadowCaster>();
+            }
+            (false, false) => {
+                entity.insert(NotShadowCaster);
+            }
+            _ => {}
+        }
+        match (holdout.extension.receive_shadows, not_receiver) {
+            (true, true) => {
+                entity.remove::<NotShadowReceiver>();
+            }
+            (false, false) => {
+                entity.insert(NotShadowReceiver);
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,11 +1,20 @@
-//! Holdout prepass: writes depth (for occlusion) and normal alpha=0.0
-//! so edge detection ignores holdout geometry.
+//! Holdout prepass: writes depth (for occlusion) and normal alpha =
+//! `prepass_alpha * outline_participation` (default 0.0) so edge detection
+//! ignores holdout geometry.
 
 #import bevy_pbr::{
     prepass_io::{VertexOutput, FragmentOutput},
     pbr_prepass_functions,
 }
 
+struct HoldoutParams {
+    prepass_alpha: f32,
+    outline_participation: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> holdout: HoldoutParams;
+
 #ifdef PREPASS_FRAGMENT
 @fragment
 fn fragment(
@@ -21,8 +30,11 @@ fn fragment(
 #endif
 
 #ifdef NORMAL_PREPASS
-    // Alpha 0.0 → edge detection suppressed on holdout pixels
-    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), 0.0);
+    // Alpha 0.0 (default) → edge detection suppressed on holdout pixels;
+    // ⅓ (HOLDOUT_MASK_ALPHA, which documents the mask encoding) also marks
+    // them in the holdout mask
+    let mask = holdout.prepass_alpha * holdout.outline_participation;
+    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), mask);
 #endif
 
 #ifdef MOTION_VECTOR_PREPASS
@@ -0,0 +1,151 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+
+use crate::{DEFAULT_PALETTE_BUFFER, default_pixel_art_palette};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Material type alias: StandardMaterial + PixelArtHologramExtension.
+pub type PixelArtHologramMaterial = ExtendedMaterial<StandardMaterial, PixelArtHologramExtension>;
+
+/// Hologram / ghost look for the low-res layer: the base color texture's
+/// luminance in a palette-snapped tint (cyan body, magenta rim by default),
+/// scrolling scanline bands and Bayer screen-door transparency.
+///
+/// Unlit, with no shadows and no prepass, so it gets no screen-space outline
+/// and never hides what is behind its dithered holes. Useful for ghosts,
+/// holograms and building placement previews.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default)]
+pub struct PixelArtHologramExtension {
+    #[uniform(100)]
+    pub params: PixelArtHologramParams,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(101, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+}
+
+impl Default for PixelArtHologramExtension {
+    fn default() -> Self {
+        Self {
+            params: PixelArtHologramParams::default(),
+            palette: DEFAULT_PALETTE_BUFFER,
+        }
+    }
+}
+
+impl PixelArtHologramExtension {
+    /// Hologram material over `base` (its color and texture give the detail).
+    pub fn material(self, base: StandardMaterial) -> PixelArtHologramMaterial {
+        ExtendedMaterial {
+            base,
+            extension: self,
+        }
+    }
+
+    /// Set `color` and `rim_color`.
+    pub fn with_colors(mut self, color: Color, rim_color: Color) -> Self {
+        self.params.color = color.to_linear().to_vec4();
+        self.params.rim_color = rim_color.to_linear().to_vec4();
+        self
+    }
+
+    /// Set `opacity`.
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.params.opacity = opacity;
+        self
+    }
+}
+
+impl MaterialExtension for PixelArtHologramExtension {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/hologram.wgsl".into()
+    }
+
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+        Ok(())
+    }
+}
+
+/// GPU-side hologram parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType, Reflect)]
+#[reflect(Default)]
+pub struct PixelArtHologramParams {
+    /// Body tint (linear RGB), snapped to the palette (default: cyan).
+    pub color: Vec4,
+    /// Rim tint on grazing angles (linear RGB), snapped to the palette
+    /// (default: magenta).
+    pub rim_color: Vec4,
+    /// Grazing-angle width of the rim (0 = no rim, default: 0.35).
+    pub rim_width: f32,
+    /// How much of the base color's luminance shows through the tint
+    /// (0 = flat tint, default: 0.6).
+    pub detail: f32,
+    /// Fraction of Bayer cells drawn (1 = solid, default: 0.6).
+    pub opacity: f32,
+    /// Scanline band height in pixels (0 = no scanlines, default: 2.0).
+    pub scanline_height: f32,
+    /// Scanline scroll speed in pixels per second (default: 8.0).
+    pub scanline_speed: f32,
+    /// Brightness loss of the dark scanline bands (default: 0.4).
+    pub scanline_strength: f32,
+    /// Extra opacity loss in the dark scanline bands (default: 0.25).
+    pub scanline_opacity: f32,
+    /// Number of active palette colors (0 = no palette snapping, default: the
+    /// default palette's count).
+    pub palette_count: u32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArtHologramParams {
+    fn default() -> Self {
+        let (palette, count) = default_pixel_art_palette();
+        let linear = |r: u8, g: u8, b: u8| {
+            let c = Color::srgb_u8(r, g, b).to_linear();
+            Vec4::new(c.red, c.green, c.blue, 1.0)
+        };
+        Self {
+            color: linear(41, 173, 255),
+            rim_color: linear(255, 119, 168),
+            rim_width: 0.35,
+            detail: 0.6,
+            opacity: 0.6,
+            scanline_height: 2.0,
+            scanline_speed: 8.0,
+            scanline_strength: 0.4,
+            scanline_opacity: 0.25,
+            palette_count: count,
+            palette_colors: palette,
+        }
+    }
+}
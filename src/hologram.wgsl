@@ -0,0 +1,134 @@
+//! Pixel art hologram / ghost: base luminance in a palette-snapped tint with
+//! a grazing-angle rim, scrolling scanline bands and Bayer screen-door
+//! transparency. Unlit; no prepass or shadows.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::main_pass_post_lighting_processing,
+    mesh_view_bindings::globals,
+}
+
+struct PixelArtHologramParams {
+    color: vec4<f32>,
+    rim_color: vec4<f32>,
+    rim_width: f32,
+    detail: f32,
+    opacity: f32,
+    scanline_height: f32,
+    scanline_speed: f32,
+    scanline_strength: f32,
+    scanline_opacity: f32,
+    palette_count: u32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> hologram: PixelArtHologramParams;
+
+#ifdef PALETTE_STORAGE_BUFFER
+@group(#{MATERIAL_BIND_GROUP}) @binding(101)
+var<storage, read> palette_storage: array<vec4<f32>>;
+#endif
+
+fn palette_len() -> u32 {
+#ifdef PALETTE_STORAGE_BUFFER
+    return arrayLength(&palette_storage);
+#else
+    return 64u;
+#endif
+}
+
+fn palette_color(i: u32) -> vec3<f32> {
+#ifdef PALETTE_STORAGE_BUFFER
+    return palette_storage[i].rgb;
+#else
+    return hologram.palette_colors[i].rgb;
+#endif
+}
+
+// 4x4 Bayer threshold in [0, 1).
+fn bayer4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) & 3u;
+    let y = u32(pos.y) & 3u;
+    var index = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / 16.0;
+}
+
+fn linear_rgb_to_oklab(rgb: vec3<f32>) -> vec3<f32> {
+    let l = dot(vec3<f32>(0.4122214708, 0.5363325363, 0.0514459929), rgb);
+    let m = dot(vec3<f32>(0.2119034982, 0.6806995451, 0.1073969566), rgb);
+    let s = dot(vec3<f32>(0.0883024619, 0.2817188376, 0.6299787005), rgb);
+    let lms = pow(max(vec3<f32>(l, m, s), vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        dot(vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468), lms),
+        dot(vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099), lms),
+        dot(vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660), lms),
+    );
+}
+
+// Nearest palette entry in OKLab.
+fn snap_to_palette(color: vec3<f32>) -> vec3<f32> {
+    let lab = linear_rgb_to_oklab(color);
+    var best = color;
+    var best_distance = 1e10;
+    let n = min(hologram.palette_count, palette_len());
+    for (var i = 0u; i < n; i++) {
+        let pal_rgb = palette_color(i);
+        let d = distance(lab, linear_rgb_to_oklab(pal_rgb));
+        if (d < best_distance) {
+            best_distance = d;
+            best = pal_rgb;
+        }
+    }
+    return best;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    let pbr_input = pbr_input_from_standard_material(in, is_front);
+    let cell = floor(in.position.xy);
+
+    // --- Scanline bands scrolling down the screen ---
+    var dark_band = false;
+    if (hologram.scanline_height > 0.0) {
+        let row = floor((in.position.y - globals.time * hologram.scanline_speed) / hologram.scanline_height);
+        dark_band = (i32(row) & 1) == 1;
+    }
+
+    // --- Screen-door transparency, thinner in the dark bands ---
+    var opacity = hologram.opacity;
+    if (dark_band) {
+        opacity -= hologram.scanline_opacity;
+    }
+    if (bayer4(cell) >= opacity) {
+        discard;
+    }
+
+    // --- Body / rim tint over the base luminance ---
+    let base = pbr_input.material.base_color.rgb;
+    let luminance = dot(base, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let facing = saturate(dot(pbr_input.N, pbr_input.V));
+    let on_rim = hologram.rim_width > 0.0 && 1.0 - facing >= 1.0 - hologram.rim_width;
+    var color = select(hologram.color.rgb, hologram.rim_color.rgb, on_rim);
+    color *= mix(1.0, luminance, saturate(hologram.detail));
+    if (dark_band) {
+        color *= 1.0 - saturate(hologram.scanline_strength);
+    }
+
+    if (hologram.palette_count > 0u) {
+        color = snap_to_palette(color);
+    }
+
+    var out: FragmentOutput;
+    out.color = main_pass_post_lighting_processing(pbr_input, vec4<f32>(color, 1.0));
+    return out;
+}
@@ -0,0 +1,254 @@
+use bevy::asset::uuid_handle;
+use bevy::mesh::MeshTag;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::extract_resource::ExtractResource;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{BufferUsages, ShaderType};
+use bevy::render::renderer::RenderQueue;
+use bevy::render::storage::{GpuShaderStorageBuffer, ShaderStorageBuffer};
+
+use crate::PixelArtMaterials;
+
+/// Storage buffer holding one `PixelArtInstanceData` per
+/// `PixelArtInstanceParams` entity. Inserted by `PixelArtShaderPlugin` and
+/// bound by `PixelArtExtension::default()`.
+pub const INSTANCE_PARAMS_BUFFER: Handle<ShaderStorageBuffer> =
+    uuid_handle!("b7e2c4d1-6a3f-4e58-8c19-2d0f7a9e5b43");
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Per-entity overrides of its `PixelArtMaterial`'s params.
+///
+/// Entities sharing one material asset can differ in tint, dither strength,
+/// hit flash, outline color and dissolve while still batching together. The overrides live in a storage buffer
+/// slot addressed by the entity's `MeshTag`, which this component manages:
+/// don't combine it with a `MeshTag` of your own. Not available with the
+/// `webgl2` feature (no storage buffers).
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtInstanceParams {
+    /// Multiplies the material's `base_tint` (linear RGBA, default: white).
+    pub tint: Vec4,
+    /// Replaces the material's `dither_strength` (default: `None`, keep it).
+    pub dither_strength: Option<f32>,
+    /// Replaces the material's `flash_color` while `flash_amount` is set
+    /// (linear RGB, default: white).
+    pub flash_color: Vec4,
+    /// Replaces the material's `flash_amount` (default: `None`, keep it).
+    /// Driven by `TintFlash`.
+    pub flash_amount: Option<f32>,
+    /// Line color drawn by the material itself (linear RGBA, alpha 0 = none,
+    /// default). Driven by `OutlineColor`.
+    pub outline_color: Vec4,
+    /// Replaces the material's `dissolve_amount` (default: `None`, keep it).
+    /// Driven by `Dissolve`.
+    pub dissolve_amount: Option<f32>,
+}
+
+impl Default for PixelArtInstanceParams {
+    fn default() -> Self {
+        Self {
+            tint: Vec4::ONE,
+            dither_strength: None,
+            flash_color: Vec4::ONE,
+            flash_amount: None,
+            outline_color: Vec4::ZERO,
+            dissolve_amount: None,
+        }
+    }
+}
+
+impl PixelArtInstanceParams {
+    /// Override only the tint.
+    pub fn tint(color: Color) -> Self {
+        Self {
+            tint: color.to_linear().to_vec4(),
+            ..default()
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU data
+// ──────────────────────────────────────────────
+
+/// One slot of `INSTANCE_PARAMS_BUFFER`. Must match the WGSL struct layout.
+#[derive(Clone, Copy, Debug, PartialEq, ShaderType)]
+pub struct PixelArtInstanceData {
+    pub tint: Vec4,
+    /// Negative = use the material's `dither_strength`.
+    pub dither_strength: f32,
+    pub flash_color: Vec4,
+    /// Negative = use the material's `flash_color` and `flash_amount`.
+    pub flash_amount: f32,
+    /// Alpha 0 = use the material's `outline_color`.
+    pub outline_color: Vec4,
+    /// Negative = use the material's `dissolve_amount`.
+    pub dissolve_amount: f32,
+}
+
+impl Default for PixelArtInstanceData {
+    fn default() -> Self {
+        Self {
+            tint: Vec4::ONE,
+            dither_strength: -1.0,
+            flash_color: Vec4::ONE,
+            flash_amount: -1.0,
+            outline_color: Vec4::ZERO,
+            dissolve_amount: -1.0,
+        }
+    }
+}
+
+impl From<&PixelArtInstanceParams> for PixelArtInstanceData {
+    fn from(params: &PixelArtInstanceParams) -> Self {
+        Self {
+            tint: params.tint,
+            dither_strength: params.dither_strength.map_or(-1.0, |s| s.max(0.0)),
+            flash_color: params.flash_color,
+            flash_amount: params.flash_amount.map_or(-1.0, |a| a.clamp(0.0, 1.0)),
+            outline_color: params.outline_color,
+            dissolve_amount: params.dissolve_amount.map_or(-1.0, |a| a.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// Buffer slot per entity. Slot 0 is the identity that untagged meshes read.
+///
+/// `data` is the buffer's capacity: it grows by doubling, so the GPU buffer
+/// (and the material bind groups holding it) only has to be recreated when
+/// slots run out; other edits are written into it in place.
+#[derive(Resource)]
+pub struct InstanceParamSlots {
+    slots: HashMap<Entity, u32>,
+    free: Vec<u32>,
+    /// Slots handed out so far, including slot 0.
+    len: u32,
+    data: Vec<PixelArtInstanceData>,
+}
+
+impl Default for InstanceParamSlots {
+    fn default() -> Self {
+        Self {
+            slots: HashMap::default(),
+            free: Vec::new(),
+            len: 1,
+            data: vec![PixelArtInstanceData::default(); 16],
+        }
+    }
+}
+
+impl InstanceParamSlots {
+    /// Storage buffer of every slot. Writable, for in-place updates.
+    pub fn to_storage_buffer(&self) -> ShaderStorageBuffer {
+        let mut buffer = ShaderStorageBuffer::from(self.data.clone());
+        buffer.buffer_description.usage |= BufferUsages::COPY_DST;
+        buffer
+    }
+
+    /// A free slot; `true` if the buffer had to grow for it.
+    fn allocate(&mut self) -> (u32, bool) {
+        if let Some(slot) = self.free.pop() {
+            return (slot, false);
+        }
+        let slot = self.len;
+        self.len += 1;
+        let grown = slot as usize >= self.data.len();
+        if grown {
+            let capacity = self.data.len() * 2;
+            self.data.resize(capacity, PixelArtInstanceData::default());
+        }
+        (slot, grown)
+    }
+}
+
+/// Encoded slot data to write into the existing `INSTANCE_PARAMS_BUFFER`,
+/// extracted to the render world when it changes.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct InstanceParamUpload {
+    bytes: Vec<u8>,
+    generation: u64,
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Assigns buffer slots (and `MeshTag`s) to `PixelArtInstanceParams` entities,
+/// frees them on removal, and queues the slot data for an in-place write when
+/// anything changed. Only a grown buffer is re-created (and its materials
+/// rebound).
+pub fn sync_instance_params(
+    mut commands: Commands,
+    mut slots: ResMut<InstanceParamSlots>,
+    changed: Query<(Entity, &PixelArtInstanceParams), Changed<PixelArtInstanceParams>>,
+    mut removed: RemovedComponents<PixelArtInstanceParams>,
+    mut upload: ResMut<InstanceParamUpload>,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    mut materials: PixelArtMaterials,
+) {
+    let mut dirty = false;
+    let mut grown = false;
+
+    for entity in removed.read() {
+        let Some(slot) = slots.slots.remove(&entity) else {
+            continue;
+        };
+        slots.data[slot as usize] = PixelArtInstanceData::default();
+        slots.free.push(slot);
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_remove::<MeshTag>();
+        }
+        dirty = true;
+    }
+
+    for (entity, params) in &changed {
+        let slot = match slots.slots.get(&entity) {
+            Some(&slot) => slot,
+            None => {
+                let (slot, resized) = slots.allocate();
+                grown |= resized;
+                slots.slots.insert(entity, slot);
+                commands.entity(entity).insert(MeshTag(slot));
+                slot
+            }
+        };
+        let data = params.into();
+        if slots.data[slot as usize] != data {
+            slots.data[slot as usize] = data;
+            dirty = true;
+        }
+    }
+
+    if grown {
+        let _ = buffers.insert(&INSTANCE_PARAMS_BUFFER, slots.to_storage_buffer());
+        // Rebind against the larger buffer.
+        materials.touch_where(|ext| ext.instance_params == INSTANCE_PARAMS_BUFFER);
+    } else if dirty {
+        upload.bytes = slots.to_storage_buffer().data.unwrap_or_default();
+        upload.generation += 1;
+    }
+}
+
+/// Writes a new `InstanceParamUpload` into the GPU buffer in place, keeping
+/// the material bind groups that hold it valid.
+pub fn write_instance_params(
+    upload: Res<InstanceParamUpload>,
+    buffers: Res<RenderAssets<GpuShaderStorageBuffer>>,
+    queue: Res<RenderQueue>,
+    mut written: Local<u64>,
+) {
+    if upload.generation == *written {
+        return;
+    }
+    let Some(buffer) = buffers.get(&INSTANCE_PARAMS_BUFFER) else {
+        return;
+    };
+    if buffer.buffer.size() == upload.bytes.len() as u64 {
+        queue.write_buffer(&buffer.buffer, 0, &upload.bytes);
+    }
+    *written = upload.generation;
+}
@@ -0,0 +1,188 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+
+use crate::{DEFAULT_PALETTE_BUFFER, default_pixel_art_palette};
+
+/// Maximum palette indices in a `PixelArtLavaParams` ramp.
+pub const MAX_LAVA_RAMP: usize = 16;
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Material type alias: StandardMaterial + PixelArtLavaExtension.
+pub type PixelArtLavaMaterial = ExtendedMaterial<StandardMaterial, PixelArtLavaExtension>;
+
+/// Animated lava / fire / magic surface drawn purely with palette indices:
+/// layered scrolling noise picks an entry of `params.ramp` (Bayer-dithered
+/// between neighbors), and `cycle_speed` rotates the ramp like classic
+/// palette cycling.
+///
+/// Colors are read from the palette buffer at draw time, so editing the
+/// `SharedPalette` recolors the effect without touching the material. Noise
+/// follows the first UV set (world XZ without UVs). Unlit, with no prepass
+/// or shadows, so `cutoff` holes (flame tongues) stay see-through.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default)]
+pub struct PixelArtLavaExtension {
+    #[uniform(100)]
+    pub params: PixelArtLavaParams,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(101, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+}
+
+impl Default for PixelArtLavaExtension {
+    fn default() -> Self {
+        Self {
+            params: PixelArtLavaParams::default(),
+            palette: DEFAULT_PALETTE_BUFFER,
+        }
+    }
+}
+
+impl PixelArtLavaExtension {
+    /// Slow molten lava: dark brown through red to yellow (default palette).
+    pub fn lava() -> Self {
+        Self::default()
+    }
+
+    /// Rising flames with ragged tongues: red through orange to light yellow
+    /// (default palette).
+    pub fn fire() -> Self {
+        let mut extension = Self::default().with_ramp(&[24, 8, 25, 9, 10, 23]);
+        extension.params.scroll = Vec2::new(0.0, 1.2);
+        extension.params.noise_scale = 6.0;
+        extension.params.cycle_speed = 6.0;
+        extension.params.cutoff = 0.3;
+        extension
+    }
+
+    /// Swirling magic: deep purple through plum and lavender to pink (default
+    /// palette).
+    pub fn magic() -> Self {
+        let mut extension = Self::default().with_ramp(&[32, 18, 2, 29, 13, 14]);
+        extension.params.scroll = Vec2::new(0.3, -0.2);
+        extension.params.cycle_speed = 3.0;
+        extension
+    }
+
+    /// Palette indices from darkest to brightest (at most `MAX_LAVA_RAMP`).
+    pub fn with_ramp(mut self, indices: &[u32]) -> Self {
+        let count = indices.len().min(MAX_LAVA_RAMP);
+        let mut ramp = [0; MAX_LAVA_RAMP];
+        ramp[..count].copy_from_slice(&indices[..count]);
+        self.params.ramp = [
+            UVec4::from_slice(&ramp[0..4]),
+            UVec4::from_slice(&ramp[4..8]),
+            UVec4::from_slice(&ramp[8..12]),
+            UVec4::from_slice(&ramp[12..16]),
+        ];
+        self.params.ramp_count = count as u32;
+        self
+    }
+
+    /// Bind `palette` (see `PixelArtPalette::to_storage_buffer`); ramp
+    /// indices refer to its entries.
+    pub fn with_palette(mut self, palette: Handle<ShaderStorageBuffer>) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Lava material: this extension on a default base (only its alpha mode
+    /// and culling are used).
+    pub fn material(self) -> PixelArtLavaMaterial {
+        ExtendedMaterial {
+            base: StandardMaterial::default(),
+            extension: self,
+        }
+    }
+}
+
+impl MaterialExtension for PixelArtLavaExtension {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/lava.wgsl".into()
+    }
+
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+        Ok(())
+    }
+}
+
+/// GPU-side lava parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType, Reflect)]
+#[reflect(Default)]
+pub struct PixelArtLavaParams {
+    /// Palette indices, darkest to brightest, packed four per vector.
+    pub ramp: [UVec4; 4],
+    /// Used entries of `ramp` (default: 6).
+    pub ramp_count: u32,
+    /// Noise frequency per UV unit (per world unit without UVs, default: 4.0).
+    pub noise_scale: f32,
+    /// Noise scroll in UV units per second (default: (0.05, 0.1)).
+    pub scroll: Vec2,
+    /// Noise layers, each at double the frequency (1..=4, default: 3).
+    pub octaves: u32,
+    /// Contrast of the noise around its midpoint (default: 1.5).
+    pub contrast: f32,
+    /// Ramp entries the colors rotate by per second (0 = no cycling,
+    /// default: 2.0).
+    pub cycle_speed: f32,
+    /// Bayer dither between neighboring ramp entries (0 = hard steps,
+    /// default: 1.0).
+    pub dither_strength: f32,
+    /// Noise level below which pixels are discarded (0 = solid, default: 0.0).
+    pub cutoff: f32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArtLavaParams {
+    fn default() -> Self {
+        let (palette, _) = default_pixel_art_palette();
+        Self {
+            ramp: [
+                UVec4::new(16, 20, 24, 8),
+                UVec4::new(25, 10, 0, 0),
+                UVec4::ZERO,
+                UVec4::ZERO,
+            ],
+            ramp_count: 6,
+            noise_scale: 4.0,
+            scroll: Vec2::new(0.05, 0.1),
+            octaves: 3,
+            contrast: 1.5,
+            cycle_speed: 2.0,
+            dither_strength: 1.0,
+            cutoff: 0.0,
+            palette_colors: palette,
+        }
+    }
+}
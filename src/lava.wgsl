@@ -0,0 +1,136 @@
+//! Pixel art lava / fire / magic: layered scrolling value noise mapped to a
+//! ramp of palette indices, dithered between neighbors and rotated over time
+//! like classic palette cycling. Unlit; no prepass or shadows.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::main_pass_post_lighting_processing,
+    mesh_view_bindings::globals,
+}
+
+struct PixelArtLavaParams {
+    ramp: array<vec4<u32>, 4>,
+    ramp_count: u32,
+    noise_scale: f32,
+    scroll: vec2<f32>,
+    octaves: u32,
+    contrast: f32,
+    cycle_speed: f32,
+    dither_strength: f32,
+    cutoff: f32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> lava: PixelArtLavaParams;
+
+#ifdef PALETTE_STORAGE_BUFFER
+@group(#{MATERIAL_BIND_GROUP}) @binding(101)
+var<storage, read> palette_storage: array<vec4<f32>>;
+#endif
+
+fn palette_len() -> u32 {
+#ifdef PALETTE_STORAGE_BUFFER
+    return arrayLength(&palette_storage);
+#else
+    return 64u;
+#endif
+}
+
+fn palette_color(i: u32) -> vec3<f32> {
+#ifdef PALETTE_STORAGE_BUFFER
+    return palette_storage[i].rgb;
+#else
+    return lava.palette_colors[i].rgb;
+#endif
+}
+
+// Palette color of ramp entry `i`.
+fn ramp_color(i: u32) -> vec3<f32> {
+    let index = lava.ramp[i / 4u][i % 4u];
+    return palette_color(min(index, palette_len() - 1u));
+}
+
+// 4x4 Bayer threshold in [0, 1).
+fn bayer4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) & 3u;
+    let y = u32(pos.y) & 3u;
+    var index = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / 16.0;
+}
+
+fn hash2(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Smooth value noise in [0, 1].
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash2(i);
+    let b = hash2(i + vec2<f32>(1.0, 0.0));
+    let c = hash2(i + vec2<f32>(0.0, 1.0));
+    let d = hash2(i + vec2<f32>(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+// Layered noise; each layer doubles the frequency, scrolls a little faster
+// and is rotated so the layers slide across each other.
+fn layered_noise(p: vec2<f32>, time: f32) -> f32 {
+    let rotation = mat2x2<f32>(0.8, 0.6, -0.6, 0.8);
+    var q = p;
+    var amplitude = 0.5;
+    var total = 0.0;
+    var weight = 0.0;
+    let octaves = clamp(lava.octaves, 1u, 4u);
+    for (var i = 0u; i < octaves; i++) {
+        let speed = 1.0 + 0.5 * f32(i);
+        total += amplitude * value_noise(q - lava.scroll * lava.noise_scale * speed * time);
+        weight += amplitude;
+        q = rotation * q * 2.0 + vec2<f32>(17.0, 31.0);
+        amplitude *= 0.5;
+    }
+    return total / weight;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    let pbr_input = pbr_input_from_standard_material(in, is_front);
+
+#ifdef VERTEX_UVS_A
+    let p = in.uv * lava.noise_scale;
+#else
+    let p = in.world_position.xz * lava.noise_scale;
+#endif
+    let noise = saturate((layered_noise(p, globals.time) - 0.5) * lava.contrast + 0.5);
+    if (noise < lava.cutoff) {
+        discard;
+    }
+
+    // --- Noise → ramp entry, dithered into the next entry ---
+    let count = clamp(lava.ramp_count, 1u, 16u);
+    let level = saturate((noise - lava.cutoff) / max(1.0 - lava.cutoff, 1e-3)) * f32(count - 1u);
+    var entry = u32(floor(level));
+    if (bayer4(floor(in.position.xy)) < fract(level) * lava.dither_strength) {
+        entry += 1u;
+    }
+    entry = min(entry, count - 1u);
+
+    // --- Palette cycling: rotate the ramp over time ---
+    let shift = u32(floor(globals.time * max(lava.cycle_speed, 0.0)));
+    entry = (entry + shift) % count;
+
+    var out: FragmentOutput;
+    out.color = main_pass_post_lighting_processing(pbr_input, vec4<f32>(ramp_color(entry), 1.0));
+    return out;
+}
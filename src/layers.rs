@@ -0,0 +1,94 @@
+use bevy::camera::visibility::{Layer, RenderLayers};
+use bevy::prelude::*;
+
+// ──────────────────────────────────────────────
+//  Layer configuration
+// ──────────────────────────────────────────────
+
+/// Render layers used by the pixel art pipeline. Change this resource to move
+/// the pipeline to different layers; every `PixelLayerOnly` / `FullResOnly`
+/// entity follows automatically.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct PixelArtLayers {
+    /// Layer rendered by the low-res pixel art camera (default: 1).
+    pub pixel: Layer,
+    /// Layer rendered by the full-res window camera (default: 0).
+    pub full_res: Layer,
+    /// Layer used by offscreen `PixelArtThumbnails` rigs (default: 30).
+    pub thumbnail: Layer,
+}
+
+impl Default for PixelArtLayers {
+    fn default() -> Self {
+        Self {
+            pixel: 1,
+            full_res: 0,
+            thumbnail: 30,
+        }
+    }
+}
+
+impl PixelArtLayers {
+    /// `RenderLayers` containing only the pixel art layer.
+    pub fn pixel_layers(&self) -> RenderLayers {
+        RenderLayers::layer(self.pixel)
+    }
+
+    /// `RenderLayers` containing only the full-res layer.
+    pub fn full_res_layers(&self) -> RenderLayers {
+        RenderLayers::layer(self.full_res)
+    }
+
+    /// `RenderLayers` containing only the thumbnail layer.
+    pub fn thumbnail_layers(&self) -> RenderLayers {
+        RenderLayers::layer(self.thumbnail)
+    }
+
+    /// `RenderLayers` containing both layers (e.g. for lights).
+    pub fn both(&self) -> RenderLayers {
+        RenderLayers::from_layers(&[self.full_res, self.pixel])
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Marker components
+// ──────────────────────────────────────────────
+
+/// Keeps the entity's `RenderLayers` on `PixelArtLayers::pixel` only.
+/// Works on meshes and cameras alike.
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct PixelLayerOnly;
+
+/// Keeps the entity's `RenderLayers` on `PixelArtLayers::full_res` only.
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct FullResOnly;
+
+// ──────────────────────────────────────────────
+//  Sync system
+// ──────────────────────────────────────────────
+
+/// Writes `RenderLayers` on newly marked entities, and on every marked entity
+/// when `PixelArtLayers` changes.
+pub fn sync_layer_markers(
+    mut commands: Commands,
+    layers: Res<PixelArtLayers>,
+    pixel_query: Query<(Entity, Ref<PixelLayerOnly>)>,
+    full_res_query: Query<(Entity, Ref<FullResOnly>)>,
+) {
+    let all = layers.is_changed();
+
+    for (entity, marker) in &pixel_query {
+        if all || marker.is_added() {
+            commands.entity(entity).insert(layers.pixel_layers());
+        }
+    }
+
+    for (entity, marker) in &full_res_query {
+        if all || marker.is_added() {
+            commands.entity(entity).insert(layers.full_res_layers());
+        }
+    }
+}
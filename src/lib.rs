@@ -1,12 +1,154 @@
+pub mod aspect;
+pub mod batch;
+pub mod bloom;
+pub mod builder;
+pub mod cinematic;
 pub mod compositor;
+pub mod convert;
+pub mod crt;
+#[cfg(feature = "debug-ui")]
+pub mod debug_ui;
+pub mod decimate;
+pub mod depth_of_field;
+pub mod dissolve;
+pub mod events;
+pub mod exposure;
+pub mod features;
+pub mod flash;
+pub mod glitch;
+pub mod holdout;
+pub mod hologram;
+pub mod instance;
+pub mod lava;
+pub mod layers;
+pub mod lod;
+#[cfg(feature = "2d")]
+pub mod material2d;
+pub mod no_quantize;
+pub mod outline;
+pub mod outline_hull;
+#[cfg(feature = "outline-asset")]
+pub mod outline_settings;
+pub mod palette;
+pub mod palette_editor;
+pub mod palette_swap;
+pub mod pass_order;
+pub mod pixel_camera;
+pub mod pixel_size;
+pub mod post_quantize;
+pub mod preview;
+#[cfg(feature = "recording")]
+pub mod recorder;
+pub mod reveal;
+pub mod screenshot;
+pub mod settings;
+pub mod shake;
+#[cfg(feature = "showcase")]
+pub mod showcase;
+pub mod sky;
+pub mod skybox_quantize;
+pub mod snap;
+pub mod stats;
+pub mod stepped;
+pub mod thumbnail;
+pub mod ui_material;
+pub mod upscale;
+pub mod validation;
+pub mod water;
 
-use bevy::asset::embedded_asset;
-use bevy::pbr::{ExtendedMaterial, MaterialExtension, MaterialPlugin};
+use bevy::app::AnimationSystems;
+use bevy::asset::{embedded_asset, load_internal_asset, uuid_handle};
+use bevy::camera::CameraUpdateSystems;
+use bevy::camera::visibility::VisibilitySystems;
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+    MaterialPlugin,
+};
 use bevy::prelude::*;
-use bevy::render::render_resource::{AsBindGroup, ShaderType};
-use bevy::shader::ShaderRef;
+use bevy::render::extract_resource::ExtractResourcePlugin;
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::render::{Render, RenderApp, RenderSystems};
+use bevy::shader::{ShaderRef, load_shader_library};
+use bevy::ui_render::UiMaterialPlugin;
 
-pub use compositor::{LowResPixelArtCamera, PixelArtCompositor, PixelArtCompositorPlugin};
+pub use aspect::LowResAspect;
+pub use batch::PixelArtMaterials;
+pub use bloom::{PixelArtBloom, PixelArtBloomPlugin};
+pub use builder::PixelArtMaterialExt;
+pub use cinematic::CinematicBars;
+pub use compositor::{
+    CompositorFeather, CompositorFilter, LowResLayer, LowResPixelArtCamera, LowResSubLayer,
+    PixelArtCompositor, PixelArtCompositorPlugin, lowres_target,
+};
+pub use convert::ConvertToPixelArt;
+pub use crt::{PixelArtCrt, PixelArtCrtPlugin};
+#[cfg(feature = "debug-ui")]
+pub use debug_ui::{PixelArtDebugUi, PixelArtDebugUiPlugin};
+pub use decimate::LowResFrameRate;
+pub use depth_of_field::{PixelArtDepthOfField, PixelArtDepthOfFieldPlugin};
+pub use dissolve::Dissolve;
+pub use events::{
+    DissolveFinished, PaletteEdit, PaletteEdited, PaletteTransitionCompleted, TintFlashFinished,
+};
+pub use exposure::PixelArtAutoExposure;
+pub use features::{PixelArtExtensionKey, PixelArtFeatures};
+pub use flash::TintFlash;
+pub use glitch::{PixelArtGlitch, PixelArtGlitchPlugin};
+pub use hologram::{PixelArtHologramExtension, PixelArtHologramMaterial, PixelArtHologramParams};
+pub use instance::{INSTANCE_PARAMS_BUFFER, PixelArtInstanceParams};
+pub use lava::{MAX_LAVA_RAMP, PixelArtLavaExtension, PixelArtLavaMaterial, PixelArtLavaParams};
+pub use layers::{FullResOnly, PixelArtLayers, PixelLayerOnly};
+pub use lod::LowResLod;
+#[cfg(feature = "2d")]
+pub use material2d::{PixelArt2dMaterial, PixelArt2dParams};
+pub use no_quantize::NoQuantize;
+pub use outline::{OutlineColor, PixelArtOutline, PixelArtOutlinePlugin};
+pub use outline_hull::OutlineHullMaterial;
+#[cfg(feature = "outline-asset")]
+pub use outline_settings::{
+    PixelArtOutlineSettings, PixelArtOutlineSettingsHandle, PixelArtOutlineSettingsPlugin,
+};
+pub use palette::{DEFAULT_PALETTE_BUFFER, MAX_PALETTE_COLORS, PixelArtPalette, SharedPalette};
+pub use palette_editor::{PaletteEditError, PaletteEditor};
+pub use palette_swap::{PaletteSwapGroup, SwapPalette};
+pub use pixel_camera::{PixelArtCamera, PixelArtCameraPlugin};
+pub use pixel_size::PhysicalPixelSize;
+pub use post_quantize::{PixelArtPostQuantize, PixelArtPostQuantizePlugin};
+pub use preview::DitherPreview;
+#[cfg(feature = "recording")]
+pub use recorder::{PixelArtRecorder, RecordingFinished, RecordingFormat};
+pub use reveal::PaletteReveal;
+pub use screenshot::{CaptureLowResScreenshot, capture_lowres_screenshot};
+pub use settings::PixelArtSettings;
+pub use shake::PixelShake;
+pub use sky::{PixelArtSkyMaterial, PixelArtSkyParams};
+pub use skybox_quantize::{PixelArtSkyboxQuantize, PixelArtSkyboxQuantizePlugin};
+pub use snap::SnapToPixelGrid;
+pub use stats::{PixelArtStats, PixelArtStatsPlugin};
+pub use stepped::AnimateAtFps;
+pub use thumbnail::{PixelArtThumbnails, ThumbnailShape};
+pub use ui_material::{PixelArtUiMaterial, PixelArtUiParams};
+pub use upscale::{PixelArtUpscale, PixelArtUpscalePlugin};
+pub use validation::ParamIssue;
+pub use water::{PixelArtWaterExtension, PixelArtWaterMaterial, PixelArtWaterParams};
+
+/// Common types for setting up pixel art rendering: `use
+/// bevy_pixel_art_shader::prelude::*;`.
+pub mod prelude {
+    pub use crate::{
+        ConvertToPixelArt, DebugStage, DitherAnchor, FullResOnly, HoldoutExtension,
+        HoldoutMaterial, LowResPixelArtCamera, OutlineColor, PixelArtCompositor,
+        PixelArtCompositorPlugin, PixelArtExtension, PixelArtInstanceParams, PixelArtLayers,
+        PixelArtMaterial, PixelArtMaterialExt, PixelArtMaterials, PixelArtOutline,
+        PixelArtOutlinePlugin, PixelArtPalette, PixelArtSettings, PixelArtShaderParams,
+        PixelArtShaderPlugin, PixelLayerOnly, QuantizationSpace, ShadowEdge, SharedPalette,
+        WindWeight, lowres_target,
+    };
+}
 
 // ============================================================================
 // Public types
@@ -24,12 +166,58 @@ pub type HoldoutMaterial = ExtendedMaterial<StandardMaterial, HoldoutExtension>;
 /// Material extension for pixel art rendering of 3D models.
 /// Integrates with Bevy's full PBR lighting, then post-processes:
 ///   - Toon quantize the PBR lighting result
-///   - CIELAB palette quantization + screen-space Bayer dithering
-/// Prepass writes alpha=1.0 so edge detection outlines are enabled.
-#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+///   - Palette quantization (CIELAB by default) + screen-space Bayer dithering
+///
+/// Prepass writes `prepass_alpha` (default 1.0) so edge detection outlines are enabled.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default)]
+#[bind_group_data(PixelArtExtensionKey)]
 pub struct PixelArtExtension {
     #[uniform(100)]
     pub params: PixelArtShaderParams,
+    /// Palette storage buffer (up to `MAX_PALETTE_COLORS` entries), built with
+    /// `PixelArtPalette::to_storage_buffer()`. Unbound with the `webgl2`
+    /// feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(101, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+    /// Mask selecting a `params.sub_params` set per texel (R/G/B/A → set 0..3)
+    /// when `params.sub_material_source` is `SubMaterialSource::MaskTexture`.
+    /// Sampled with the mesh's first UV set.
+    #[texture(102)]
+    #[sampler(103)]
+    pub sub_material_mask: Option<Handle<Image>>,
+    /// Per-entity overrides written by `PixelArtInstanceParams` (default:
+    /// `INSTANCE_PARAMS_BUFFER`). Unbound with the `webgl2` feature.
+    #[cfg_attr(not(feature = "webgl2"), storage(104, read_only))]
+    pub instance_params: Handle<ShaderStorageBuffer>,
+    /// Keep this material's own values instead of following the
+    /// `PixelArtSettings` resource (default: false).
+    pub ignore_settings: bool,
+    /// Shader features the material's pipelines are compiled with, kept in
+    /// sync with the `PixelArtFeatures` resource.
+    #[reflect(ignore)]
+    pub features: PixelArtFeatures,
+}
+
+impl From<&PixelArtExtension> for PixelArtExtensionKey {
+    fn from(extension: &PixelArtExtension) -> Self {
+        Self {
+            features: extension.features.to_bits(),
+        }
+    }
+}
+
+impl Default for PixelArtExtension {
+    fn default() -> Self {
+        Self {
+            params: PixelArtShaderParams::default(),
+            palette: DEFAULT_PALETTE_BUFFER,
+            sub_material_mask: None,
+            instance_params: INSTANCE_PARAMS_BUFFER,
+            ignore_settings: false,
+            features: PixelArtFeatures::default(),
+        }
+    }
 }
 
 impl MaterialExtension for PixelArtExtension {
@@ -40,13 +228,122 @@ impl MaterialExtension for PixelArtExtension {
     fn prepass_fragment_shader() -> ShaderRef {
         "embedded://bevy_pixel_art_shader/pixel_art_prepass.wgsl".into()
     }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let features = PixelArtFeatures::from_bits(key.bind_group_data.features);
+        if features.wind {
+            // Forward, prepass and shadow pipelines alike; without wind the
+            // material keeps Bevy's mesh vertex shader.
+            descriptor.vertex.shader = PIXEL_ART_VERTEX_SHADER;
+        }
+        if !cfg!(feature = "webgl2") {
+            // Every stage sees the same `PixelArtParams` layout, which only
+            // carries the palette on the uniform fallback.
+            descriptor
+                .vertex
+                .shader_defs
+                .push("PALETTE_STORAGE_BUFFER".into());
+        }
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            if !cfg!(feature = "webgl2") {
+                fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+                fragment
+                    .shader_defs
+                    .push("PIXEL_ART_INSTANCE_PARAMS".into());
+            }
+            fragment.shader_defs.extend(features.shader_defs());
+        }
+        descriptor.vertex.shader_defs.extend(features.shader_defs());
+        if features.dither_anchor || !cfg!(feature = "webgl2") {
+            // Object-anchored dithering needs the mesh transform per fragment,
+            // instance params the mesh tag.
+            descriptor
+                .vertex
+                .shader_defs
+                .push("VERTEX_OUTPUT_INSTANCE_INDEX".into());
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                fragment
+                    .shader_defs
+                    .push("VERTEX_OUTPUT_INSTANCE_INDEX".into());
+            }
+        }
+        Ok(())
+    }
 }
 
+/// Vertex shader with wind sway, swapped in by `PixelArtExtension::specialize`
+/// when the `wind` feature is enabled.
+pub const PIXEL_ART_VERTEX_SHADER: Handle<Shader> =
+    uuid_handle!("3c9d6e81-4f2a-4b7d-a0e5-8b1f6c2d9e47");
+
+/// Normal-prepass alpha that marks holdout pixels in the prepass mask.
+///
+/// The prepass alpha channel (2 bits in the Rgb10a2 normal prepass) doubles as
+/// a mask target. This is the one definition of its encoding; the shaders
+/// writing and reading it refer here:
+///
+/// | Alpha | Written by | Read as (by `PixelArtOutline`) |
+/// |-------|------------|--------------------------------|
+/// | 1 | pixel art (`prepass_alpha`, default) | outlined surface (≥ 0.9) |
+/// | ⅔ | pixel art with `OutlineColor` | surface that draws its own lines (≥ 0.5) |
+/// | ⅓ | `HoldoutExtension::mask()` | holdout, for `occluder_color` (0.15..0.5) |
+/// | 0 | other holdouts, `outline_participation` 0, background | nothing |
+///
+/// Both extensions scale their `prepass_alpha` by `outline_participation`
+/// before it is written.
+///
+/// The mask lives in the low-res camera's prepass and is read by passes on
+/// that camera. `PixelArtCompositor` does not sample it: it composites by
+/// depth alone.
+pub const HOLDOUT_MASK_ALPHA: f32 = 1.0 / 3.0;
+
 /// Material extension for holdout/occluder rendering.
 /// Writes depth to the depth buffer while outputting fully transparent color.
-/// The prepass writes alpha=0.0 so edge detection ignores holdout geometry.
-#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
-pub struct HoldoutExtension {}
+/// The prepass writes `prepass_alpha` (default 0.0) so edge detection ignores
+/// holdout geometry.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default)]
+pub struct HoldoutExtension {
+    /// Alpha written to the normal prepass (default: 0.0, no outlines).
+    #[uniform(100)]
+    pub prepass_alpha: f32,
+    /// Weight of this material in edge detection: scales `prepass_alpha`
+    /// (0.0..1.0, default: 1.0). 0 drops a `mask()` holdout from the mask.
+    #[uniform(100)]
+    pub outline_participation: f32,
+    /// Whether entities with this material cast shadows (default: true).
+    /// Turn off when the holdout duplicates full-res geometry that already
+    /// casts the same shadow onto pixel art.
+    pub cast_shadows: bool,
+    /// Whether entities with this material receive shadows (default: true).
+    pub receive_shadows: bool,
+}
+
+impl Default for HoldoutExtension {
+    fn default() -> Self {
+        Self {
+            prepass_alpha: 0.0,
+            outline_participation: 1.0,
+            cast_shadows: true,
+            receive_shadows: true,
+        }
+    }
+}
+
+impl HoldoutExtension {
+    /// Holdout that writes `HOLDOUT_MASK_ALPHA` to the prepass mask.
+    pub fn mask() -> Self {
+        Self {
+            prepass_alpha: HOLDOUT_MASK_ALPHA,
+            ..default()
+        }
+    }
+}
 
 impl MaterialExtension for HoldoutExtension {
     fn fragment_shader() -> ShaderRef {
@@ -59,9 +356,12 @@ impl MaterialExtension for HoldoutExtension {
 }
 
 /// GPU-side pixel art parameters. Must match the WGSL struct layout exactly.
-#[derive(Clone, Debug, ShaderType)]
+#[derive(Clone, Debug, PartialEq, ShaderType, Reflect)]
+#[reflect(Default)]
 pub struct PixelArtShaderParams {
-    /// Base tint color (linear RGBA). Replaces the model's base_color.
+    /// Base tint color (linear RGBA). Multiplies the StandardMaterial's
+    /// `base_color` and `base_color_texture`, so textured models keep their
+    /// texture detail through the toon and palette steps.
     pub base_tint: Vec4,
     /// Number of toon shading bands (default: 3.0).
     pub toon_bands: f32,
@@ -69,22 +369,231 @@ pub struct PixelArtShaderParams {
     pub toon_softness: f32,
     /// Minimum brightness in shadow areas (default: 0.3).
     pub toon_shadow_floor: f32,
-    /// Screen-space dither pattern scale: 1.0 = 1 Bayer cell per pixel (default).
+    /// Dither pattern scale: 1.0 = 1 Bayer cell per pixel (default). Bayer
+    /// cells per world unit when `dither_density_space` is 1.
     pub dither_density: f32,
-    /// Number of active palette colors (0 = disable quantization, max 64).
+    /// Number of active palette colors (0 = disable quantization, max
+    /// `MAX_PALETTE_COLORS`; 64 with the `webgl2` feature).
     pub palette_count: u32,
     /// Blend strength toward palette colors (0.0..1.0, default: 1.0).
     pub palette_strength: f32,
     /// Bayer dither strength (0 = off, 1.0 = full, default: 0.3).
     pub dither_strength: f32,
-    /// Debug visualization stage (0=full, 1=PBR only, 2=+toon, 3=+palette, 4=+dither).
+    /// Debug visualization stage (`DebugStage as u32`, default: full pipeline).
     pub debug_stage: u32,
-    /// Palette colors in linear RGB (max 64 entries, stored as Vec4 for alignment).
+    /// World-space center of the zone where `reveal_count` does not apply.
+    pub reveal_origin: Vec3,
+    /// Radius of the unrestricted zone around `reveal_origin` (0 = limit applies everywhere).
+    pub reveal_radius: f32,
+    /// Palette entries usable outside the reveal zone (default: u32::MAX = no limit;
+    /// 0 still allows the first entry). Driven by the `PaletteReveal` resource.
+    pub reveal_count: u32,
+    /// Color matrix applied to the toon result before palette matching
+    /// (see `ColorStylize`, default: identity).
+    pub stylize_matrix: Mat3,
+    /// Blend toward `stylize_matrix * color` (0 = off, 1 = full, default: 0.0).
+    pub stylize_strength: f32,
+    /// While stylizing, palette entries whose chromaticity changes by more than
+    /// this under the matrix are skipped, so e.g. grayscale only snaps to grays
+    /// (default: 0.02).
+    pub stylize_palette_tolerance: f32,
+    /// Color space used for nearest-palette matching (`QuantizationSpace as u32`,
+    /// default: CIELAB).
+    pub quantization_space: u32,
+    /// Where sub-material selection comes from (`SubMaterialSource as u32`,
+    /// default: none).
+    pub sub_material_source: u32,
+    /// Per-part overrides (skin/cloth/metal on a single mesh). Set `i` is used
+    /// where channel `i` of the mask texture or vertex color is dominant (> 0.5);
+    /// elsewhere the top-level params apply.
+    pub sub_params: [PixelArtSubParams; MAX_SUB_MATERIALS],
+    /// Weight of the lightness term in CIELAB/OKLab palette distance. Raise it to
+    /// preserve brightness ramps over hue accuracy (default: 1.0).
+    pub lightness_weight: f32,
+    /// Weight of the chroma (a/b) terms in CIELAB/OKLab palette distance (default: 1.0).
+    pub chroma_weight: f32,
+    /// Hue rotation in degrees applied to shaded bands (e.g. +20 toward blue/purple,
+    /// default: 0.0). Applied between toon quantization and palette matching.
+    pub shadow_hue_shift: f32,
+    /// Hue rotation in degrees applied to lit bands (default: 0.0).
+    pub highlight_hue_shift: f32,
+    /// Dither stretch/skew axis: screen-space (x, y) or a world-space direction,
+    /// depending on `dither_direction_space` (default: +X).
+    pub dither_direction: Vec3,
+    /// 0 = `dither_direction` is a screen-space vector, 1 = world-space (projected).
+    pub dither_direction_space: u32,
+    /// Pattern stretch along `dither_direction` (1.0 = isotropic Bayer, default).
+    pub dither_stretch: f32,
+    /// Pattern skew along `dither_direction` per cell across it (0.0 = none, 1.0 = 45° hatching).
+    pub dither_skew: f32,
+    /// Number of explicit toon bands in `toon_thresholds`/`toon_levels`
+    /// (0 = evenly spaced `toon_bands`, default). Set with `set_toon_thresholds`.
+    pub toon_threshold_count: u32,
+    /// Ascending luminance thresholds of the explicit bands, 4 per Vec4.
+    pub toon_thresholds: [Vec4; 2],
+    /// Output level (0..1) of each explicit band, 4 per Vec4.
+    pub toon_levels: [Vec4; 2],
+    /// Rim light intensity (0.0 = off, default).
+    pub rim_strength: f32,
+    /// Rim width as a fraction of the silhouette falloff (0..1, default: 0.2).
+    /// The rim is a single hard band, not a gradient.
+    pub rim_width: f32,
+    /// Rim color in linear RGB. Snapped to the nearest palette entry when a
+    /// palette is active.
+    pub rim_color: Vec4,
+    /// Band count for point light response (attenuation × N·L), applied per
+    /// light before the global toon pass (0.0 = smooth, default).
+    pub point_light_bands: f32,
+    /// Band count for spot light response, including the cone falloff
+    /// (0.0 = smooth, default).
+    pub spot_light_bands: f32,
+    /// How directional light shadow edges are drawn (`ShadowEdge as u32`,
+    /// default: soft).
+    pub shadow_edge: u32,
+    /// Skip palette quantization (and the palette dither) while keeping toon
+    /// lighting and every other stage (0 = quantize, default). Set per entity
+    /// with the `NoQuantize` marker.
+    pub no_quantize: u32,
+    /// Bayer matrix size: 2 (chunky checker), 4 (default) or 8 (smoother gradients).
+    pub bayer_size: u32,
+    /// What the dither pattern is anchored to (`DitherAnchor as u32`,
+    /// default: screen).
+    pub dither_anchor: u32,
+    /// Bayer cells per world unit for the object/world anchors (default: 16.0).
+    pub dither_anchor_scale: f32,
+    /// Multiplier on the lit color before toon/palette quantization
+    /// (default: 1.0). Driven by `PixelArtAutoExposure` when present.
+    pub exposure: f32,
+    /// Animated dither: the Bayer pattern shifts every frame over a cycle of
+    /// this many frames (0 = static, default).
+    pub dither_temporal_cycle: u32,
+    /// Screen-door transparency (0 = off, default). When set, base color alpha
+    /// below 1 drops Bayer cells in proportion instead of blending; the rest
+    /// draws opaque, writing depth and taking part in edge detection.
+    pub dither_alpha: u32,
+    /// Alpha written to the normal prepass (0.0..1.0, default: 1.0). Edge
+    /// detection outlines pixels at 1.0 and skips 0.0; post effects that read
+    /// prepass alpha can use intermediate values as a per-object channel (the
+    /// normal prepass stores alpha in 2 bits: 0, ⅓, ⅔, 1).
+    pub prepass_alpha: f32,
+    /// Weight of this material in edge detection (0.0..1.0, default: 1.0):
+    /// the prepass writes `prepass_alpha * outline_participation`. 0 opts the
+    /// material out; intermediate values give partial outlining, snapped to
+    /// the 2-bit prepass alpha.
+    pub outline_participation: f32,
+    /// Confine dithering to toon band transitions: only pixels whose lit
+    /// luminance lies within this distance of a band boundary are dithered
+    /// (0 = dither the whole surface, default).
+    pub dither_band_window: f32,
+    /// Dithered dissolve (0 = solid, default; 1 = fully gone). Bayer cells
+    /// with a threshold below this are discarded. `Dissolve` overrides it per entity.
+    pub dissolve_amount: f32,
+    /// Threshold range above `dissolve_amount` drawn in the edge color
+    /// (default: 0.1).
+    pub dissolve_edge_width: f32,
+    /// Dissolve edge color (linear RGBA), snapped to the palette
+    /// (default: white).
+    pub dissolve_edge_color: Vec4,
+    /// Where the per-fragment weight toward the secondary palette comes from
+    /// (`PaletteBlendSource as u32`, default: off).
+    pub palette_blend_source: u32,
+    /// First entry of the secondary palette within the palette buffer.
+    pub palette_b_offset: u32,
+    /// Number of secondary palette entries (0 = no blending, default).
+    pub palette_b_count: u32,
+    /// Per-object outline color (linear RGBA; alpha = opacity, 0 = off,
+    /// default). When set, the material draws its own silhouette/crease lines
+    /// from the view's prepass instead of `PixelArtOutline` (set
+    /// `prepass_alpha` to ⅔ so the camera outline skips it). `OutlineColor`
+    /// overrides it per entity through `PixelArtInstanceParams`.
+    pub outline_color: Vec4,
+    /// Relative depth difference for per-object silhouette lines (default: 0.05).
+    pub outline_depth_threshold: f32,
+    /// `1 - dot(n, n_neighbor)` for per-object crease lines (0 = off,
+    /// default: 0.4).
+    pub outline_normal_threshold: f32,
+    /// Write normal-mapped normals to the normal prepass (0 = geometric
+    /// normals, default), so outlines pick up creases painted into the normal
+    /// map. Needs a normal map and mesh tangents.
+    pub outline_normal_map: u32,
+    /// Brightness steps for emissive light, banded separately from the lit
+    /// result (0 = off: emissive goes through the toon bands, default).
+    pub emissive_bands: f32,
+    /// Normal-map influence on lighting (0 = geometric normals, 1 = full
+    /// normal map, default: 1.0). Lower it to calm noisy toon bands at low
+    /// resolution without editing the StandardMaterial.
+    pub normal_map_strength: f32,
+    /// Wind sway amplitude in world units (0 = off, default: 0.0). Needs
+    /// the `wind` feature.
+    pub wind_strength: f32,
+    /// Sways per second (default: 0.5).
+    pub wind_frequency: f32,
+    /// World-space direction the wind pushes vertices (default: +X).
+    pub wind_direction: Vec3,
+    /// Distance in world units over which gusts roll across the scene, so
+    /// neighboring plants sway out of phase (default: 4.0).
+    pub wind_wavelength: f32,
+    /// Sway updates per second; the motion holds between updates so it reads
+    /// as discrete pixel steps (0 = smooth, default: 8.0).
+    pub wind_fps: f32,
+    /// Per-vertex sway weight (`WindWeight as u32`, default: local height).
+    pub wind_weight: u32,
+    /// Local height at which `WindWeight::LocalHeight` reaches full sway
+    /// (default: 1.0).
+    pub wind_height: f32,
+    /// Skip PBR lighting and toon banding: base color (× texture × vertex
+    /// color) plus emissive goes straight to palette and dither (0 = lit,
+    /// 1 = unlit, default: 0). For particles and VFX meshes.
+    pub unlit: u32,
+    /// Sprite-sheet columns of the base color texture (1 = no flipbook,
+    /// default: 1). The first UV set is remapped to the current tile.
+    pub flipbook_tiles_x: u32,
+    /// Sprite-sheet rows (default: 1).
+    pub flipbook_tiles_y: u32,
+    /// Frames in the sheet, row-major from the top left (0 = every tile,
+    /// default: 0).
+    pub flipbook_frame_count: u32,
+    /// Current frame, or the start frame when `flipbook_fps` > 0 (default: 0).
+    pub flipbook_frame: u32,
+    /// Playback rate in frames per second, looping (0 = show
+    /// `flipbook_frame`, default: 0.0).
+    pub flipbook_fps: f32,
+    /// Hit-flash color (linear RGB), snapped to the palette and drawn over
+    /// the quantized result (default: white). Driven per entity by `TintFlash`.
+    pub flash_color: Vec4,
+    /// Hit-flash coverage: fraction of Bayer cells showing `flash_color`
+    /// (0 = off, 1 = solid, default: 0.0).
+    pub flash_amount: f32,
+    /// Steps of the ambient occlusion term when SSAO is enabled on the camera
+    /// (SSAO combined with any occlusion texture; 0 = smooth, default: 0.0).
+    pub ssao_bands: f32,
+    /// Bayer dither at the SSAO band steps (0 = hard steps, 1 = full ordered
+    /// dither, default: 0.5).
+    pub ssao_dither: f32,
+    /// Strength of the banded occlusion (0 = none, default: 1.0).
+    pub ssao_strength: f32,
+    /// Distance rings of point/spot light attenuation: each ring is lit at
+    /// the attenuation of its middle (0 = smooth falloff, default: 0.0).
+    pub light_falloff_bands: f32,
+    /// Bayer-dithered width at the ring edges, as a fraction of a ring
+    /// (0 = hard edges, default: 0.0).
+    pub light_falloff_dither: f32,
+    /// 0 = `dither_density` is Bayer cells per screen pixel, 1 = Bayer cells
+    /// per world unit, projected at the fragment's depth so near and far
+    /// objects show the same apparent dither scale (default: 0).
+    pub dither_density_space: u32,
+    /// Bayer cells per UV unit for the UV anchor (default: 64.0).
+    pub dither_texels_per_uv: f32,
+    /// Uniform palette fallback for WebGL2, in linear RGB (max 64 entries, stored
+    /// as Vec4 for alignment). Only with the `webgl2` feature; otherwise the
+    /// palette is read from the bound storage buffer alone.
+    #[cfg(feature = "webgl2")]
     pub palette_colors: [Vec4; 64],
 }
 
 impl Default for PixelArtShaderParams {
     fn default() -> Self {
+        #[cfg_attr(not(feature = "webgl2"), allow(unused_variables))]
         let (palette, count) = default_pixel_art_palette();
         Self {
             base_tint: Vec4::new(1.0, 1.0, 1.0, 1.0),
@@ -95,27 +604,531 @@ impl Default for PixelArtShaderParams {
             palette_count: count,
             palette_strength: 0.25,
             dither_strength: 0.3,
-            debug_stage: 0,
+            debug_stage: DebugStage::Full as u32,
+            reveal_origin: Vec3::ZERO,
+            reveal_radius: 0.0,
+            reveal_count: u32::MAX,
+            stylize_matrix: Mat3::IDENTITY,
+            stylize_strength: 0.0,
+            stylize_palette_tolerance: 0.02,
+            quantization_space: QuantizationSpace::CieLab as u32,
+            sub_material_source: SubMaterialSource::None as u32,
+            sub_params: [PixelArtSubParams::default(); MAX_SUB_MATERIALS],
+            lightness_weight: 1.0,
+            chroma_weight: 1.0,
+            shadow_hue_shift: 0.0,
+            highlight_hue_shift: 0.0,
+            dither_direction: Vec3::X,
+            dither_direction_space: 0,
+            dither_stretch: 1.0,
+            dither_skew: 0.0,
+            toon_threshold_count: 0,
+            toon_thresholds: [Vec4::ZERO; 2],
+            toon_levels: [Vec4::ZERO; 2],
+            rim_strength: 0.0,
+            rim_width: 0.2,
+            rim_color: Vec4::ONE,
+            point_light_bands: 0.0,
+            spot_light_bands: 0.0,
+            shadow_edge: ShadowEdge::Soft as u32,
+            no_quantize: 0,
+            bayer_size: 4,
+            dither_anchor: DitherAnchor::Screen as u32,
+            dither_anchor_scale: 16.0,
+            exposure: 1.0,
+            dither_temporal_cycle: 0,
+            dither_alpha: 0,
+            prepass_alpha: 1.0,
+            outline_participation: 1.0,
+            dither_band_window: 0.0,
+            dissolve_amount: 0.0,
+            dissolve_edge_width: 0.1,
+            dissolve_edge_color: Vec4::ONE,
+            palette_blend_source: PaletteBlendSource::Off as u32,
+            palette_b_offset: 0,
+            palette_b_count: 0,
+            outline_color: Vec4::ZERO,
+            outline_depth_threshold: 0.05,
+            outline_normal_threshold: 0.4,
+            outline_normal_map: 0,
+            emissive_bands: 0.0,
+            normal_map_strength: 1.0,
+            wind_strength: 0.0,
+            wind_frequency: 0.5,
+            wind_direction: Vec3::X,
+            wind_wavelength: 4.0,
+            wind_fps: 8.0,
+            wind_weight: WindWeight::LocalHeight as u32,
+            wind_height: 1.0,
+            unlit: 0,
+            flipbook_tiles_x: 1,
+            flipbook_tiles_y: 1,
+            flipbook_frame_count: 0,
+            flipbook_frame: 0,
+            flipbook_fps: 0.0,
+            flash_color: Vec4::ONE,
+            flash_amount: 0.0,
+            ssao_bands: 0.0,
+            ssao_dither: 0.5,
+            ssao_strength: 1.0,
+            light_falloff_bands: 0.0,
+            light_falloff_dither: 0.0,
+            dither_density_space: 0,
+            dither_texels_per_uv: 64.0,
+            #[cfg(feature = "webgl2")]
             palette_colors: palette,
         }
     }
 }
 
+/// Built-in pre-quantization color stylizations. Write `matrix()` into
+/// `PixelArtShaderParams::stylize_matrix` and raise `stylize_strength`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorStylize {
+    /// Identity — colors pass through unchanged.
+    #[default]
+    None,
+    /// Rec. 709 luminance desaturation.
+    Grayscale,
+    /// Classic sepia tone.
+    Sepia,
+}
+
+impl ColorStylize {
+    /// Linear RGB color matrix for this mode.
+    pub fn matrix(self) -> Mat3 {
+        match self {
+            ColorStylize::None => Mat3::IDENTITY,
+            ColorStylize::Grayscale => Mat3::from_cols(
+                Vec3::splat(0.2126),
+                Vec3::splat(0.7152),
+                Vec3::splat(0.0722),
+            ),
+            ColorStylize::Sepia => Mat3::from_cols(
+                Vec3::new(0.393, 0.349, 0.272),
+                Vec3::new(0.769, 0.686, 0.534),
+                Vec3::new(0.189, 0.168, 0.131),
+            ),
+        }
+    }
+}
+
+/// Maximum number of explicit toon bands (`PixelArtShaderParams::set_toon_thresholds`).
+pub const MAX_TOON_THRESHOLDS: usize = 8;
+
+impl PixelArtShaderParams {
+    /// Replace evenly spaced `toon_bands` with explicit `(threshold, level)` pairs.
+    /// Luminance at or above `threshold` maps to `level` (0..1, before the shadow
+    /// floor is applied); below the first threshold maps to 0. Pairs are sorted by
+    /// threshold and truncated to `MAX_TOON_THRESHOLDS`; an empty slice restores
+    /// even bands.
+    pub fn set_toon_thresholds(&mut self, bands: &[(f32, f32)]) {
+        let mut bands = bands.to_vec();
+        bands.sort_by(|a, b| a.0.total_cmp(&b.0));
+        bands.truncate(MAX_TOON_THRESHOLDS);
+
+        self.toon_thresholds = [Vec4::ZERO; 2];
+        self.toon_levels = [Vec4::ZERO; 2];
+        for (i, (threshold, level)) in bands.iter().enumerate() {
+            self.toon_thresholds[i / 4][i % 4] = *threshold;
+            self.toon_levels[i / 4][i % 4] = *level;
+        }
+        self.toon_threshold_count = bands.len() as u32;
+    }
+}
+
+/// Number of sub-parameter sets per material (one per RGBA mask channel).
+pub const MAX_SUB_MATERIALS: usize = 4;
+
+/// One sub-material's overrides of the top-level `PixelArtShaderParams`.
+#[derive(Clone, Copy, Debug, PartialEq, ShaderType, Reflect)]
+#[reflect(Default)]
+pub struct PixelArtSubParams {
+    /// Tint used instead of `base_tint`.
+    pub tint: Vec4,
+    /// Toon bands used instead of `toon_bands`.
+    pub toon_bands: f32,
+    /// Shadow floor used instead of `toon_shadow_floor`.
+    pub toon_shadow_floor: f32,
+    /// Palette strength used instead of `palette_strength`.
+    pub palette_strength: f32,
+    /// Dither strength used instead of `dither_strength`.
+    pub dither_strength: f32,
+}
+
+impl Default for PixelArtSubParams {
+    fn default() -> Self {
+        Self {
+            tint: Vec4::ONE,
+            toon_bands: 10.0,
+            toon_shadow_floor: 0.1,
+            palette_strength: 0.25,
+            dither_strength: 0.3,
+        }
+    }
+}
+
+/// Source of the per-fragment sub-material selection.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubMaterialSource {
+    /// Sub-materials disabled; the top-level params apply everywhere.
+    #[default]
+    None = 0,
+    /// `PixelArtExtension::sub_material_mask`, sampled with UV set 0.
+    MaskTexture = 1,
+    /// Mesh vertex colors (`Mesh::ATTRIBUTE_COLOR`).
+    VertexColor = 2,
+}
+
+/// Color space for nearest-palette matching. Some palettes look better matched
+/// in gamma space, others perceptually. Write `as u32` into
+/// `PixelArtShaderParams::quantization_space`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuantizationSpace {
+    /// CIE L*a*b* (D65). Perceptually uniform lightness.
+    #[default]
+    CieLab = 0,
+    /// OKLab. Better hue preservation for saturated palettes, cheaper in WGSL.
+    OkLab = 1,
+    /// Plain linear RGB Euclidean distance.
+    LinearRgb = 2,
+    /// Gamma-encoded sRGB distance — matches how many palettes were authored.
+    Srgb = 3,
+}
+
+/// Space the dither pattern is anchored to. Write `as u32` into
+/// `PixelArtShaderParams::dither_anchor`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherAnchor {
+    /// Screen pixels: perfectly grid-aligned, but swims over moving surfaces.
+    #[default]
+    Screen = 0,
+    /// Object space: the pattern moves and rotates with the mesh, like a
+    /// hand-dithered sprite.
+    Object = 1,
+    /// World space: static surfaces keep their pattern while the camera moves.
+    World = 2,
+    /// Mesh UVs (first set) times `dither_texels_per_uv`: locked to the
+    /// surface like dithering baked into the texture.
+    Uv = 3,
+}
+
+/// Per-fragment weight toward the secondary palette. Write `as u32` into
+/// `PixelArtShaderParams::palette_blend_source`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaletteBlendSource {
+    /// Single palette.
+    #[default]
+    Off = 0,
+    /// Red channel of the mesh vertex colors.
+    VertexColorR = 1,
+    /// Green channel of the mesh vertex colors.
+    VertexColorG = 2,
+    /// Blue channel of the mesh vertex colors.
+    VertexColorB = 3,
+    /// Alpha channel of the mesh vertex colors.
+    VertexColorA = 4,
+    /// U coordinate of the second UV set (`Mesh::ATTRIBUTE_UV_1`).
+    Uv1 = 5,
+}
+
+/// Shadow edge style for directional lights. Write `as u32` into
+/// `PixelArtShaderParams::shadow_edge`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShadowEdge {
+    /// Bevy's filtered (PCF) penumbra.
+    #[default]
+    Soft = 0,
+    /// Penumbra thresholded at 50% into a hard edge.
+    Hard = 1,
+    /// Penumbra converted to an ordered Bayer dither on the pixel grid.
+    Dithered = 2,
+}
+
+/// Per-vertex wind sway weight. Write `as u32` into
+/// `PixelArtShaderParams::wind_weight`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindWeight {
+    /// Local height above the mesh origin over `wind_height`, squared, so
+    /// stems bend and roots stay put.
+    #[default]
+    LocalHeight = 0,
+    /// Vertex color red channel (painted weights); local height on meshes
+    /// without vertex colors.
+    VertexColorRed = 1,
+    /// The whole mesh sways evenly.
+    Uniform = 2,
+}
+
+/// Pipeline stage to visualize. Write `as u32` (or `.into()`) into
+/// `PixelArtShaderParams::debug_stage`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DebugStage {
+    /// Every stage (same output as `Dither`).
+    #[default]
+    Full = 0,
+    /// Bevy's PBR lighting result, no pixel art processing.
+    PbrOnly = 1,
+    /// PBR + toon bands.
+    Toon = 2,
+    /// PBR + toon + palette quantization, without dithering.
+    Palette = 3,
+    /// PBR + toon + palette + dither.
+    Dither = 4,
+    /// Each pixel in a false color identifying the palette entry it
+    /// quantized to (after dithering; black = not quantized). Entries whose
+    /// color never shows up are unused; see `palette_index_color`.
+    PaletteIndex = 5,
+}
+
+impl DebugStage {
+    /// Every stage, in uniform order.
+    pub const ALL: [Self; 6] = [
+        Self::Full,
+        Self::PbrOnly,
+        Self::Toon,
+        Self::Palette,
+        Self::Dither,
+        Self::PaletteIndex,
+    ];
+
+    /// Stage for a `debug_stage` uniform value.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+
+    /// Human-readable name for debug UIs.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Full => "Full Pipeline",
+            Self::PbrOnly => "PBR Only",
+            Self::Toon => "PBR + Toon",
+            Self::Palette => "PBR + Toon + Palette",
+            Self::Dither => "PBR + Toon + Palette + Dither",
+            Self::PaletteIndex => "Palette Index",
+        }
+    }
+}
+
+/// Linear false color that `DebugStage::PaletteIndex` draws for palette
+/// entry `index`, for legends in debug UIs. Matches the shader's
+/// `palette_index_color`.
+pub fn palette_index_color(index: u32) -> Color {
+    let hue = (index as f32 * 0.618034).fract();
+    let k = (Vec3::splat(hue) + Vec3::new(0.0, 2.0, 1.0) / 3.0).fract();
+    let rgb = ((k * 6.0 - 3.0).abs() - 1.0).clamp(Vec3::ZERO, Vec3::ONE);
+    let value = if (index / 8) % 2 == 1 { 0.55 } else { 1.0 };
+    let rgb = rgb * value;
+    Color::linear_rgb(rgb.x, rgb.y, rgb.z)
+}
+
+impl From<DebugStage> for u32 {
+    fn from(stage: DebugStage) -> Self {
+        stage as u32
+    }
+}
+
 // ============================================================================
 // Plugin
 // ============================================================================
 
-pub struct PixelArtShaderPlugin;
+/// Registers the pixel art and holdout materials plus their helper systems.
+///
+/// `PixelArtShaderPlugin::default()` compiles the default features; set
+/// `features` to prune or add some.
+#[derive(Default)]
+pub struct PixelArtShaderPlugin {
+    /// Optional shader features to compile in, inserted as the
+    /// `PixelArtFeatures` resource (default: all but `wind`).
+    pub features: PixelArtFeatures,
+}
 
 impl Plugin for PixelArtShaderPlugin {
     fn build(&self, app: &mut App) {
+        app.insert_resource(self.features);
+
+        load_shader_library!(app, "pixel_art_common.wgsl");
         embedded_asset!(app, "pixel_art.wgsl");
         embedded_asset!(app, "pixel_art_prepass.wgsl");
+        load_internal_asset!(
+            app,
+            PIXEL_ART_VERTEX_SHADER,
+            "pixel_art_vertex.wgsl",
+            Shader::from_wgsl
+        );
         embedded_asset!(app, "holdout.wgsl");
         embedded_asset!(app, "holdout_prepass.wgsl");
+        embedded_asset!(app, "outline_hull.wgsl");
+        embedded_asset!(app, "water.wgsl");
+        embedded_asset!(app, "hologram.wgsl");
+        embedded_asset!(app, "lava.wgsl");
+        embedded_asset!(app, "sky.wgsl");
+        embedded_asset!(app, "pixel_art_ui.wgsl");
+        #[cfg(feature = "2d")]
+        embedded_asset!(app, "pixel_art_2d.wgsl");
+
+        app.init_asset::<PixelArtPalette>();
+        let _ = app
+            .world_mut()
+            .resource_mut::<Assets<ShaderStorageBuffer>>()
+            .insert(&DEFAULT_PALETTE_BUFFER, PixelArtPalette::default().to_storage_buffer());
+        let instance_slots = instance::InstanceParamSlots::default();
+        let _ = app
+            .world_mut()
+            .resource_mut::<Assets<ShaderStorageBuffer>>()
+            .insert(&INSTANCE_PARAMS_BUFFER, instance_slots.to_storage_buffer());
+        app.insert_resource(instance_slots);
+        app.init_resource::<palette::SharedPaletteUpload>();
+        app.init_resource::<instance::InstanceParamUpload>();
+        app.init_resource::<palette_swap::PaletteSwapUpload>();
+        app.add_plugins((
+            ExtractResourcePlugin::<palette::SharedPaletteUpload>::default(),
+            ExtractResourcePlugin::<instance::InstanceParamUpload>::default(),
+            ExtractResourcePlugin::<palette_swap::PaletteSwapUpload>::default(),
+        ));
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                (
+                    palette::write_shared_palette,
+                    instance::write_instance_params,
+                    palette_swap::write_palette_swaps,
+                )
+                    .in_set(RenderSystems::PrepareResources),
+            );
+        }
 
         app.add_plugins(MaterialPlugin::<PixelArtMaterial>::default());
         app.add_plugins(MaterialPlugin::<HoldoutMaterial>::default());
+        app.add_plugins(MaterialPlugin::<OutlineHullMaterial>::default());
+        app.add_plugins(MaterialPlugin::<PixelArtWaterMaterial>::default());
+        app.add_plugins(MaterialPlugin::<PixelArtHologramMaterial>::default());
+        app.add_plugins(MaterialPlugin::<PixelArtLavaMaterial>::default());
+        app.add_plugins(MaterialPlugin::<PixelArtSkyMaterial>::default());
+        app.add_plugins(UiMaterialPlugin::<PixelArtUiMaterial>::default());
+        #[cfg(feature = "2d")]
+        app.add_plugins(bevy::sprite_render::Material2dPlugin::<PixelArt2dMaterial>::default());
+
+        app.register_type::<PixelArtShaderParams>();
+        app.register_type::<PixelArtSubParams>();
+        app.register_type::<PixelArtExtension>();
+        app.register_type::<HoldoutExtension>();
+        app.register_asset_reflect::<PixelArtMaterial>();
+        app.register_asset_reflect::<HoldoutMaterial>();
+        app.register_type::<PixelArtWaterExtension>();
+        app.register_asset_reflect::<PixelArtWaterMaterial>();
+        app.register_type::<PixelArtHologramExtension>();
+        app.register_asset_reflect::<PixelArtHologramMaterial>();
+        app.register_type::<PixelArtLavaExtension>();
+        app.register_asset_reflect::<PixelArtLavaMaterial>();
+
+        app.init_resource::<PixelArtLayers>();
+        app.register_type::<PixelArtLayers>();
+        app.register_type::<PixelLayerOnly>();
+        app.register_type::<FullResOnly>();
+        app.register_type::<PhysicalPixelSize>();
+        app.register_type::<NoQuantize>();
+        app.register_type::<PixelArtAutoExposure>();
+        app.register_type::<CinematicBars>();
+        app.register_type::<Dissolve>();
+        app.register_type::<TintFlash>();
+        app.register_type::<LowResAspect>();
+        app.register_type::<SnapToPixelGrid>();
+        app.register_type::<PixelShake>();
+        app.register_type::<PixelArtInstanceParams>();
+        app.register_type::<PixelArtSettings>();
+        app.register_type::<AnimateAtFps>();
+        app.register_type::<PaletteSwapGroup>();
+        app.init_resource::<no_quantize::NoQuantizeVariants>();
+        app.init_resource::<palette_swap::PaletteSwaps>();
+
+        app.add_message::<PaletteTransitionCompleted>();
+        app.add_message::<TintFlashFinished>();
+        app.add_message::<DissolveFinished>();
+        app.add_message::<PaletteEdited>();
+        app.add_message::<CaptureLowResScreenshot>();
+        app.add_message::<SwapPalette>();
+        #[cfg(feature = "recording")]
+        {
+            app.register_type::<PixelArtRecorder>();
+            app.init_resource::<recorder::PendingRecordings>();
+            app.add_message::<RecordingFinished>();
+            app.add_systems(
+                Update,
+                (
+                    recorder::capture_recording_frames,
+                    recorder::finish_recordings,
+                ),
+            );
+        }
+
+        app.add_systems(
+            PreUpdate,
+            (
+                (shake::restore_pixel_shake, snap::restore_pixel_grid_snap).chain(),
+                stepped::restore_stepped_poses,
+            ),
+        );
+        app.add_systems(
+            Update,
+            (
+                (
+                    palette::sync_shared_palette
+                        .run_if(resource_exists_and_changed::<SharedPalette>),
+                    palette_swap::start_palette_swaps,
+                    palette_swap::advance_palette_swaps,
+                    palette_swap::bind_palette_swaps,
+                )
+                    .chain(),
+                settings::sync_pixel_art_settings.run_if(resource_exists::<PixelArtSettings>),
+                features::sync_pixel_art_features,
+                reveal::update_palette_reveal.run_if(resource_exists::<PaletteReveal>),
+                thumbnail::tick_thumbnail_rigs,
+                no_quantize::apply_no_quantize,
+                (exposure::schedule_exposure_readback, exposure::apply_auto_exposure),
+                (cinematic::spawn_cinematic_bars, cinematic::animate_cinematic_bars).chain(),
+                holdout::sync_holdout_shadows,
+                convert::convert_to_pixel_art,
+                (
+                    (flash::animate_tint_flash, dissolve::animate_dissolve),
+                    instance::sync_instance_params,
+                )
+                    .chain(),
+                screenshot::capture_lowres_screenshots,
+                aspect::track_window_aspect,
+            ),
+        );
+        #[cfg(any(debug_assertions, feature = "validation"))]
+        app.add_systems(PostUpdate, validation::validate_pixel_art_materials);
+
+        app.add_systems(
+            PostUpdate,
+            (
+                layers::sync_layer_markers.before(VisibilitySystems::CheckVisibility),
+                pixel_size::apply_physical_pixel_size.after(CameraUpdateSystems),
+                (
+                    snap::snap_to_pixel_grid,
+                    shake::apply_pixel_shake,
+                    snap::sync_compositor_subpixel_offset,
+                )
+                    .chain()
+                    .after(CameraUpdateSystems)
+                    .before(TransformSystems::Propagate),
+                stepped::step_animated_poses
+                    .after(AnimationSystems)
+                    .before(TransformSystems::Propagate),
+                lod::update_low_res_lod
+                    .after(TransformSystems::Propagate)
+                    .before(VisibilitySystems::CalculateBounds),
+            ),
+        );
     }
 }
 
@@ -0,0 +1,114 @@
+use bevy::camera::primitives::Aabb;
+use bevy::prelude::*;
+
+use crate::LowResPixelArtCamera;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Switches the entity's `Mesh3d` by its projected size in low-res texels.
+///
+/// Screen-size LOD heuristics based on window pixels mis-fire at an
+/// effective resolution of 320×180: a mesh covering 40 texels covers 160
+/// window pixels at 4× upscale. This measures the bounding-sphere diameter on
+/// the `LowResPixelArtCamera`'s own target instead.
+#[derive(Component, Clone, Debug)]
+pub struct LowResLod {
+    /// `(mesh, min_texels)` from most to least detailed. The first level whose
+    /// `min_texels` the projected diameter reaches is used; the last level is
+    /// the fallback.
+    pub levels: Vec<(Handle<Mesh>, f32)>,
+    /// Extra texels required before switching to a more detailed level, so
+    /// meshes near a threshold do not flicker (default: 2.0).
+    pub hysteresis: f32,
+    /// Index of the active level.
+    pub current: usize,
+}
+
+impl LowResLod {
+    /// LOD levels from most to least detailed as `(mesh, min_texels)`.
+    pub fn new(levels: Vec<(Handle<Mesh>, f32)>) -> Self {
+        Self {
+            levels,
+            hysteresis: 2.0,
+            current: 0,
+        }
+    }
+
+    /// Level for a projected diameter of `texels`, given the active level.
+    pub fn level_for(&self, texels: f32) -> usize {
+        let last = self.levels.len().saturating_sub(1);
+        (0..self.levels.len())
+            .find(|&i| {
+                // Moving to a more detailed level needs the extra margin.
+                let margin = if i < self.current {
+                    self.hysteresis
+                } else {
+                    0.0
+                };
+                texels >= self.levels[i].1 + margin
+            })
+            .unwrap_or(last)
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Projected diameter, in target texels, of a sphere of `radius` at `position`.
+fn projected_texels(
+    projection: &Projection,
+    camera_transform: &GlobalTransform,
+    target_height: f32,
+    position: Vec3,
+    radius: f32,
+) -> f32 {
+    let view_height = match projection {
+        Projection::Perspective(p) => {
+            let distance = (position - camera_transform.translation())
+                .length()
+                .max(1e-3);
+            2.0 * distance * (p.fov * 0.5).tan()
+        }
+        Projection::Orthographic(o) => o.area.height(),
+        _ => return f32::INFINITY,
+    };
+    2.0 * radius / view_height.max(1e-6) * target_height
+}
+
+/// Picks each `LowResLod` entity's level from its size on the low-res camera.
+pub fn update_low_res_lod(
+    cameras: Query<(&Camera, &Projection, &GlobalTransform), With<LowResPixelArtCamera>>,
+    mut meshes: Query<(&mut LowResLod, &mut Mesh3d, &GlobalTransform, &Aabb)>,
+) {
+    let Some((camera, projection, camera_transform)) = cameras.iter().next() else {
+        return;
+    };
+    let Some(target) = camera.physical_target_size() else {
+        return;
+    };
+
+    for (mut lod, mut mesh, transform, aabb) in &mut meshes {
+        if lod.levels.is_empty() {
+            continue;
+        }
+        let scale = transform.compute_transform().scale.abs().max_element();
+        let radius = Vec3::from(aabb.half_extents).length() * scale;
+        let center = transform.transform_point(Vec3::from(aabb.center));
+        let texels = projected_texels(
+            projection,
+            camera_transform,
+            target.y as f32,
+            center,
+            radius,
+        );
+
+        let level = lod.level_for(texels);
+        if level != lod.current || mesh.0 != lod.levels[level].0 {
+            lod.current = level;
+            mesh.0 = lod.levels[level].0.clone();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+use bevy::sprite_render::{AlphaMode2d, Material2d, Material2dKey};
+
+use crate::{DEFAULT_PALETTE_BUFFER, PixelArtShaderParams};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// 2D pixel art material for `Mesh2d` quads and sprites: the texture (times
+/// `params.color` and vertex colors) is palette-quantized with the same
+/// nearest-two-colors matching and screen-space Bayer dither as
+/// `PixelArtExtension`, without lighting or toon bands.
+///
+/// `PixelArt2dParams::from(&shader_params)` copies the palette and dither
+/// settings of a 3D material, so a `Camera2d` rendering into the low-res
+/// target matches the 3D layer pixel for pixel.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub struct PixelArt2dMaterial {
+    #[uniform(0)]
+    pub params: PixelArt2dParams,
+    /// Color texture (sampled with the mesh UVs; white when unset).
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Option<Handle<Image>>,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(3, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+    /// Blending of the quantized result (default: `AlphaMode2d::Blend`).
+    pub alpha_mode: AlphaMode2d,
+}
+
+/// GPU-side 2D parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType)]
+pub struct PixelArt2dParams {
+    /// Tint multiplied with the texture (linear RGBA, default: white).
+    pub color: Vec4,
+    /// Color space used for nearest-palette matching (`QuantizationSpace as u32`,
+    /// default: CIELAB).
+    pub quantization_space: u32,
+    /// Weight of the lightness term in CIELAB/OKLab palette distance (default: 1.0).
+    pub lightness_weight: f32,
+    /// Weight of the chroma (a/b) terms in CIELAB/OKLab palette distance (default: 1.0).
+    pub chroma_weight: f32,
+    /// Number of active palette colors (0 = no quantization, default: the
+    /// default palette's count).
+    pub palette_count: u32,
+    /// Blend strength toward palette colors (0.0..1.0, default: 1.0).
+    pub palette_strength: f32,
+    /// Bayer dither strength (0 = off, 1.0 = full, default: 0.3).
+    pub dither_strength: f32,
+    /// Bayer matrix size: 2, 4 (default) or 8.
+    pub bayer_size: u32,
+    /// Screen-space dither pattern scale: 1.0 = 1 Bayer cell per pixel (default).
+    pub dither_density: f32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArt2dParams {
+    fn default() -> Self {
+        Self::from(&PixelArtShaderParams::default())
+    }
+}
+
+impl From<&PixelArtShaderParams> for PixelArt2dParams {
+    /// Palette and dither settings of a 3D material, with a white tint.
+    fn from(params: &PixelArtShaderParams) -> Self {
+        Self {
+            color: Vec4::ONE,
+            quantization_space: params.quantization_space,
+            lightness_weight: params.lightness_weight,
+            chroma_weight: params.chroma_weight,
+            palette_count: params.palette_count,
+            palette_strength: params.palette_strength,
+            dither_strength: params.dither_strength,
+            bayer_size: params.bayer_size,
+            dither_density: params.dither_density,
+            #[cfg(feature = "webgl2")]
+            palette_colors: params.palette_colors,
+            #[cfg(not(feature = "webgl2"))]
+            palette_colors: [Vec4::ZERO; 64],
+        }
+    }
+}
+
+impl Default for PixelArt2dMaterial {
+    fn default() -> Self {
+        Self {
+            params: PixelArt2dParams::default(),
+            texture: None,
+            palette: DEFAULT_PALETTE_BUFFER,
+            alpha_mode: AlphaMode2d::Blend,
+        }
+    }
+}
+
+impl PixelArt2dMaterial {
+    /// Material drawing `texture` with the default palette and dither.
+    pub fn from_texture(texture: Handle<Image>) -> Self {
+        Self {
+            texture: Some(texture),
+            ..default()
+        }
+    }
+
+    /// Bind `palette` (see `PixelArtPalette::to_storage_buffer`) with `count`
+    /// active colors.
+    pub fn with_palette(mut self, palette: Handle<ShaderStorageBuffer>, count: u32) -> Self {
+        self.palette = palette;
+        self.params.palette_count = count;
+        self
+    }
+}
+
+impl Material2d for PixelArt2dMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/pixel_art_2d.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        self.alpha_mode
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::PixelArtMaterial;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Renders the entity's `PixelArtMaterial` without palette quantization.
+///
+/// The entity is switched to a variant of its material with
+/// `no_quantize = 1`, shared by every `NoQuantize` entity using the same
+/// material, and switched back when the marker is removed. Useful for a
+/// full-color photograph prop or a cutscene screen inside a paletted scene.
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct NoQuantize;
+
+/// Material the entity used before `NoQuantize` swapped it.
+#[derive(Component, Clone)]
+pub struct NoQuantizeOriginal(pub Handle<PixelArtMaterial>);
+
+/// Unquantized variant per original material.
+#[derive(Resource, Default)]
+pub struct NoQuantizeVariants(HashMap<AssetId<PixelArtMaterial>, Handle<PixelArtMaterial>>);
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// `NoQuantize` entities that were just marked or got a new material.
+type NewlyMarked = (
+    With<NoQuantize>,
+    Or<(Added<NoQuantize>, Changed<MeshMaterial3d<PixelArtMaterial>>)>,
+);
+
+/// Swaps `NoQuantize` entities to the unquantized variant of their material,
+/// and restores the original when the marker is removed.
+pub fn apply_no_quantize(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<PixelArtMaterial>>,
+    mut variants: ResMut<NoQuantizeVariants>,
+    mut marked: Query<(Entity, &mut MeshMaterial3d<PixelArtMaterial>), NewlyMarked>,
+    mut removed: RemovedComponents<NoQuantize>,
+    mut restore: Query<
+        (&NoQuantizeOriginal, &mut MeshMaterial3d<PixelArtMaterial>),
+        Without<NoQuantize>,
+    >,
+) {
+    for (entity, mut material) in &mut marked {
+        let Some(source) = materials.get(&material.0) else {
+            continue;
+        };
+        if source.extension.params.no_quantize != 0 {
+            // Already a variant (our own swap), or quantization is off anyway.
+            continue;
+        }
+        let mut unquantized = source.clone();
+        unquantized.extension.params.no_quantize = 1;
+
+        let original = material.0.clone();
+        let variant = variants
+            .0
+            .entry(original.id())
+            .or_insert_with(|| materials.add(unquantized))
+            .clone();
+
+        material.0 = variant;
+        commands.entity(entity).insert(NoQuantizeOriginal(original));
+    }
+
+    for entity in removed.read() {
+        if let Ok((original, mut material)) = restore.get_mut(entity) {
+            material.0 = original.0.clone();
+            commands.entity(entity).remove::<NoQuantizeOriginal>();
+        }
+    }
+
+    // Drop variants whose original material is gone.
+    variants.0.retain(|id, _| materials.contains(*id));
+}
@@ -0,0 +1,493 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+        prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{texture_2d, texture_depth_2d, uniform_buffer},
+            *,
+        },
+        renderer::RenderContext,
+        view::ViewTarget,
+    },
+};
+
+use crate::pass_order::order_low_res_passes;
+use crate::{PixelArtInstanceParams, PixelArtPalette, SharedPalette, preview};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Edge-detection outlines for the low-res pixel art camera.
+///
+/// Runs on the camera's own target after tonemapping (and after
+/// `PixelArtPostQuantize`; see `pass_order`): a 1-texel line is
+/// drawn on pixels whose depth or normal differs from a neighbor (2 texels
+/// within `thick_distance`), limited to
+/// surfaces whose prepass alpha (`prepass_alpha`) is 1.0 so holdout geometry
+/// and `OutlineColor` entities (which draw their own lines) are skipped.
+/// Requires the depth and normal prepasses.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+#[require(DepthPrepass, NormalPrepass)]
+pub struct PixelArtOutline {
+    /// Relative depth difference to a farther neighbor that counts as a
+    /// silhouette edge (0 = off, default: 0.05).
+    pub depth_threshold: f32,
+    /// `1 - dot(n, n_neighbor)` above which a crease is drawn (0 = off,
+    /// default: 0.4).
+    pub normal_threshold: f32,
+    /// Outline color; alpha is the opacity (default: near-black).
+    pub color: Color,
+    /// Snap `color` to the `SharedPalette` (or the default palette) so the
+    /// outline stays on-palette (default: true).
+    pub snap_to_palette: bool,
+    /// Line color where pixel art is cut off by a nearer holdout that writes
+    /// the prepass mask (`HoldoutExtension::mask`), tracing characters as
+    /// they pass behind walls. Alpha is the opacity (default: off).
+    pub occluder_color: Color,
+    /// View distance (world units) within which silhouette and crease lines
+    /// are 2 texels thick; farther lines stay 1 texel (0 = always 1 texel,
+    /// default).
+    pub thick_distance: f32,
+    /// View distance (world units) at which both thresholds are doubled;
+    /// they grow linearly with distance, so far geometry sheds its smaller
+    /// edges instead of turning into a mass of lines (0 = constant, default).
+    pub threshold_distance: f32,
+    /// `color` after palette snapping, maintained by `resolve_outline_color`.
+    #[reflect(ignore)]
+    resolved_color: Vec4,
+    /// `occluder_color` after palette snapping.
+    #[reflect(ignore)]
+    resolved_occluder_color: Vec4,
+}
+
+impl Default for PixelArtOutline {
+    fn default() -> Self {
+        Self {
+            depth_threshold: 0.05,
+            normal_threshold: 0.4,
+            color: Color::srgba(0.0, 0.0, 0.0, 0.9),
+            snap_to_palette: true,
+            occluder_color: Color::NONE,
+            thick_distance: 0.0,
+            threshold_distance: 0.0,
+            resolved_color: Vec4::new(0.0, 0.0, 0.0, 0.9),
+            resolved_occluder_color: Vec4::ZERO,
+        }
+    }
+}
+
+/// Per-entity outline color, e.g. red enemies and yellow interactables.
+///
+/// The color, snapped to the active palette, is written to the entity's slot
+/// of the per-instance buffer (`PixelArtInstanceParams::outline_color`,
+/// inserted if missing), so entities keep sharing and batching their
+/// material. The prepass marks their pixels ⅔ in the mask, which
+/// `PixelArtOutline` skips, and the material draws the lines in the instance
+/// color from the view's prepass. The camera needs `DepthPrepass` (and
+/// `NormalPrepass` for creases), which `PixelArtOutline` adds. Not available
+/// with the `webgl2` feature (no instance buffer).
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct OutlineColor(pub Color);
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct OutlineUniform {
+    pub depth_threshold: f32,
+    pub normal_threshold: f32,
+    /// Linear RGB outline color + opacity.
+    pub color: Vec4,
+    /// Linear RGB occlusion-contour color + opacity.
+    pub occluder_color: Vec4,
+    pub thick_distance: f32,
+    pub threshold_distance: f32,
+    /// Terms of the camera's `clip_from_view` that map prepass depth back to
+    /// view distance: (z_axis.z, w_axis.z, z_axis.w, w_axis.w).
+    pub depth_to_view: Vec4,
+}
+
+impl ExtractComponent for OutlineUniform {
+    type QueryData = (&'static PixelArtOutline, &'static Camera);
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        (outline, camera): bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        let clip_from_view = camera.clip_from_view();
+        Some(OutlineUniform {
+            depth_threshold: outline.depth_threshold,
+            normal_threshold: outline.normal_threshold,
+            color: outline.resolved_color,
+            occluder_color: outline.resolved_occluder_color,
+            thick_distance: outline.thick_distance,
+            threshold_distance: outline.threshold_distance,
+            depth_to_view: Vec4::new(
+                clip_from_view.z_axis.z,
+                clip_from_view.w_axis.z,
+                clip_from_view.z_axis.w,
+                clip_from_view.w_axis.w,
+            ),
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtOutlinePlugin;
+
+impl Plugin for PixelArtOutlinePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "outline.wgsl");
+
+        app.register_type::<PixelArtOutline>();
+        app.register_type::<OutlineColor>();
+        app.add_plugins((
+            ExtractComponentPlugin::<OutlineUniform>::default(),
+            UniformComponentPlugin::<OutlineUniform>::default(),
+        ));
+        app.add_systems(PostUpdate, resolve_outline_color);
+        app.add_systems(Update, apply_outline_color);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<OutlinePipeline>>()
+            .add_systems(
+                Render,
+                prepare_outline_pipelines.in_set(RenderSystems::Prepare),
+            )
+            .add_render_graph_node::<OutlineNode>(Core3d, PixelArtOutlineLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (Node3d::Tonemapping, PixelArtOutlineLabel, Node3d::Fxaa),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<OutlinePipeline>();
+        order_low_res_passes(app);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Main-world system
+// ──────────────────────────────────────────────
+
+/// Snaps each outline color to the active palette when it or the palette changes.
+pub fn resolve_outline_color(
+    shared: Option<Res<SharedPalette>>,
+    mut outlines: Query<&mut PixelArtOutline>,
+    mut default_palette: Local<Option<PixelArtPalette>>,
+) {
+    let palette_changed = shared.as_ref().is_some_and(|s| s.is_changed());
+    let palette = match &shared {
+        Some(shared) => &shared.0,
+        None => default_palette.get_or_insert_with(PixelArtPalette::default),
+    };
+
+    for mut outline in &mut outlines {
+        if !outline.is_changed() && !palette_changed {
+            continue;
+        }
+        let resolve = |color: Color| {
+            let linear = color.to_linear();
+            let mut rgb = Vec3::new(linear.red, linear.green, linear.blue);
+            if outline.snap_to_palette {
+                rgb = preview::nearest_palette_color(palette, rgb);
+            }
+            rgb.extend(linear.alpha)
+        };
+        let resolved = resolve(outline.color);
+        let resolved_occluder = resolve(outline.occluder_color);
+        if outline.resolved_color != resolved
+            || outline.resolved_occluder_color != resolved_occluder
+        {
+            outline.resolved_color = resolved;
+            outline.resolved_occluder_color = resolved_occluder;
+        }
+    }
+}
+
+/// Writes each `OutlineColor`, snapped to the active palette, into the
+/// entity's `PixelArtInstanceParams`, and clears it when the component is
+/// removed.
+pub fn apply_outline_color(
+    mut commands: Commands,
+    shared: Option<Res<SharedPalette>>,
+    mut marked: Query<(
+        Entity,
+        Ref<OutlineColor>,
+        Option<Mut<PixelArtInstanceParams>>,
+    )>,
+    mut removed: RemovedComponents<OutlineColor>,
+    mut cleared: Query<&mut PixelArtInstanceParams, Without<OutlineColor>>,
+    mut default_palette: Local<Option<PixelArtPalette>>,
+) {
+    let palette_changed = shared.as_ref().is_some_and(|s| s.is_changed());
+    let palette = match &shared {
+        Some(shared) => &shared.0,
+        None => default_palette.get_or_insert_with(PixelArtPalette::default),
+    };
+
+    for (entity, outline, params) in &mut marked {
+        // Params replaced wholesale lose the color, so re-check them too.
+        let params_changed = params.as_ref().is_some_and(|p| p.is_changed());
+        if !outline.is_changed() && !palette_changed && !params_changed {
+            continue;
+        }
+        let linear = outline.0.to_linear();
+        let rgb = preview::nearest_palette_color(
+            palette,
+            Vec3::new(linear.red, linear.green, linear.blue),
+        );
+        let color = rgb.extend(linear.alpha);
+        match params {
+            Some(mut params) => {
+                if params.outline_color != color {
+                    params.outline_color = color;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(PixelArtInstanceParams {
+                    outline_color: color,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    for entity in removed.read() {
+        if let Ok(mut params) = cleared.get_mut(entity)
+            && params.outline_color != Vec4::ZERO
+        {
+            params.outline_color = Vec4::ZERO;
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct OutlinePipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtOutlineLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct OutlinePipeline {
+    pub shader: Handle<Shader>,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for OutlinePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "outline.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_outline: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: prepass depth
+                    texture_depth_2d(),
+                    // 2: prepass normal (alpha = prepass_alpha)
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 3: outline uniform
+                    uniform_buffer::<OutlineUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            shader,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutlineKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for OutlinePipeline {
+    type Key = OutlineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_outline: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_outline_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OutlinePipeline>>,
+    outline_pipeline: Res<OutlinePipeline>,
+    query: Query<(Entity, &ViewTarget), With<OutlineUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &outline_pipeline,
+            OutlineKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(OutlinePipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct OutlineNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static ViewPrepassTextures,
+        &'static OutlinePipelineId,
+        &'static DynamicUniformIndex<OutlineUniform>,
+    )>,
+}
+
+impl FromWorld for OutlineNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for OutlineNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, prepass, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let outline_pipeline = world.resource::<OutlinePipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let (Some(depth), Some(normal)) = (&prepass.depth, &prepass.normal) else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<OutlineUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_outline_bind_group",
+            &pipeline_cache.get_bind_group_layout(&outline_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &depth.texture.default_view,
+                &normal.texture.default_view,
+                uniform_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_outline_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
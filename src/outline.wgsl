@@ -0,0 +1,116 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+@group(0) @binding(1) var prepass_depth: texture_depth_2d;
+@group(0) @binding(2) var prepass_normal: texture_2d<f32>;
+
+struct OutlineSettings {
+    depth_threshold: f32,
+    normal_threshold: f32,
+    color: vec4<f32>,              // rgb = linear color, a = opacity
+    occluder_color: vec4<f32>,     // occlusion contour, a = opacity (0 = off)
+    thick_distance: f32,           // 2-texel lines within this view distance (0 = off)
+    threshold_distance: f32,       // thresholds double at this view distance (0 = off)
+    depth_to_view: vec4<f32>,      // clip_from_view z/w terms for view distance
+}
+@group(0) @binding(3) var<uniform> settings: OutlineSettings;
+
+// Prepass sample at an integer texel, clamped to the target.
+struct PrepassSample {
+    depth: f32,
+    normal: vec3<f32>,
+    surface: bool,
+    outlined: bool,
+    holdout: bool,
+}
+
+fn load_prepass(texel: vec2<i32>) -> PrepassSample {
+    let size = vec2<i32>(textureDimensions(prepass_depth));
+    let p = clamp(texel, vec2<i32>(0), size - 1);
+    let n = textureLoad(prepass_normal, p, 0);
+    var out: PrepassSample;
+    out.depth = textureLoad(prepass_depth, p, 0);
+    out.normal = n.xyz * 2.0 - 1.0;
+    // Prepass mask; encoding and thresholds documented at
+    // `HOLDOUT_MASK_ALPHA` (lib.rs).
+    out.surface = n.a >= 0.5;
+    out.outlined = n.a >= 0.9;
+    out.holdout = n.a > 0.15 && n.a < 0.5;
+    return out;
+}
+
+// View distance of a prepass depth: inverts ndc = (a·z + b) / (c·z + d).
+// Background (perspective depth 0) maps to a huge distance.
+fn view_distance(depth: f32) -> f32 {
+    let m = settings.depth_to_view;
+    let denom = depth * m.z - m.x;
+    if (abs(denom) < 1e-8) {
+        return 1e8;
+    }
+    return abs((m.y - depth * m.w) / denom);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = vec2<i32>(in.position.xy);
+    let color = textureLoad(source_color, texel, 0);
+
+    let center = load_prepass(texel);
+    if (!center.outlined || (settings.color.a <= 0.0 && settings.occluder_color.a <= 0.0)) {
+        return color;
+    }
+
+    // Distance attenuation: thicker lines up close, stricter thresholds far away.
+    let distance = view_distance(center.depth);
+    let thick = settings.thick_distance > 0.0 && distance < settings.thick_distance;
+    var threshold_scale = 1.0;
+    if (settings.threshold_distance > 0.0) {
+        threshold_scale = 1.0 + distance / settings.threshold_distance;
+    }
+    let depth_threshold = settings.depth_threshold * threshold_scale;
+    let normal_threshold = settings.normal_threshold * threshold_scale;
+
+    var edge = false;
+    var occluded_edge = false;
+    let offsets = array<vec2<i32>, 4>(
+        vec2<i32>(1, 0), vec2<i32>(-1, 0), vec2<i32>(0, 1), vec2<i32>(0, -1),
+    );
+    let taps = select(4u, 8u, thick);
+    for (var t = 0u; t < taps; t++) {
+        // Taps 4..8 reach two texels out, for 2-texel silhouettes.
+        let i = t % 4u;
+        let reach = i32(t / 4u) + 1;
+        let neighbor = load_prepass(texel + offsets[i] * reach);
+
+        // Occlusion contour: a nearer masked holdout cuts this surface off.
+        if (reach == 1 && settings.occluder_color.a > 0.0 && neighbor.holdout && neighbor.depth > center.depth) {
+            occluded_edge = true;
+        }
+
+        // Silhouette: the neighbor is farther away (reversed-Z: smaller depth).
+        // The line lands on the nearer surface, inside its silhouette.
+        if (depth_threshold > 0.0) {
+            let relative = (center.depth - neighbor.depth) / max(center.depth, 1e-6);
+            if (relative > depth_threshold) {
+                edge = true;
+            }
+        }
+
+        // Crease: normal discontinuity. Only the +x / +y neighbors are tested
+        // so a crease is one texel wide instead of two; thick lines test all
+        // four direct neighbors.
+        if (normal_threshold > 0.0 && reach == 1 && (thick || i % 2u == 0u) && neighbor.surface) {
+            if (1.0 - dot(center.normal, neighbor.normal) > normal_threshold) {
+                edge = true;
+            }
+        }
+    }
+
+    if (occluded_edge) {
+        return vec4<f32>(mix(color.rgb, settings.occluder_color.rgb, settings.occluder_color.a), color.a);
+    }
+    if (edge && settings.color.a > 0.0) {
+        return vec4<f32>(mix(color.rgb, settings.color.rgb, settings.color.a), color.a);
+    }
+    return color;
+}
@@ -0,0 +1,94 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, Face, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::shader::ShaderRef;
+
+use crate::{PixelArtPalette, preview};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Inverted-hull outline: the mesh's back faces pushed out along their normals
+/// by a constant number of screen pixels, drawn in a flat color behind the
+/// front faces.
+///
+/// An alternative to `PixelArtOutline` for thin features (railings, antennas,
+/// weapons) that screen-space edges miss at 320×180. Spawn it as a second
+/// entity (e.g. a child) with the same `Mesh3d` on the pixel art layer. It
+/// skips the prepass and shadows, so screen-space outlines and lighting ignore
+/// it. Needs mesh normals; skinned and morphed meshes are not extruded.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub struct OutlineHullMaterial {
+    #[uniform(0)]
+    pub params: OutlineHullParams,
+}
+
+/// GPU-side hull parameters.
+#[derive(Clone, Copy, Debug, PartialEq, ShaderType)]
+pub struct OutlineHullParams {
+    /// Outline color (linear RGB, alpha unused).
+    pub color: Vec4,
+    /// Outline width in target pixels (default: 1.0).
+    pub width: f32,
+}
+
+impl Default for OutlineHullMaterial {
+    fn default() -> Self {
+        Self::new(Color::BLACK, 1.0)
+    }
+}
+
+impl OutlineHullMaterial {
+    /// Hull of `width` target pixels in `color`.
+    pub fn new(color: Color, width: f32) -> Self {
+        Self {
+            params: OutlineHullParams {
+                color: color.to_linear().to_vec4(),
+                width,
+            },
+        }
+    }
+
+    /// Hull in the `palette` entry nearest to `color`.
+    pub fn on_palette(color: Color, width: f32, palette: &PixelArtPalette) -> Self {
+        let linear = color.to_linear();
+        let rgb = preview::nearest_palette_color(
+            palette,
+            Vec3::new(linear.red, linear.green, linear.blue),
+        );
+        Self::new(Color::linear_rgb(rgb.x, rgb.y, rgb.z), width)
+    }
+}
+
+impl Material for OutlineHullMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/outline_hull.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/outline_hull.wgsl".into()
+    }
+
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // Only the back faces of the pushed-out hull show around the mesh.
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Inverted-hull outline: back faces extruded along the normal by a constant
+//! screen-space width, shaded in a flat color.
+
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput},
+    mesh_functions,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_core_pipeline::tonemapping::tone_mapping
+
+struct OutlineHullParams {
+    color: vec4<f32>,
+    width: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0)
+var<uniform> hull: OutlineHullParams;
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    out.world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex.instance_index,
+    );
+
+    // Push the vertex out along the screen-space normal by `width` pixels;
+    // scaling by w keeps the width constant at any distance.
+    var clip = position_world_to_clip(out.world_position.xyz);
+    let clip_normal = (view.clip_from_world * vec4<f32>(out.world_normal, 0.0)).xy;
+    if (length(clip_normal) > 1e-6) {
+        clip = vec4<f32>(
+            clip.xy + normalize(clip_normal) * hull.width * 2.0 / view.viewport.zw * clip.w,
+            clip.zw,
+        );
+    }
+    out.position = clip;
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = vec4<f32>(hull.color.rgb, 1.0);
+#ifdef TONEMAP_IN_SHADER
+    // Same tonemapping as the pixel art materials, so palette colors match.
+    color = tone_mapping(color, view.color_grading);
+#endif
+    return color;
+}
@@ -0,0 +1,198 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    platform::collections::HashSet,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::PixelArtOutline;
+
+// ──────────────────────────────────────────────
+//  Settings asset
+// ──────────────────────────────────────────────
+
+/// `PixelArtOutline` settings as a RON asset (`*.outline.ron`), so look-dev
+/// values live in a file next to the scene instead of in code.
+///
+/// Point a camera's `PixelArtOutlineSettingsHandle` at it; the settings are
+/// written into its `PixelArtOutline` when the asset loads and whenever it is
+/// modified (with Bevy's `file_watcher` feature, on every save). Colors are
+/// sRGBA. Missing fields keep the `PixelArtOutline` defaults:
+///
+/// ```ron
+/// (
+///     depth_threshold: 0.08,
+///     color: (0.1, 0.05, 0.15, 1.0),
+///     thick_distance: 6.0,
+/// )
+/// ```
+#[derive(Asset, TypePath, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PixelArtOutlineSettings {
+    /// See `PixelArtOutline::depth_threshold`.
+    pub depth_threshold: f32,
+    /// See `PixelArtOutline::normal_threshold`.
+    pub normal_threshold: f32,
+    /// See `PixelArtOutline::color` (sRGBA).
+    pub color: [f32; 4],
+    /// See `PixelArtOutline::snap_to_palette`.
+    pub snap_to_palette: bool,
+    /// See `PixelArtOutline::occluder_color` (sRGBA).
+    pub occluder_color: [f32; 4],
+    /// See `PixelArtOutline::thick_distance`.
+    pub thick_distance: f32,
+    /// See `PixelArtOutline::threshold_distance`.
+    pub threshold_distance: f32,
+}
+
+impl Default for PixelArtOutlineSettings {
+    fn default() -> Self {
+        Self::from_outline(&PixelArtOutline::default())
+    }
+}
+
+impl PixelArtOutlineSettings {
+    /// Settings matching `outline` (e.g. to save tuned values to a file).
+    pub fn from_outline(outline: &PixelArtOutline) -> Self {
+        Self {
+            depth_threshold: outline.depth_threshold,
+            normal_threshold: outline.normal_threshold,
+            color: outline.color.to_srgba().to_f32_array(),
+            snap_to_palette: outline.snap_to_palette,
+            occluder_color: outline.occluder_color.to_srgba().to_f32_array(),
+            thick_distance: outline.thick_distance,
+            threshold_distance: outline.threshold_distance,
+        }
+    }
+
+    /// Write the settings into `outline`.
+    pub fn apply(&self, outline: &mut PixelArtOutline) {
+        outline.depth_threshold = self.depth_threshold;
+        outline.normal_threshold = self.normal_threshold;
+        outline.color = Color::Srgba(Srgba::from_f32_array(self.color));
+        outline.snap_to_palette = self.snap_to_palette;
+        outline.occluder_color = Color::Srgba(Srgba::from_f32_array(self.occluder_color));
+        outline.thick_distance = self.thick_distance;
+        outline.threshold_distance = self.threshold_distance;
+    }
+
+    /// The settings as RON text, loadable as an `*.outline.ron` asset.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+/// Camera component selecting the `PixelArtOutlineSettings` asset that drives
+/// its `PixelArtOutline`.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+#[require(PixelArtOutline)]
+pub struct PixelArtOutlineSettingsHandle(pub Handle<PixelArtOutlineSettings>);
+
+// ──────────────────────────────────────────────
+//  Loader
+// ──────────────────────────────────────────────
+
+/// Errors from loading an `*.outline.ron` file.
+#[derive(Debug)]
+pub enum OutlineSettingsLoadError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not valid settings RON.
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for OutlineSettingsLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read outline settings: {error}"),
+            Self::Ron(error) => write!(f, "invalid outline settings: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for OutlineSettingsLoadError {}
+
+impl From<std::io::Error> for OutlineSettingsLoadError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for OutlineSettingsLoadError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+/// Loads `PixelArtOutlineSettings` from `*.outline.ron` files.
+#[derive(Default, TypePath)]
+pub struct PixelArtOutlineSettingsLoader;
+
+impl AssetLoader for PixelArtOutlineSettingsLoader {
+    type Asset = PixelArtOutlineSettings;
+    type Settings = ();
+    type Error = OutlineSettingsLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["outline.ron"]
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+/// Registers the `PixelArtOutlineSettings` asset and its loader, and keeps
+/// `PixelArtOutline`s in sync with their settings files. Add it next to
+/// `PixelArtOutlinePlugin`.
+pub struct PixelArtOutlineSettingsPlugin;
+
+impl Plugin for PixelArtOutlineSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PixelArtOutlineSettings>();
+        app.init_asset_loader::<PixelArtOutlineSettingsLoader>();
+        app.register_type::<PixelArtOutlineSettingsHandle>();
+        app.add_systems(Update, apply_outline_settings);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  System
+// ──────────────────────────────────────────────
+
+/// Writes each camera's `PixelArtOutlineSettings` into its `PixelArtOutline`
+/// when the handle changes or the asset (re)loads.
+pub fn apply_outline_settings(
+    settings: Res<Assets<PixelArtOutlineSettings>>,
+    mut events: MessageReader<AssetEvent<PixelArtOutlineSettings>>,
+    mut outlines: Query<(Ref<PixelArtOutlineSettingsHandle>, &mut PixelArtOutline)>,
+) {
+    let reloaded: HashSet<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (handle, mut outline) in &mut outlines {
+        if !handle.is_changed() && !reloaded.contains(&handle.0.id()) {
+            continue;
+        }
+        if let Some(settings) = settings.get(&handle.0) {
+            settings.apply(&mut outline);
+        }
+    }
+}
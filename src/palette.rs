@@ -0,0 +1,273 @@
+use bevy::asset::uuid_handle;
+use bevy::prelude::*;
+use bevy::render::extract_resource::ExtractResource;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::BufferUsages;
+use bevy::render::renderer::RenderQueue;
+use bevy::render::storage::{GpuShaderStorageBuffer, ShaderStorageBuffer};
+
+use crate::{PixelArtExtension, PixelArtMaterials, default_pixel_art_palette};
+
+/// Maximum palette size supported by the storage-buffer palette binding.
+pub const MAX_PALETTE_COLORS: usize = 256;
+
+/// Maximum palette size of the uniform fallback used on WebGL2.
+pub const MAX_UNIFORM_PALETTE_COLORS: usize = 64;
+
+/// Storage buffer holding `default_pixel_art_palette()`. Inserted by
+/// `PixelArtShaderPlugin` and used by `PixelArtExtension::default()`.
+pub const DEFAULT_PALETTE_BUFFER: Handle<ShaderStorageBuffer> =
+    uuid_handle!("5f0b9a3e-2d61-4c7a-9b0e-3f4c8d2a71e6");
+
+// ──────────────────────────────────────────────
+//  Palette asset
+// ──────────────────────────────────────────────
+
+/// CPU-side palette: colors in linear RGB (alpha unused), up to
+/// `MAX_PALETTE_COLORS` entries.
+///
+/// Upload it with `to_storage_buffer()` and point `PixelArtExtension::palette`
+/// at the resulting handle; with the `webgl2` feature (no storage buffers) the
+/// first 64 entries from `uniform_fallback()` are read from
+/// `PixelArtShaderParams::palette_colors` instead.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct PixelArtPalette {
+    pub colors: Vec<Vec4>,
+}
+
+impl Default for PixelArtPalette {
+    fn default() -> Self {
+        let (colors, count) = default_pixel_art_palette();
+        Self {
+            colors: colors[..count as usize].to_vec(),
+        }
+    }
+}
+
+impl PixelArtPalette {
+    /// Palette from linear RGB colors. Entries past `MAX_PALETTE_COLORS` are dropped.
+    pub fn new(mut colors: Vec<Vec4>) -> Self {
+        colors.truncate(MAX_PALETTE_COLORS);
+        Self { colors }
+    }
+
+    /// Palette from sRGB u8 triplets (as found in .hex/.gpl palette files).
+    pub fn from_srgb_u8(colors: &[[u8; 3]]) -> Self {
+        Self::new(
+            colors
+                .iter()
+                .map(|&[r, g, b]| {
+                    let c = Color::srgb_u8(r, g, b).to_linear();
+                    Vec4::new(c.red, c.green, c.blue, 1.0)
+                })
+                .collect(),
+        )
+    }
+
+    /// Colors as sRGB u8 triplets, the inverse of `from_srgb_u8`.
+    pub fn to_srgb_u8(&self) -> Vec<[u8; 3]> {
+        self.colors
+            .iter()
+            .map(|c| {
+                let srgba = Color::linear_rgb(c.x, c.y, c.z).to_srgba().to_u8_array();
+                [srgba[0], srgba[1], srgba[2]]
+            })
+            .collect()
+    }
+
+    /// GIMP palette (.gpl) text, readable by Aseprite, GIMP and Krita.
+    pub fn to_gpl(&self, name: &str) -> String {
+        let mut out = format!("GIMP Palette\nName: {name}\nColumns: 8\n#\n");
+        for (i, [r, g, b]) in self.to_srgb_u8().into_iter().enumerate() {
+            out.push_str(&format!("{r:3} {g:3} {b:3}\tIndex {i}\n"));
+        }
+        out
+    }
+
+    /// Lospec .hex text: one lowercase `rrggbb` per line.
+    pub fn to_hex(&self) -> String {
+        self.to_srgb_u8()
+            .into_iter()
+            .map(|[r, g, b]| format!("{r:02x}{g:02x}{b:02x}\n"))
+            .collect()
+    }
+
+    /// Write the palette as a .gpl file, named after the file stem, so
+    /// generated palettes (k-means extraction, blends) round-trip into 2D tools.
+    pub fn export_gpl(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy())
+            .unwrap_or_default();
+        std::fs::write(path, self.to_gpl(&name))
+    }
+
+    /// Write the palette as a .hex file (see `to_hex`).
+    pub fn export_hex(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_hex())
+    }
+
+    /// Number of colors, suitable for `PixelArtShaderParams::palette_count`.
+    pub fn count(&self) -> u32 {
+        self.colors.len() as u32
+    }
+
+    /// GPU storage buffer for the `PixelArtExtension::palette` binding.
+    /// Writable, so same-sized palettes can replace it in place.
+    pub fn to_storage_buffer(&self) -> ShaderStorageBuffer {
+        let mut colors = self.colors.clone();
+        if colors.is_empty() {
+            // Zero-sized storage bindings are invalid; count stays 0.
+            colors.push(Vec4::ZERO);
+        }
+        let mut buffer = ShaderStorageBuffer::from(colors);
+        buffer.buffer_description.usage |= BufferUsages::COPY_DST;
+        buffer
+    }
+
+    /// Fixed-size uniform array + count for `PixelArtShaderParams::palette_colors`.
+    /// Truncated to `MAX_UNIFORM_PALETTE_COLORS` entries.
+    pub fn uniform_fallback(&self) -> ([Vec4; MAX_UNIFORM_PALETTE_COLORS], u32) {
+        let mut out = [Vec4::ZERO; MAX_UNIFORM_PALETTE_COLORS];
+        let count = self.colors.len().min(MAX_UNIFORM_PALETTE_COLORS);
+        out[..count].copy_from_slice(&self.colors[..count]);
+        (out, count as u32)
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Shared scene palette
+// ──────────────────────────────────────────────
+
+/// Optional scene-wide palette.
+///
+/// While present, its colors are written into `DEFAULT_PALETTE_BUFFER`, the
+/// buffer every `PixelArtExtension::default()` material binds. All of those
+/// materials therefore share one GPU buffer, and replacing this resource with
+/// a palette of the same size re-palettes the whole scene with a single buffer
+/// write, without touching any material. A different number of colors
+/// re-creates the buffer and updates `palette_count` of the materials bound
+/// to it. Materials bound to their own palette buffer are left alone.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SharedPalette(pub PixelArtPalette);
+
+/// Encoded `SharedPalette` colors to write into the existing
+/// `DEFAULT_PALETTE_BUFFER`, extracted to the render world when they change.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct SharedPaletteUpload {
+    bytes: Vec<u8>,
+    generation: u64,
+}
+
+/// Uploads `SharedPalette` into `DEFAULT_PALETTE_BUFFER`: in place while the
+/// color count is unchanged, otherwise by re-creating the buffer and
+/// refreshing the palette count of every material bound to it.
+pub fn sync_shared_palette(
+    shared: Res<SharedPalette>,
+    mut upload: ResMut<SharedPaletteUpload>,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    mut materials: PixelArtMaterials,
+    mut uploaded_len: Local<Option<usize>>,
+) {
+    let buffer = shared.0.to_storage_buffer();
+    let bytes = buffer.data.clone().unwrap_or_default();
+    let current_len = *uploaded_len.get_or_insert_with(|| {
+        buffers
+            .get(&DEFAULT_PALETTE_BUFFER)
+            .and_then(|current| current.data.as_ref())
+            .map_or(0, Vec::len)
+    });
+    let resized = current_len != bytes.len();
+    *uploaded_len = Some(bytes.len());
+
+    if resized {
+        let _ = buffers.insert(&DEFAULT_PALETTE_BUFFER, buffer);
+    } else {
+        upload.bytes = bytes;
+        upload.generation += 1;
+    }
+
+    let count = shared.0.count();
+    let shared_buffer = |ext: &PixelArtExtension| ext.palette == DEFAULT_PALETTE_BUFFER;
+    #[cfg(feature = "webgl2")]
+    {
+        // The uniform fallback is a per-material copy.
+        let (fallback, _) = shared.0.uniform_fallback();
+        materials.update_where(shared_buffer, |params| {
+            params.palette_count = count;
+            params.palette_colors = fallback;
+        });
+    }
+    #[cfg(not(feature = "webgl2"))]
+    if resized {
+        materials.update_where(shared_buffer, |params| params.palette_count = count);
+        // Rebind against the re-created buffer.
+        materials.touch_where(shared_buffer);
+    }
+}
+
+/// Writes a new `SharedPaletteUpload` into `DEFAULT_PALETTE_BUFFER` in place,
+/// keeping the material bind groups that hold it valid.
+pub fn write_shared_palette(
+    upload: Res<SharedPaletteUpload>,
+    buffers: Res<RenderAssets<GpuShaderStorageBuffer>>,
+    queue: Res<RenderQueue>,
+    mut written: Local<u64>,
+) {
+    if upload.generation == *written {
+        return;
+    }
+    let Some(buffer) = buffers.get(&DEFAULT_PALETTE_BUFFER) else {
+        return;
+    };
+    if buffer.buffer.size() == upload.bytes.len() as u64 {
+        queue.write_buffer(&buffer.buffer, 0, &upload.bytes);
+    }
+    *written = upload.generation;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> PixelArtPalette {
+        PixelArtPalette::from_srgb_u8(&[[255, 0, 77], [0, 0, 0], [29, 43, 83]])
+    }
+
+    #[test]
+    fn srgb_u8_round_trips() {
+        let colors: Vec<[u8; 3]> = (0..=255u8).map(|v| [v, 255 - v, v / 2]).collect();
+        assert_eq!(PixelArtPalette::from_srgb_u8(&colors).to_srgb_u8(), colors);
+    }
+
+    #[test]
+    fn gpl_export() {
+        assert_eq!(
+            sample().to_gpl("test"),
+            "GIMP Palette\nName: test\nColumns: 8\n#\n\
+             255   0  77\tIndex 0\n  0   0   0\tIndex 1\n 29  43  83\tIndex 2\n"
+        );
+    }
+
+    #[test]
+    fn hex_export() {
+        assert_eq!(sample().to_hex(), "ff004d\n000000\n1d2b53\n");
+    }
+
+    #[test]
+    fn empty_palette_exports() {
+        let empty = PixelArtPalette::new(Vec::new());
+        assert_eq!(
+            empty.to_gpl("empty"),
+            "GIMP Palette\nName: empty\nColumns: 8\n#\n"
+        );
+        assert_eq!(empty.to_hex(), "");
+    }
+
+    #[test]
+    fn new_truncates_to_capacity() {
+        let palette = PixelArtPalette::new(vec![Vec4::ONE; MAX_PALETTE_COLORS + 8]);
+        assert_eq!(palette.count() as usize, MAX_PALETTE_COLORS);
+    }
+}
@@ -0,0 +1,356 @@
+use std::fmt;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::{
+    DEFAULT_PALETTE_BUFFER, MAX_PALETTE_COLORS, PaletteEdit, PaletteEdited, PaletteReveal,
+    PixelArtMaterials, PixelArtPalette, PixelArtShaderParams, SharedPalette,
+};
+
+// ──────────────────────────────────────────────
+//  Errors
+// ──────────────────────────────────────────────
+
+/// Why a `PaletteEditor` operation was rejected. Rejected edits change nothing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteEditError {
+    /// No `SharedPalette` resource to edit.
+    NoSharedPalette,
+    /// Index past the end of the palette.
+    OutOfRange { index: usize, len: usize },
+    /// The palette already holds `MAX_PALETTE_COLORS` entries.
+    Full,
+    /// The edit would leave the palette without colors.
+    Empty,
+}
+
+impl fmt::Display for PaletteEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSharedPalette => write!(f, "no SharedPalette resource to edit"),
+            Self::OutOfRange { index, len } => {
+                write!(f, "palette index {index} out of range (len {len})")
+            }
+            Self::Full => write!(f, "palette is full ({MAX_PALETTE_COLORS} colors)"),
+            Self::Empty => write!(f, "palette must keep at least one color"),
+        }
+    }
+}
+
+impl std::error::Error for PaletteEditError {}
+
+// ──────────────────────────────────────────────
+//  Editor
+// ──────────────────────────────────────────────
+
+/// Runtime editing of the `SharedPalette`, for level editors and modding tools.
+///
+/// Every successful edit mutates the resource once — which rewrites
+/// `DEFAULT_PALETTE_BUFFER`, refreshes `palette_count` of every material
+/// bound to it when the color count changed, and re-resolves the palette
+/// copies of outlines, post passes and glitches — and writes a
+/// `PaletteEdited` message. Params holding palette indices (`reveal_count`,
+/// `palette_b_offset`, `palette_b_count` and a `PaletteReveal`) are shifted so
+/// they keep pointing at the same colors, then clamped to the new size.
+/// Invalid edits, including ones that would leave no color, return an error
+/// and leave the palette untouched.
+#[derive(SystemParam)]
+pub struct PaletteEditor<'w> {
+    palette: Option<ResMut<'w, SharedPalette>>,
+    reveal: Option<ResMut<'w, PaletteReveal>>,
+    materials: PixelArtMaterials<'w>,
+    edited: MessageWriter<'w, PaletteEdited>,
+}
+
+impl PaletteEditor<'_> {
+    /// Current shared palette, if any.
+    pub fn palette(&self) -> Option<&PixelArtPalette> {
+        self.palette.as_ref().map(|p| &p.0)
+    }
+
+    /// Insert `color` (linear RGB) at `index`; `index == len` appends.
+    pub fn insert(&mut self, index: usize, color: Vec4) -> Result<(), PaletteEditError> {
+        self.edit(PaletteEdit::Inserted { index }, |colors| {
+            if index > colors.len() {
+                return Err(PaletteEditError::OutOfRange {
+                    index,
+                    len: colors.len(),
+                });
+            }
+            if colors.len() >= MAX_PALETTE_COLORS {
+                return Err(PaletteEditError::Full);
+            }
+            colors.insert(index, color);
+            Ok(())
+        })
+    }
+
+    /// Append `color` (linear RGB).
+    pub fn push(&mut self, color: Vec4) -> Result<(), PaletteEditError> {
+        let len = self.palette().map_or(0, |p| p.colors.len());
+        self.insert(len, color)
+    }
+
+    /// Remove the color at `index`. The last color cannot be removed.
+    pub fn remove(&mut self, index: usize) -> Result<(), PaletteEditError> {
+        self.edit(PaletteEdit::Removed { index }, |colors| {
+            check_index(colors, index)?;
+            if colors.len() == 1 {
+                return Err(PaletteEditError::Empty);
+            }
+            colors.remove(index);
+            Ok(())
+        })
+    }
+
+    /// Move the color at `from` to `to`, shifting the entries in between.
+    pub fn reorder(&mut self, from: usize, to: usize) -> Result<(), PaletteEditError> {
+        self.edit(PaletteEdit::Moved { from, to }, |colors| {
+            check_index(colors, from)?;
+            check_index(colors, to)?;
+            let color = colors.remove(from);
+            colors.insert(to, color);
+            Ok(())
+        })
+    }
+
+    /// Replace the color at `index` with `color` (linear RGB).
+    pub fn replace(&mut self, index: usize, color: Vec4) -> Result<(), PaletteEditError> {
+        self.edit(PaletteEdit::Replaced { index }, |colors| {
+            check_index(colors, index)?;
+            colors[index] = color;
+            Ok(())
+        })
+    }
+
+    /// Replace the whole palette (with at least one color).
+    pub fn set(&mut self, palette: PixelArtPalette) -> Result<(), PaletteEditError> {
+        self.edit(PaletteEdit::Reset, |colors| {
+            if palette.colors.is_empty() {
+                return Err(PaletteEditError::Empty);
+            }
+            *colors = PixelArtPalette::new(palette.colors).colors;
+            Ok(())
+        })
+    }
+
+    /// Validates on a copy, then writes the result back (one change tick).
+    fn edit(
+        &mut self,
+        edit: PaletteEdit,
+        f: impl FnOnce(&mut Vec<Vec4>) -> Result<(), PaletteEditError>,
+    ) -> Result<(), PaletteEditError> {
+        let Some(shared) = self.palette.as_mut() else {
+            return Err(PaletteEditError::NoSharedPalette);
+        };
+        let mut colors = shared.0.colors.clone();
+        f(&mut colors)?;
+        shared.0.colors = colors;
+        let count = shared.0.count();
+
+        self.materials.update_where(
+            |ext| ext.palette == DEFAULT_PALETTE_BUFFER,
+            |params| remap_index_params(edit, count, params),
+        );
+        if let Some(reveal) = self.reveal.as_mut() {
+            let (revealed, target) = (reveal.count, reveal.target);
+            let remapped = (
+                remap_prefix(edit, count, revealed.max(0.0) as u32) as f32,
+                remap_prefix(edit, count, target.max(0.0) as u32) as f32,
+            );
+            if remapped != (revealed.floor(), target.floor()) {
+                reveal.count = remapped.0 + revealed.fract();
+                reveal.target = remapped.1 + target.fract();
+            }
+        }
+
+        self.edited.write(PaletteEdited { edit, count });
+        Ok(())
+    }
+}
+
+/// Single insertions/removals making up `edit`, as (index, +1 / -1).
+fn index_shifts(edit: PaletteEdit) -> Vec<(u32, i32)> {
+    match edit {
+        PaletteEdit::Inserted { index } => vec![(index as u32, 1)],
+        PaletteEdit::Removed { index } => vec![(index as u32, -1)],
+        PaletteEdit::Moved { from, to } => vec![(from as u32, -1), (to as u32, 1)],
+        PaletteEdit::Replaced { .. } | PaletteEdit::Reset => Vec::new(),
+    }
+}
+
+/// A count of leading palette entries (`reveal_count`) after `edit`, covering
+/// the same colors, clamped to `len`. `u32::MAX` (everything) is kept.
+fn remap_prefix(edit: PaletteEdit, len: u32, count: u32) -> u32 {
+    if count == u32::MAX {
+        return count;
+    }
+    let mut count = count;
+    for (index, delta) in index_shifts(edit) {
+        if index < count {
+            count = count.saturating_add_signed(delta);
+        }
+    }
+    count.min(len)
+}
+
+/// Keeps palette-index params on the same colors across `edit`, then clamps
+/// them to the `len` entries left.
+fn remap_index_params(edit: PaletteEdit, len: u32, params: &mut PixelArtShaderParams) {
+    params.reveal_count = remap_prefix(edit, len, params.reveal_count);
+
+    let (mut offset, mut count) = (params.palette_b_offset, params.palette_b_count);
+    for (index, delta) in index_shifts(edit) {
+        // Insertions at the range start push the whole range up.
+        if index < offset || (delta > 0 && index == offset) {
+            offset = offset.saturating_add_signed(delta);
+        } else if index < offset + count {
+            count = count.saturating_add_signed(delta);
+        }
+    }
+    params.palette_b_offset = offset.min(len.saturating_sub(1));
+    params.palette_b_count = count.min(len.saturating_sub(params.palette_b_offset));
+}
+
+fn check_index(colors: &[Vec4], index: usize) -> Result<(), PaletteEditError> {
+    if index < colors.len() {
+        Ok(())
+    } else {
+        Err(PaletteEditError::OutOfRange {
+            index,
+            len: colors.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::message::Messages;
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+    use crate::PixelArtMaterial;
+
+    fn world_with_palette(len: usize) -> World {
+        let mut world = World::new();
+        world.init_resource::<Assets<PixelArtMaterial>>();
+        world.init_resource::<Messages<PaletteEdited>>();
+        let colors = (0..len).map(|i| Vec4::splat(i as f32)).collect();
+        world.insert_resource(SharedPalette(PixelArtPalette::new(colors)));
+        world
+    }
+
+    fn edit<R>(world: &mut World, f: impl FnOnce(&mut PaletteEditor) -> R) -> R {
+        let mut state = SystemState::<PaletteEditor<'static>>::new(world);
+        let result = f(&mut state.get_mut(world));
+        state.apply(world);
+        result
+    }
+
+    fn colors(world: &World) -> Vec<Vec4> {
+        world.resource::<SharedPalette>().0.colors.clone()
+    }
+
+    #[test]
+    fn no_shared_palette() {
+        let mut world = world_with_palette(1);
+        world.remove_resource::<SharedPalette>();
+        assert_eq!(
+            edit(&mut world, |editor| editor.push(Vec4::ONE)),
+            Err(PaletteEditError::NoSharedPalette)
+        );
+    }
+
+    #[test]
+    fn out_of_range_changes_nothing() {
+        let mut world = world_with_palette(3);
+        let before = colors(&world);
+        let out_of_range = Err(PaletteEditError::OutOfRange { index: 3, len: 3 });
+        assert_eq!(edit(&mut world, |e| e.remove(3)), out_of_range);
+        assert_eq!(edit(&mut world, |e| e.replace(3, Vec4::ONE)), out_of_range);
+        assert_eq!(edit(&mut world, |e| e.reorder(0, 3)), out_of_range);
+        assert_eq!(
+            edit(&mut world, |e| e.insert(4, Vec4::ONE)),
+            Err(PaletteEditError::OutOfRange { index: 4, len: 3 })
+        );
+        assert_eq!(colors(&world), before);
+        assert!(world.resource::<Messages<PaletteEdited>>().is_empty());
+    }
+
+    #[test]
+    fn full_palette_rejects_insert() {
+        let mut world = world_with_palette(MAX_PALETTE_COLORS);
+        assert_eq!(
+            edit(&mut world, |e| e.push(Vec4::ONE)),
+            Err(PaletteEditError::Full)
+        );
+        assert_eq!(colors(&world).len(), MAX_PALETTE_COLORS);
+    }
+
+    #[test]
+    fn last_color_is_kept() {
+        let mut world = world_with_palette(1);
+        assert_eq!(
+            edit(&mut world, |e| e.remove(0)),
+            Err(PaletteEditError::Empty)
+        );
+        assert_eq!(
+            edit(&mut world, |e| e.set(PixelArtPalette::new(Vec::new()))),
+            Err(PaletteEditError::Empty)
+        );
+        assert_eq!(colors(&world).len(), 1);
+    }
+
+    #[test]
+    fn valid_edits_apply_and_report() {
+        let mut world = world_with_palette(3);
+        assert_eq!(edit(&mut world, |e| e.reorder(0, 2)), Ok(()));
+        assert_eq!(edit(&mut world, |e| e.remove(0)), Ok(()));
+        assert_eq!(colors(&world), vec![Vec4::splat(2.0), Vec4::splat(0.0)]);
+        assert_eq!(world.resource::<Messages<PaletteEdited>>().len(), 2);
+    }
+
+    #[test]
+    fn index_params_follow_their_colors() {
+        let mut params = PixelArtShaderParams {
+            reveal_count: 4,
+            palette_b_offset: 8,
+            palette_b_count: 4,
+            ..default()
+        };
+        // Before both ranges: everything shifts down.
+        remap_index_params(PaletteEdit::Removed { index: 1 }, 15, &mut params);
+        assert_eq!(
+            (
+                params.reveal_count,
+                params.palette_b_offset,
+                params.palette_b_count
+            ),
+            (3, 7, 4)
+        );
+        // Inside the secondary range: it grows.
+        remap_index_params(PaletteEdit::Inserted { index: 9 }, 16, &mut params);
+        assert_eq!((params.palette_b_offset, params.palette_b_count), (7, 5));
+        // Shrinking the palette clamps the range.
+        remap_index_params(PaletteEdit::Reset, 9, &mut params);
+        assert_eq!(
+            (
+                params.reveal_count,
+                params.palette_b_offset,
+                params.palette_b_count
+            ),
+            (3, 7, 2)
+        );
+    }
+
+    #[test]
+    fn reveal_all_is_kept() {
+        assert_eq!(
+            remap_prefix(PaletteEdit::Removed { index: 0 }, 4, u32::MAX),
+            u32::MAX
+        );
+        // Moving a revealed color past the prefix leaves one revealed.
+        assert_eq!(remap_prefix(PaletteEdit::Moved { from: 0, to: 3 }, 4, 2), 1);
+    }
+}
@@ -0,0 +1,340 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::extract_resource::ExtractResource;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::renderer::RenderQueue;
+use bevy::render::storage::{GpuShaderStorageBuffer, ShaderStorageBuffer};
+
+use crate::{
+    PaletteTransitionCompleted, PixelArtExtension, PixelArtMaterial, PixelArtMaterials,
+    PixelArtPalette, SharedPalette, preview,
+};
+
+// ──────────────────────────────────────────────
+//  Public message / component
+// ──────────────────────────────────────────────
+
+/// Message retargeting pixel art materials to `palette`, for area transitions
+/// or a "corrupted world" look.
+///
+/// Every `PixelArtMaterial` (or, with `group`, the materials of entities
+/// tagged `PaletteSwapGroup(group)`) is bound to one palette buffer owned by
+/// the swap, so they all change in the same frame. With `crossfade` the
+/// buffer's colors blend from the previous palette to the new one over that
+/// many seconds, written in place. The swap waits until `palette` is loaded,
+/// and `PaletteTransitionCompleted` is written when it finishes.
+///
+/// Entities tagged into a swapped group later (or given a new material) are
+/// bound as they join. Other materials created after an all-materials swap
+/// keep their own palette, and swapped materials no longer follow
+/// `SharedPalette`. Materials are shared assets: a grouped swap also recolors
+/// untagged entities using the same material.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct SwapPalette {
+    pub palette: Handle<PixelArtPalette>,
+    /// Crossfade time in seconds (0 = instant).
+    pub crossfade: f32,
+    /// Only swap materials of entities with this `PaletteSwapGroup` (None = all).
+    pub group: Option<u32>,
+}
+
+impl SwapPalette {
+    /// Instantly swap every material to `palette`.
+    pub fn new(palette: Handle<PixelArtPalette>) -> Self {
+        Self {
+            palette,
+            crossfade: 0.0,
+            group: None,
+        }
+    }
+
+    /// Blend to the new palette over `seconds`.
+    pub fn with_crossfade(mut self, seconds: f32) -> Self {
+        self.crossfade = seconds;
+        self
+    }
+
+    /// Only swap materials of entities tagged `PaletteSwapGroup(group)`.
+    pub fn for_group(mut self, group: u32) -> Self {
+        self.group = Some(group);
+        self
+    }
+}
+
+/// Tags an entity's `PixelArtMaterial` for `SwapPalette::for_group(group)`.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct PaletteSwapGroup(pub u32);
+
+// ──────────────────────────────────────────────
+//  Swap state
+// ──────────────────────────────────────────────
+
+/// Palette buffer and crossfade state per swap group (None = all materials).
+#[derive(Resource, Default)]
+pub struct PaletteSwaps {
+    groups: HashMap<Option<u32>, GroupSwap>,
+}
+
+struct GroupSwap {
+    /// Buffer the group's materials are bound to.
+    buffer: Handle<ShaderStorageBuffer>,
+    /// Colors currently in `buffer`.
+    current: PixelArtPalette,
+    /// Colors at the start of the running crossfade.
+    from: PixelArtPalette,
+    target: Handle<PixelArtPalette>,
+    duration: f32,
+    elapsed: f32,
+    /// `target` has loaded and `buffer` holds its colors (or the fade to them).
+    started: bool,
+    /// Members still have to be bound to `buffer`.
+    rebind: bool,
+    done: bool,
+}
+
+impl PaletteSwaps {
+    /// Palette buffer of `group` (None = the all-materials swap), once swapped.
+    pub fn buffer(&self, group: Option<u32>) -> Option<&Handle<ShaderStorageBuffer>> {
+        self.groups.get(&group).map(|swap| &swap.buffer)
+    }
+
+    /// Whether a swap of `group` is still waiting for its palette or fading.
+    pub fn is_swapping(&self, group: Option<u32>) -> bool {
+        self.groups.get(&group).is_some_and(|swap| !swap.done)
+    }
+}
+
+/// Palette colors to write into existing swap buffers, extracted to the render
+/// world when they change.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct PaletteSwapUpload {
+    writes: Vec<(Handle<ShaderStorageBuffer>, Vec<u8>)>,
+    generation: u64,
+}
+
+/// Entry-wise linear blend of two palettes at `t`. Entries one palette lacks
+/// take the other palette's nearest color, so sizes may differ.
+fn blend_palettes(from: &PixelArtPalette, to: &PixelArtPalette, t: f32) -> PixelArtPalette {
+    let len = from.colors.len().max(to.colors.len());
+    let entry = |palette: &PixelArtPalette, other: &PixelArtPalette, i: usize| {
+        let fallback = || preview::nearest_palette_color(palette, other.colors[i].truncate());
+        palette
+            .colors
+            .get(i)
+            .map_or_else(fallback, |color| color.truncate())
+    };
+    PixelArtPalette::new(
+        (0..len)
+            .map(|i| {
+                let a = entry(from, to, i);
+                let b = entry(to, from, i);
+                a.lerp(b, t).extend(1.0)
+            })
+            .collect(),
+    )
+}
+
+/// Binds a material extension to the swap's buffer and palette count.
+fn bind_to_swap(extension: &mut PixelArtExtension, swap: &GroupSwap) {
+    extension.palette = swap.buffer.clone();
+    extension.params.palette_count = swap.current.count();
+    #[cfg(feature = "webgl2")]
+    {
+        extension.params.palette_colors = swap.current.uniform_fallback().0;
+    }
+}
+
+/// Entities that joined a swap group or changed material.
+type JoinedGroup = Or<(
+    Changed<PaletteSwapGroup>,
+    Changed<MeshMaterial3d<PixelArtMaterial>>,
+)>;
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Starts (or restarts) swaps from `SwapPalette` messages. A new group gets a
+/// buffer holding the current palette until its target loads.
+pub fn start_palette_swaps(
+    mut requests: MessageReader<SwapPalette>,
+    mut swaps: ResMut<PaletteSwaps>,
+    shared: Option<Res<SharedPalette>>,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+) {
+    for request in requests.read() {
+        match swaps.groups.get_mut(&request.group) {
+            Some(swap) => {
+                swap.from = swap.current.clone();
+                swap.target = request.palette.clone();
+                swap.duration = request.crossfade;
+                swap.elapsed = 0.0;
+                swap.rebind = true;
+                swap.done = false;
+            }
+            None => {
+                let current = shared.as_ref().map(|s| s.0.clone()).unwrap_or_default();
+                swaps.groups.insert(
+                    request.group,
+                    GroupSwap {
+                        buffer: buffers.add(current.to_storage_buffer()),
+                        from: current.clone(),
+                        current,
+                        target: request.palette.clone(),
+                        duration: request.crossfade,
+                        elapsed: 0.0,
+                        started: false,
+                        rebind: true,
+                        done: false,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Advances crossfades and queues each group's colors for an in-place write
+/// into its buffer. Only a resized palette re-creates the buffer (and rebinds
+/// its materials). Writes `PaletteTransitionCompleted` when a swap finishes.
+pub fn advance_palette_swaps(
+    time: Res<Time>,
+    mut swaps: ResMut<PaletteSwaps>,
+    palettes: Res<Assets<PixelArtPalette>>,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    mut upload: ResMut<PaletteSwapUpload>,
+    mut materials: PixelArtMaterials,
+    mut completed: MessageWriter<PaletteTransitionCompleted>,
+) {
+    let dt = time.delta_secs();
+    let mut writes = Vec::new();
+    for swap in swaps.groups.values_mut() {
+        if swap.done {
+            continue;
+        }
+        let Some(target) = palettes.get(&swap.target) else {
+            continue;
+        };
+
+        swap.elapsed += dt;
+        let t = if swap.duration > 0.0 {
+            (swap.elapsed / swap.duration).min(1.0)
+        } else {
+            1.0
+        };
+        #[cfg(not(feature = "webgl2"))]
+        let previous_count = swap.current.count();
+        swap.current = if t >= 1.0 {
+            target.clone()
+        } else {
+            blend_palettes(&swap.from, target, t)
+        };
+        swap.started = true;
+        swap.done = t >= 1.0;
+        if swap.done {
+            completed.write(PaletteTransitionCompleted {
+                count: swap.current.count(),
+            });
+        }
+
+        let buffer = swap.current.to_storage_buffer();
+        let bytes = buffer.data.clone().unwrap_or_default();
+        let resized = buffers
+            .get(&swap.buffer)
+            .and_then(|current| current.data.as_ref())
+            .is_none_or(|current| current.len() != bytes.len());
+        if resized {
+            let _ = buffers.insert(&swap.buffer, buffer);
+        } else {
+            writes.push((swap.buffer.clone(), bytes));
+        }
+
+        let count = swap.current.count();
+        let bound = |ext: &PixelArtExtension| ext.palette == swap.buffer;
+        #[cfg(feature = "webgl2")]
+        {
+            // The uniform fallback is a per-material copy.
+            let (fallback, _) = swap.current.uniform_fallback();
+            materials.update_where(bound, |params| {
+                params.palette_count = count;
+                params.palette_colors = fallback;
+            });
+        }
+        #[cfg(not(feature = "webgl2"))]
+        if count != previous_count {
+            materials.update_where(bound, |params| params.palette_count = count);
+        }
+        if resized {
+            // Rebind against the re-created buffer.
+            materials.touch_where(bound);
+        }
+    }
+
+    if !writes.is_empty() {
+        upload.writes = writes;
+        upload.generation += 1;
+    }
+}
+
+/// Binds the materials of a started swap to its buffer, and the materials of
+/// entities that join a swapped group afterwards.
+pub fn bind_palette_swaps(
+    mut swaps: ResMut<PaletteSwaps>,
+    mut materials: ResMut<Assets<PixelArtMaterial>>,
+    tagged: Query<(&PaletteSwapGroup, &MeshMaterial3d<PixelArtMaterial>)>,
+    joined: Query<(&PaletteSwapGroup, &MeshMaterial3d<PixelArtMaterial>), JoinedGroup>,
+) {
+    for (&group, swap) in &mut swaps.groups {
+        if !swap.started || !swap.rebind {
+            continue;
+        }
+        swap.rebind = false;
+        let ids: Vec<_> = match group {
+            Some(group) => tagged
+                .iter()
+                .filter(|(tag, _)| tag.0 == group)
+                .map(|(_, material)| material.0.id())
+                .collect(),
+            None => materials.ids().collect(),
+        };
+        for id in ids {
+            if let Some(material) = materials.get_mut(id) {
+                bind_to_swap(&mut material.extension, swap);
+            }
+        }
+    }
+
+    for (tag, material) in &joined {
+        let Some(swap) = swaps.groups.get(&Some(tag.0)).filter(|swap| swap.started) else {
+            continue;
+        };
+        if materials
+            .get(&material.0)
+            .is_some_and(|mat| mat.extension.palette != swap.buffer)
+            && let Some(mat) = materials.get_mut(&material.0)
+        {
+            bind_to_swap(&mut mat.extension, swap);
+        }
+    }
+}
+
+/// Writes a new `PaletteSwapUpload` into the swap buffers in place, keeping
+/// the material bind groups that hold them valid.
+pub fn write_palette_swaps(
+    upload: Res<PaletteSwapUpload>,
+    buffers: Res<RenderAssets<GpuShaderStorageBuffer>>,
+    queue: Res<RenderQueue>,
+    mut written: Local<u64>,
+) {
+    if upload.generation == *written {
+        return;
+    }
+    for (handle, bytes) in &upload.writes {
+        if let Some(buffer) = buffers.get(handle)
+            && buffer.buffer.size() == bytes.len() as u64
+        {
+            queue.write_buffer(&buffer.buffer, 0, bytes);
+        }
+    }
+    *written = upload.generation;
+}
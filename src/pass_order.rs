@@ -0,0 +1,62 @@
+use bevy::{
+    core_pipeline::core_3d::graph::Core3d,
+    prelude::*,
+    render::{
+        RenderApp,
+        render_graph::{Edge, InternedRenderLabel, RenderGraph, RenderLabel},
+    },
+};
+
+use crate::bloom::PixelArtBloomLabel;
+use crate::depth_of_field::PixelArtDepthOfFieldLabel;
+use crate::glitch::PixelArtGlitchLabel;
+use crate::outline::PixelArtOutlineLabel;
+use crate::post_quantize::PixelArtPostQuantizeLabel;
+use crate::skybox_quantize::PixelArtSkyboxQuantizeLabel;
+
+/// Order of the low-res camera's post passes. All of them sit between
+/// `Node3d::Tonemapping` and `Node3d::Fxaa`; each pass plugin calls
+/// `order_low_res_passes` from `finish`, chaining whichever are present:
+///
+/// skybox quantize → post quantize → outline → bloom → depth of field → glitch
+///
+/// Quantization comes first so outline colors stay exact, bloom halos come
+/// from the final palette colors, depth of field blurs the finished image and
+/// the glitch corrupts it last.
+pub fn low_res_pass_order() -> [InternedRenderLabel; 6] {
+    [
+        PixelArtSkyboxQuantizeLabel.intern(),
+        PixelArtPostQuantizeLabel.intern(),
+        PixelArtOutlineLabel.intern(),
+        PixelArtBloomLabel.intern(),
+        PixelArtDepthOfFieldLabel.intern(),
+        PixelArtGlitchLabel.intern(),
+    ]
+}
+
+/// Adds node edges between consecutive low-res passes in `Core3d`, skipping
+/// passes whose plugin is not added. Safe to call from every pass plugin.
+pub fn order_low_res_passes(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+    let Some(core_3d) = graph.get_sub_graph_mut(Core3d) else {
+        return;
+    };
+
+    let present: Vec<_> = low_res_pass_order()
+        .into_iter()
+        .filter(|label| core_3d.get_node_state(*label).is_ok())
+        .collect();
+    for pair in present.windows(2) {
+        let edge = Edge::NodeEdge {
+            output_node: pair[0],
+            input_node: pair[1],
+        };
+        // Already added when another pass plugin finished first.
+        if !core_3d.has_edge(&edge) {
+            core_3d.add_node_edge(pair[0], pair[1]);
+        }
+    }
+}
@@ -2,17 +2,22 @@
 //!
 //! Integrates with Bevy's full PBR lighting pipeline, then post-processes:
 //!   1. Toon quantize the PBR lighting result (hard band edges)
-//!   2. CIELAB palette quantization
-//!   3. Screen-space Bayer dithering (pixel-grid aligned)
+//!   2. Optional grayscale/sepia stylization
+//!   3. Palette quantization (CIELAB / OKLab / linear RGB / sRGB)
+//!   4. Screen-space Bayer dithering (pixel-grid aligned)
 //!
 //! debug_stage controls which stages are applied:
-//!   0 = full pipeline, 1 = PBR only, 2 = +toon, 3 = +palette, 4 = +dither
+//!   0 = full pipeline, 1 = PBR only, 2 = +toon, 3 = +palette, 4 = +dither,
+//!   5 = palette index false colors
 
 #import bevy_pbr::{
     pbr_fragment::pbr_input_from_standard_material,
     pbr_functions::alpha_discard,
+    mesh_view_bindings::view,
 }
 
+#import bevy_pixel_art_shader::common::{pixel_art, bayer, dither_cell, instance_overrides, flipbook_uv}
+
 #ifdef PREPASS_PIPELINE
 #import bevy_pbr::{
     prepass_io::{VertexOutput, FragmentOutput},
@@ -22,28 +27,157 @@
 #import bevy_pbr::{
     forward_io::{VertexOutput, FragmentOutput},
     pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+    clustering,
+    lighting::getDistanceAttenuation,
+    mesh_view_bindings as view_bindings,
+    mesh_view_types,
+    prepass_utils,
+    shadows,
+}
+#endif
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var sub_material_mask: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(103) var sub_material_mask_sampler: sampler;
+
+// ============================================================================
+// Palette storage (binding 101), uniform fallback on WebGL2
+// ============================================================================
+
+#ifdef PALETTE_STORAGE_BUFFER
+@group(#{MATERIAL_BIND_GROUP}) @binding(101)
+var<storage, read> palette_storage: array<vec4<f32>>;
+#endif
+
+fn palette_len() -> u32 {
+#ifdef PALETTE_STORAGE_BUFFER
+    return arrayLength(&palette_storage);
+#else
+    return 64u;
+#endif
 }
+
+fn palette_color(i: u32) -> vec3<f32> {
+#ifdef PALETTE_STORAGE_BUFFER
+    return palette_storage[i].rgb;
+#else
+    return pixel_art.palette_colors[i].rgb;
 #endif
+}
 
 // ============================================================================
-// Extension uniform (binding 100)
+// Sub-material resolution (mask texture / vertex color)
 // ============================================================================
 
-struct PixelArtParams {
-    base_tint: vec4<f32>,
+// Params that sub-materials may override, resolved per fragment.
+struct ResolvedParams {
+    tint: vec4<f32>,
     toon_bands: f32,
-    toon_softness: f32,
     toon_shadow_floor: f32,
-    dither_density: f32,
-    palette_count: u32,
     palette_strength: f32,
     dither_strength: f32,
-    debug_stage: u32,              // 0=full, 1=PBR, 2=+toon, 3=+palette, 4=+dither
-    palette_colors: array<vec4<f32>, 64>,
+    flash_color: vec4<f32>,
+    flash_amount: f32,
+    outline_color: vec4<f32>,
+    dissolve_amount: f32,
+}
+
+fn resolve_params(in: VertexOutput) -> ResolvedParams {
+    var r: ResolvedParams;
+    r.tint = pixel_art.base_tint;
+    r.toon_bands = pixel_art.toon_bands;
+    r.toon_shadow_floor = pixel_art.toon_shadow_floor;
+    r.palette_strength = pixel_art.palette_strength;
+    r.dither_strength = pixel_art.dither_strength;
+    r.flash_color = pixel_art.flash_color;
+    r.flash_amount = pixel_art.flash_amount;
+    r.outline_color = pixel_art.outline_color;
+    r.dissolve_amount = pixel_art.dissolve_amount;
+
+#ifdef PIXEL_ART_SUB_MATERIALS
+    var selector = vec4<f32>(0.0);
+    if (pixel_art.sub_material_source == 1u) {
+#ifdef VERTEX_UVS_A
+        selector = textureSample(sub_material_mask, sub_material_mask_sampler, in.uv);
+#endif
+    } else if (pixel_art.sub_material_source == 2u) {
+#ifdef VERTEX_COLORS
+        selector = in.color;
+#endif
+    }
+
+    // Dominant channel above 0.5 picks the set.
+    var best = 0.5;
+    var index = -1;
+    for (var i = 0; i < 4; i++) {
+        if (selector[i] > best) {
+            best = selector[i];
+            index = i;
+        }
+    }
+    if (index >= 0) {
+        let sub = pixel_art.sub_params[index];
+        r.tint = sub.tint;
+        r.toon_bands = sub.toon_bands;
+        r.toon_shadow_floor = sub.toon_shadow_floor;
+        r.palette_strength = sub.palette_strength;
+        r.dither_strength = sub.dither_strength;
+    }
+#endif
+
+    // PixelArtInstanceParams: tint multiplies, dither strength, flash,
+    // outline color and dissolve replace.
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    let instance = instance_overrides(in.instance_index);
+#else
+    let instance = instance_overrides(0u);
+#endif
+    r.tint *= instance.tint;
+    if (instance.dither_strength >= 0.0) {
+        r.dither_strength = instance.dither_strength;
+    }
+    if (instance.flash_amount >= 0.0) {
+        r.flash_color = instance.flash_color;
+        r.flash_amount = instance.flash_amount;
+    }
+    if (instance.outline_color.a > 0.0) {
+        r.outline_color = instance.outline_color;
+    }
+    if (instance.dissolve_amount >= 0.0) {
+        r.dissolve_amount = instance.dissolve_amount;
+    }
+    return r;
+}
+
+// Palette entries available at this fragment. Progressive reveal: outside the
+// reveal sphere only the first reveal_count entries (at least one, so the
+// world never falls back to unquantized color) are available.
+fn revealed_palette_count(in: VertexOutput) -> u32 {
+    var count = pixel_art.palette_count;
+#ifdef PIXEL_ART_PALETTE_REVEAL
+    if (pixel_art.reveal_radius <= 0.0
+        || distance(in.world_position.xyz, pixel_art.reveal_origin) > pixel_art.reveal_radius) {
+        count = min(count, max(pixel_art.reveal_count, 1u));
+    }
+#endif
+    return count;
 }
 
-@group(#{MATERIAL_BIND_GROUP}) @binding(100)
-var<uniform> pixel_art: PixelArtParams;
+#ifdef PIXEL_ART_PALETTE_BLEND
+// Weight toward the secondary palette (0 = primary only, 1 = secondary only).
+fn palette_blend_weight(in: VertexOutput) -> f32 {
+    let source = pixel_art.palette_blend_source;
+    if (source >= 1u && source <= 4u) {
+#ifdef VERTEX_COLORS
+        return in.color[source - 1u];
+#endif
+    } else if (source == 5u) {
+#ifdef VERTEX_UVS_B
+        return in.uv_b.x;
+#endif
+    }
+    return 0.0;
+}
+#endif
 
 // ============================================================================
 // Toon quantization (hard edge version)
@@ -58,6 +192,216 @@ fn toon_quantize(value: f32, bands: f32, softness: f32) -> f32 {
     return smoothstep(nearest - softness, nearest + softness, value);
 }
 
+// Explicit (threshold, level) bands — asymmetric lit/mid/shadow splits.
+fn toon_explicit(value: f32, softness: f32) -> f32 {
+    var result = 0.0;
+    for (var i = 0u; i < min(pixel_art.toon_threshold_count, 8u); i++) {
+        let threshold = pixel_art.toon_thresholds[i / 4u][i % 4u];
+        let level = pixel_art.toon_levels[i / 4u][i % 4u];
+        var w = select(0.0, 1.0, value >= threshold);
+        if (softness >= 0.001) {
+            w = smoothstep(threshold - softness, threshold + softness, value);
+        }
+        result = mix(result, level, w);
+    }
+    return result;
+}
+
+// Luminance distance to the nearest band boundary (round() snaps at k + 0.5).
+fn toon_boundary_distance(value: f32, bands: f32) -> f32 {
+#ifdef PIXEL_ART_EXPLICIT_BANDS
+    if (pixel_art.toon_threshold_count > 0u) {
+        var d = 1.0;
+        for (var i = 0u; i < min(pixel_art.toon_threshold_count, 8u); i++) {
+            d = min(d, abs(value - pixel_art.toon_thresholds[i / 4u][i % 4u]));
+        }
+        return d;
+    }
+#endif
+    return abs(fract(value * bands) - 0.5) / max(bands, 1.0);
+}
+
+// ============================================================================
+// Per-light lighting corrections (forward pass only)
+// ============================================================================
+//
+// Bevy's lighting is evaluated in one pass, so per-light stylization is done
+// as a diffuse correction on top of it: subtract the light's smooth Lambert
+// term and add back the stylized one. Specular is left as Bevy computed it.
+
+#ifndef PREPASS_PIPELINE
+fn fragment_view_z(world_position: vec4<f32>) -> f32 {
+    return dot(vec4<f32>(
+        view.view_from_world[0].z,
+        view.view_from_world[1].z,
+        view.view_from_world[2].z,
+        view.view_from_world[3].z,
+    ), world_position);
+}
+
+#ifdef PIXEL_ART_LOCAL_LIGHT_BANDS
+// Distance attenuation with light_falloff_bands rings over the light's
+// range: each ring takes the attenuation at its middle distance.
+fn stepped_distance_attenuation(distance_square: f32, inverse_range_squared: f32, in: VertexOutput) -> f32 {
+    let range = inverseSqrt(max(inverse_range_squared, 1e-12));
+    let rings = pixel_art.light_falloff_bands;
+    let offset = (bayer(dither_position(in)) - 0.5) * pixel_art.light_falloff_dither;
+    let ring = floor(sqrt(distance_square) / range * rings + offset);
+    let ring_distance = (max(ring, 0.0) + 0.5) / rings * range;
+    return getDistanceAttenuation(ring_distance * ring_distance, inverse_range_squared);
+}
+
+// Diffuse response (attenuation × N·L × shadow) of one clustered light:
+// x with smooth distance attenuation, y with light_falloff_bands rings
+// (equal to x when the rings are off).
+fn local_light_response(light_id: u32, is_spot: bool, in: VertexOutput, n: vec3<f32>) -> vec2<f32> {
+    let light = &view_bindings::clusterable_objects.data[light_id];
+    let to_light = (*light).position_radius.xyz - in.world_position.xyz;
+    let l = normalize(to_light);
+    let distance_square = dot(to_light, to_light);
+    let inverse_range_squared = (*light).color_inverse_square_range.w;
+    var attenuation = getDistanceAttenuation(distance_square, inverse_range_squared);
+    var stepped_attenuation = attenuation;
+    if (pixel_art.light_falloff_bands > 0.0) {
+        stepped_attenuation = stepped_distance_attenuation(distance_square, inverse_range_squared, in);
+    }
+    var shadow = 1.0;
+
+    if (is_spot) {
+        // Spot direction is packed as xz + sign of y (see Bevy's spot_light()).
+        var spot_dir = vec3<f32>((*light).light_custom_data.x, 0.0, (*light).light_custom_data.y);
+        spot_dir.y = sqrt(max(0.0, 1.0 - spot_dir.x * spot_dir.x - spot_dir.z * spot_dir.z));
+        if (((*light).flags & mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u) {
+            spot_dir.y = -spot_dir.y;
+        }
+        let cone = saturate(
+            dot(-spot_dir, l) * (*light).light_custom_data.z + (*light).light_custom_data.w
+        );
+        attenuation *= cone * cone;
+        stepped_attenuation *= cone * cone;
+        if (((*light).flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+            shadow = shadows::fetch_spot_shadow(
+                light_id, in.world_position, in.world_normal, (*light).shadow_map_near_z,
+            );
+        }
+    } else if (((*light).flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+        shadow = shadows::fetch_point_shadow(light_id, in.world_position, in.world_normal);
+    }
+
+    return vec2<f32>(attenuation, stepped_attenuation) * saturate(dot(n, l)) * shadow;
+}
+
+// Bands the diffuse contribution of every point/spot light in this
+// fragment's cluster: distance rings first, then the per-light response
+// bands. Returns the correction to add to the PBR result.
+fn local_light_banding(in: VertexOutput, n: vec3<f32>, diffuse_color: vec3<f32>) -> vec3<f32> {
+    let view_z = fragment_view_z(in.world_position);
+    let is_orthographic = view.clip_from_view[3].w == 1.0;
+    let cluster_index = clustering::fragment_cluster_index(in.position.xy, view_z, is_orthographic);
+    let ranges = clustering::unpack_clusterable_object_index_ranges(cluster_index);
+
+    var correction = vec3<f32>(0.0);
+    for (var i = ranges.first_point_light_index_offset; i < ranges.first_reflection_probe_index_offset; i++) {
+        let light_id = clustering::get_clusterable_object_id(i);
+        let is_spot = i >= ranges.first_spot_light_index_offset;
+        let bands = select(pixel_art.point_light_bands, pixel_art.spot_light_bands, is_spot);
+        if (bands <= 0.0 && pixel_art.light_falloff_bands <= 0.0) {
+            continue;
+        }
+        let response = local_light_response(light_id, is_spot, in, n);
+        let smooth_response = response.x;
+        var stepped = response.y;
+        if (bands > 0.0) {
+            stepped = toon_quantize(stepped, bands, 0.0);
+        }
+        let light_color = view_bindings::clusterable_objects.data[light_id].color_inverse_square_range.rgb;
+        // Lambert diffuse, matching Bevy's 1/π normalization.
+        correction += (stepped - smooth_response) * light_color * diffuse_color * (1.0 / 3.14159265);
+    }
+    return correction * view.exposure;
+}
+#endif
+
+#ifdef PIXEL_ART_SHADOW_EDGE
+// Replaces the filtered penumbra of every shadowed directional light with a
+// hard (1) or Bayer-dithered (2) edge. Returns the correction to add to the
+// PBR result.
+fn directional_shadow_edge(in: VertexOutput, n: vec3<f32>, diffuse_color: vec3<f32>) -> vec3<f32> {
+    let view_z = fragment_view_z(in.world_position);
+    var threshold = 0.5;
+    if (pixel_art.shadow_edge == 2u) {
+        // Offset keeps fully shadowed (0.0) texels intact.
+        threshold = bayer(dither_position(in)) + 0.001;
+    }
+
+    var correction = vec3<f32>(0.0);
+    for (var i = 0u; i < view_bindings::lights.n_directional_lights; i++) {
+        let light = &view_bindings::lights.directional_lights[i];
+        if (((*light).flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) == 0u) {
+            continue;
+        }
+        let soft = shadows::fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
+        let hard = select(0.0, 1.0, soft >= threshold);
+        let n_dot_l = saturate(dot(n, (*light).direction_to_light));
+        correction += (hard - soft) * n_dot_l * (*light).color.rgb * diffuse_color * (1.0 / 3.14159265);
+    }
+    return correction * view.exposure;
+}
+#endif
+
+#ifdef DEPTH_PREPASS
+// Ambient occlusion stepped into ssao_bands levels. ssao_dither moves the
+// step threshold from rounding (0) to the Bayer value (1); ssao_strength
+// fades the result toward no occlusion.
+fn banded_occlusion(occlusion: vec3<f32>, cell: vec2<f32>) -> vec3<f32> {
+    let level = saturate(dot(occlusion, vec3<f32>(1.0 / 3.0))) * pixel_art.ssao_bands;
+    var step = floor(level);
+    if (fract(level) > mix(0.5, bayer(cell), saturate(pixel_art.ssao_dither))) {
+        step += 1.0;
+    }
+    let banded = min(step / pixel_art.ssao_bands, 1.0);
+    return vec3<f32>(mix(1.0, banded, saturate(pixel_art.ssao_strength)));
+}
+
+// Per-object outline (`outline_color`): the outline node's silhouette / crease
+// test, run against the view's prepass textures.
+fn on_outline_edge(in: VertexOutput) -> bool {
+    let center_depth = prepass_utils::prepass_depth(in.position, 0u);
+#ifdef NORMAL_PREPASS
+    let center_normal = prepass_utils::prepass_normal(in.position, 0u);
+#endif
+    let offsets = array<vec2<f32>, 4>(
+        vec2<f32>(1.0, 0.0), vec2<f32>(-1.0, 0.0), vec2<f32>(0.0, 1.0), vec2<f32>(0.0, -1.0),
+    );
+    for (var i = 0u; i < 4u; i++) {
+        let p = in.position + vec4<f32>(offsets[i], 0.0, 0.0);
+        // Silhouette: the neighbor is farther away (reversed-Z).
+        let depth = prepass_utils::prepass_depth(p, 0u);
+        if ((center_depth - depth) / max(center_depth, 1e-6) > pixel_art.outline_depth_threshold) {
+            return true;
+        }
+#ifdef NORMAL_PREPASS
+        // Crease: +x / +y neighbors only, for one-texel lines.
+        if (pixel_art.outline_normal_threshold > 0.0 && i % 2u == 0u) {
+            let normal = prepass_utils::prepass_normal(p, 0u);
+            if (1.0 - dot(center_normal, normal) > pixel_art.outline_normal_threshold) {
+                return true;
+            }
+        }
+#endif
+    }
+    return false;
+}
+#endif
+#endif
+
+// Rotate hue around the gray axis (Rodrigues rotation), angle in radians.
+fn hue_rotate(color: vec3<f32>, angle: f32) -> vec3<f32> {
+    let k = vec3<f32>(0.57735026);
+    let c = cos(angle);
+    return color * c + cross(k, color) * sin(angle) + k * dot(k, color) * (1.0 - c);
+}
+
 // ============================================================================
 // CIELAB color conversion
 // ============================================================================
@@ -92,43 +436,149 @@ fn linear_rgb_to_lab(rgb: vec3<f32>) -> vec3<f32> {
 }
 
 // ============================================================================
-// Palette matching (CIELAB nearest-neighbor)
+// OKLab color conversion
+// ============================================================================
+
+fn linear_rgb_to_oklab(rgb: vec3<f32>) -> vec3<f32> {
+    let l = dot(vec3<f32>(0.4122214708, 0.5363325363, 0.0514459929), rgb);
+    let m = dot(vec3<f32>(0.2119034982, 0.6806995451, 0.1073969566), rgb);
+    let s = dot(vec3<f32>(0.0883024619, 0.2817188376, 0.6299787005), rgb);
+    let lms = pow(max(vec3<f32>(l, m, s), vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        dot(vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468), lms),
+        dot(vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099), lms),
+        dot(vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660), lms),
+    );
+}
+
+// ============================================================================
+// sRGB transfer function
+// ============================================================================
+
+fn linear_to_srgb(rgb: vec3<f32>) -> vec3<f32> {
+    let c = max(rgb, vec3<f32>(0.0));
+    let lo = c * 12.92;
+    let hi = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(hi, lo, c <= vec3<f32>(0.0031308));
+}
+
+// Convert linear RGB into the configured matching space.
+fn to_quantization_space(rgb: vec3<f32>) -> vec3<f32> {
+#ifdef PIXEL_ART_COLOR_SPACES
+    switch (pixel_art.quantization_space) {
+        case 1u: { return linear_rgb_to_oklab(rgb); }
+        case 2u: { return rgb; }
+        case 3u: { return linear_to_srgb(rgb); }
+        default: { return linear_rgb_to_lab(rgb); }
+    }
+#else
+    return linear_rgb_to_lab(rgb);
+#endif
+}
+
+// Palette distance. In the Lab spaces, lightness and chroma are weighted
+// separately so quantization can favor brightness ramps over hue accuracy.
+fn palette_distance(a: vec3<f32>, b: vec3<f32>) -> f32 {
+    let d = a - b;
+#ifdef PIXEL_ART_COLOR_SPACES
+    if (pixel_art.quantization_space > 1u) {
+        return length(d);
+    }
+#endif
+    return sqrt(
+        pixel_art.lightness_weight * d.x * d.x
+            + pixel_art.chroma_weight * (d.y * d.y + d.z * d.z)
+    );
+}
+
+// ============================================================================
+// Stylization (grayscale / sepia color matrix)
+// ============================================================================
+
+fn stylize(color: vec3<f32>) -> vec3<f32> {
+    return mix(color, pixel_art.stylize_matrix * color, pixel_art.stylize_strength);
+}
+
+fn chromaticity(rgb: vec3<f32>) -> vec3<f32> {
+    return rgb / max(rgb.r + rgb.g + rgb.b, 0.0001);
+}
+
+// While stylizing, only palette entries the matrix leaves (nearly) unchanged
+// in chromaticity are eligible — grays for grayscale, browns for sepia.
+fn palette_entry_allowed(pal_rgb: vec3<f32>) -> bool {
+#ifdef PIXEL_ART_STYLIZE
+    if (pixel_art.stylize_strength > 0.0) {
+        let shift = distance(chromaticity(pal_rgb), chromaticity(stylize(pal_rgb)));
+        return shift <= pixel_art.stylize_palette_tolerance;
+    }
+#endif
+    return true;
+}
+
+// ============================================================================
+// Palette matching (nearest-neighbor in the configured color space)
 // ============================================================================
 
 struct PaletteMatch {
     nearest_rgb: vec3<f32>,
     second_rgb: vec3<f32>,
     blend: f32,
+    nearest_index: u32,
+    second_index: u32,
 }
 
-fn find_palette_match(color: vec3<f32>) -> PaletteMatch {
-    let lab = linear_rgb_to_lab(color);
+// Distinct false color per palette index (golden-ratio hue steps, with the
+// value alternating every 8 entries) for the palette index debug stage.
+fn palette_index_color(index: u32) -> vec3<f32> {
+    let hue = fract(f32(index) * 0.618034);
+    let k = fract(vec3<f32>(hue) + vec3<f32>(0.0, 2.0, 1.0) / 3.0);
+    let rgb = clamp(abs(k * 6.0 - 3.0) - 1.0, vec3<f32>(0.0), vec3<f32>(1.0));
+    return rgb * select(1.0, 0.55, (index / 8u) % 2u == 1u);
+}
+
+fn find_palette_match(color: vec3<f32>, count: u32) -> PaletteMatch {
+    return find_palette_range_match(color, 0u, count);
+}
+
+// Nearest two entries among `count` palette entries starting at `start`.
+fn find_palette_range_match(color: vec3<f32>, start: u32, count: u32) -> PaletteMatch {
+    let lab = to_quantization_space(color);
 
     var d1: f32 = 1e10;
     var d2: f32 = 1e10;
     var c1: vec3<f32> = color;
     var c2: vec3<f32> = color;
-
-    let count = pixel_art.palette_count;
-    for (var i: u32 = 0u; i < count; i++) {
-        let pal_rgb = pixel_art.palette_colors[i].rgb;
-        let pal_lab = linear_rgb_to_lab(pal_rgb);
-        let dist = distance(lab, pal_lab);
+    var i1 = start;
+    var i2 = start;
+
+    let n = min(start + count, palette_len());
+    for (var i: u32 = start; i < n; i++) {
+        let pal_rgb = palette_color(i);
+        if (!palette_entry_allowed(pal_rgb)) {
+            continue;
+        }
+        let pal_lab = to_quantization_space(pal_rgb);
+        let dist = palette_distance(lab, pal_lab);
 
         if (dist < d1) {
             d2 = d1;
             c2 = c1;
+            i2 = i1;
             d1 = dist;
             c1 = pal_rgb;
+            i1 = i;
         } else if (dist < d2) {
             d2 = dist;
             c2 = pal_rgb;
+            i2 = i;
         }
     }
 
     var result: PaletteMatch;
     result.nearest_rgb = c1;
     result.second_rgb = c2;
+    result.nearest_index = i1;
+    result.second_index = i2;
     let total = d1 + d2;
     if (total > 0.001) {
         result.blend = d1 / total;
@@ -139,19 +589,22 @@ fn find_palette_match(color: vec3<f32>) -> PaletteMatch {
 }
 
 // ============================================================================
-// 4x4 Bayer dithering matrix
+// Dither coordinates
 // ============================================================================
 
-fn bayer4x4(pos: vec2<f32>) -> f32 {
-    let x = u32(pos.x) % 4u;
-    let y = u32(pos.y) % 4u;
-    var matrix = array<array<f32, 4>, 4>(
-        array<f32, 4>( 0.0/16.0,  8.0/16.0,  2.0/16.0, 10.0/16.0),
-        array<f32, 4>(12.0/16.0,  4.0/16.0, 14.0/16.0,  6.0/16.0),
-        array<f32, 4>( 3.0/16.0, 11.0/16.0,  1.0/16.0,  9.0/16.0),
-        array<f32, 4>(15.0/16.0,  7.0/16.0, 13.0/16.0,  5.0/16.0),
-    );
-    return matrix[y][x];
+fn dither_position(in: VertexOutput) -> vec2<f32> {
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    let instance_index = in.instance_index;
+#else
+    let instance_index = 0u;
+#endif
+#ifdef VERTEX_UVS_A
+    // Flipbook tile UVs, as the prepass sees them.
+    let uv = flipbook_uv(in.uv);
+#else
+    let uv = vec2<f32>(0.0);
+#endif
+    return dither_cell(in.position.xy, in.world_position.xyz, in.world_normal, uv, instance_index);
 }
 
 // ============================================================================
@@ -164,10 +617,37 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     // --- 1. Build PBR input from base StandardMaterial ---
-    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    // Flipbook: textures are sampled in the current sprite-sheet tile.
+    var mesh_in = in;
+#ifdef VERTEX_UVS_A
+    mesh_in.uv = flipbook_uv(in.uv);
+#endif
+    var pbr_input = pbr_input_from_standard_material(mesh_in, is_front);
+    let params = resolve_params(in);
+
+    // base_color already holds StandardMaterial base_color × base_color_texture;
+    // the tint multiplies it, so texture detail survives toon/palette steps.
+    pbr_input.material.base_color *= params.tint;
+
+    // Screen-door transparency: drop Bayer cells in proportion to alpha and
+    // draw the rest opaque. The prepass discards the same cells.
+    if (pixel_art.dither_alpha != 0u) {
+        if (bayer(dither_position(in)) >= pbr_input.material.base_color.a) {
+            discard;
+        }
+        pbr_input.material.base_color.a = 1.0;
+    }
 
-    // Multiply texture-sampled base color with tint (preserves texture detail)
-    pbr_input.material.base_color *= pixel_art.base_tint;
+    // Dissolve: cells whose threshold is below dissolve_amount are gone, the
+    // next dissolve_edge_width of thresholds draw in the edge color.
+    var dissolve_edge = false;
+    if (params.dissolve_amount > 0.0) {
+        let threshold = bayer(dither_position(in));
+        if (threshold < params.dissolve_amount) {
+            discard;
+        }
+        dissolve_edge = threshold < params.dissolve_amount + pixel_art.dissolve_edge_width;
+    }
 
     // Alpha discard
     pbr_input.material.base_color = alpha_discard(
@@ -175,15 +655,74 @@ fn fragment(
         pbr_input.material.base_color,
     );
 
+    // Attenuate the normal map toward the geometric normal before lighting.
+    if (pixel_art.normal_map_strength < 1.0) {
+        pbr_input.N = normalize(mix(
+            pbr_input.world_normal,
+            pbr_input.N,
+            max(pixel_art.normal_map_strength, 0.0),
+        ));
+    }
+
+#ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
+    // Banded SSAO: pbr_input_from_standard_material folded the camera's SSAO
+    // into diffuse_occlusion; step it before lighting.
+    if (pixel_art.ssao_bands > 0.0) {
+        pbr_input.diffuse_occlusion = banded_occlusion(pbr_input.diffuse_occlusion, dither_position(in));
+    }
+#endif
+
 #ifdef PREPASS_PIPELINE
     let out = deferred_output(in, pbr_input);
 #else
     var out: FragmentOutput;
 
     // --- 2. Bevy PBR lighting (all scene lights, shadows, IBL) ---
-    out.color = apply_pbr_lighting(pbr_input);
+    // Unlit (particles / VFX): base color plus emissive, no lighting or toon
+    // bands, straight to palette and dither.
+    let unlit = pixel_art.unlit != 0u;
+    if (unlit) {
+        let material = pbr_input.material;
+        out.color = vec4<f32>(
+            material.base_color.rgb + material.emissive.rgb * mix(1.0, view.exposure, material.emissive.a),
+            material.base_color.a,
+        );
+    } else {
+        out.color = apply_pbr_lighting(pbr_input);
+    }
     var color = out.color.rgb;
 
+    // Emissive banding: take the emissive term (as apply_pbr_lighting added
+    // it) out of the lit color, to step it separately after the toon pass.
+    var emissive = vec3<f32>(0.0);
+    if (pixel_art.emissive_bands > 0.0 && !unlit) {
+        let material_emissive = pbr_input.material.emissive;
+        emissive = material_emissive.rgb * out.color.a
+            * mix(1.0, view.exposure, material_emissive.a);
+        color = max(color - emissive, vec3<f32>(0.0));
+    }
+
+#ifdef PIXEL_ART_LOCAL_LIGHT_BANDS
+    // Point/spot lights banded per light, before the global toon pass.
+    if (!unlit && (pixel_art.point_light_bands > 0.0 || pixel_art.spot_light_bands > 0.0
+        || pixel_art.light_falloff_bands > 0.0)) {
+        let diffuse_color = pbr_input.material.base_color.rgb * (1.0 - pbr_input.material.metallic);
+        color = max(color + local_light_banding(in, pbr_input.N, diffuse_color), vec3<f32>(0.0));
+    }
+#endif
+
+#ifdef PIXEL_ART_SHADOW_EDGE
+    // Hard / dithered directional shadow edges instead of the PCF penumbra.
+    if (pixel_art.shadow_edge != 0u && !unlit) {
+        let diffuse_color = pbr_input.material.base_color.rgb * (1.0 - pbr_input.material.metallic);
+        color = max(color + directional_shadow_edge(in, pbr_input.N, diffuse_color), vec3<f32>(0.0));
+    }
+#endif
+
+    // Pre-quantization exposure (auto-exposure or manual).
+    color *= pixel_art.exposure;
+    emissive *= pixel_art.exposure;
+
     // Stage 1: PBR only — stop here
     if (pixel_art.debug_stage == 1u) {
         out.color = vec4<f32>(color, out.color.a);
@@ -193,14 +732,71 @@ fn fragment(
 
     // --- 3. Toon quantize the lit result (hard band edges) ---
     let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
-    if (luminance > 0.001) {
-        let toon_lum = toon_quantize(luminance, pixel_art.toon_bands, pixel_art.toon_softness);
-        let final_lum = mix(pixel_art.toon_shadow_floor, 1.0, toon_lum);
+    var band_distance = 0.0;
+    if (!unlit && luminance > 0.001) {
+        band_distance = toon_boundary_distance(luminance, params.toon_bands);
+        var toon_lum = toon_quantize(luminance, params.toon_bands, pixel_art.toon_softness);
+#ifdef PIXEL_ART_EXPLICIT_BANDS
+        if (pixel_art.toon_threshold_count > 0u) {
+            toon_lum = toon_explicit(luminance, pixel_art.toon_softness);
+        }
+#endif
+        let final_lum = mix(params.toon_shadow_floor, 1.0, toon_lum);
         color = color * (final_lum / luminance);
-    } else {
+
+#ifdef PIXEL_ART_HUE_SHIFT
+        // Hue shift: shaded bands toward shadow_hue_shift, lit bands toward
+        // highlight_hue_shift.
+        let hue_shift = mix(pixel_art.shadow_hue_shift, pixel_art.highlight_hue_shift, toon_lum);
+        if (hue_shift != 0.0) {
+            color = max(hue_rotate(color, radians(hue_shift)), vec3<f32>(0.0));
+        }
+#endif
+    } else if (!unlit) {
         color = vec3<f32>(0.0);
     }
+
+    // Emissive back on top in emissive_bands brightness steps (rounded up, so
+    // a faint glow still shows).
+    let emissive_luminance = dot(emissive, vec3<f32>(0.2126, 0.7152, 0.0722));
+    if (emissive_luminance > 0.001) {
+        let stepped = ceil(emissive_luminance * pixel_art.emissive_bands) / pixel_art.emissive_bands;
+        color += emissive * (stepped / emissive_luminance);
+    }
+#ifdef TONEMAP_IN_SHADER
     color = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+#else
+    // HDR target (tonemapped later by the camera): quantize the color scaled
+    // into [0, 1] and restore the overbright factor afterwards, so emissive
+    // pixel art keeps its energy for bloom instead of clipping.
+    let hdr_scale = max(max(color.r, max(color.g, color.b)), 1.0);
+    color = clamp(color / hdr_scale, vec3<f32>(0.0), vec3<f32>(1.0));
+#endif
+
+    // Rim, dissolve edge and flash colors snap to the revealed entries too.
+    let revealed_palette = revealed_palette_count(in);
+
+#ifdef PIXEL_ART_RIM_LIGHT
+    // One-band rim light on grazing angles, in a palette color.
+    if (pixel_art.rim_strength > 0.0) {
+        let facing = 1.0 - saturate(dot(pbr_input.N, pbr_input.V));
+        if (facing >= 1.0 - pixel_art.rim_width) {
+            var rim_rgb = pixel_art.rim_color.rgb;
+            if (revealed_palette > 0u) {
+                rim_rgb = find_palette_match(rim_rgb, revealed_palette).nearest_rgb;
+            }
+            color = mix(color, rim_rgb, saturate(pixel_art.rim_strength));
+        }
+    }
+#endif
+
+    if (dissolve_edge) {
+        var edge_rgb = pixel_art.dissolve_edge_color.rgb;
+        if (revealed_palette > 0u) {
+            edge_rgb = find_palette_match(edge_rgb, revealed_palette).nearest_rgb;
+        }
+        color = edge_rgb;
+    }
 
     // Stage 2: PBR + Toon — stop here
     if (pixel_art.debug_stage == 2u) {
@@ -209,28 +805,85 @@ fn fragment(
         return out;
     }
 
-    // --- 4. CIELAB palette quantization ---
-    if (pixel_art.palette_count > 0u) {
-        let pm = find_palette_match(color);
+#ifdef PIXEL_ART_STYLIZE
+    // --- 4. Stylization (grayscale/sepia) before palette matching ---
+    if (pixel_art.stylize_strength > 0.0) {
+        color = clamp(stylize(color), vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+#endif
+
+    // --- 5. Palette quantization in the configured color space ---
+    var palette_count = revealed_palette;
+    var palette_start = 0u;
+#ifdef PIXEL_ART_PALETTE_BLEND
+    // Multi-palette blending: a Bayer-dithered choice between the primary and
+    // secondary palette, weighted per vertex. The cell is offset so the choice
+    // does not correlate with the color dither below.
+    if (palette_count > 0u && pixel_art.palette_b_count > 0u && pixel_art.palette_blend_source != 0u) {
+        let weight = palette_blend_weight(in);
+        if (bayer(dither_position(in) + vec2<f32>(2.0, 1.0)) < weight) {
+            palette_start = pixel_art.palette_b_offset;
+            palette_count = pixel_art.palette_b_count;
+        }
+    }
+#endif
+    // Stage 5: palette index false colors (black = not quantized).
+    let index_stage = pixel_art.debug_stage == 5u;
+    var palette_index_rgb = vec3<f32>(0.0);
+
+    if (palette_count > 0u && pixel_art.no_quantize == 0u) {
+        let pm = find_palette_range_match(color, palette_start, palette_count);
         var quantized = pm.nearest_rgb;
+        var index = pm.nearest_index;
 
         // Stage 3: +Palette (no dither) — skip dithering
-        if (pixel_art.debug_stage != 3u && pixel_art.dither_strength > 0.0) {
+        // Transition-only dithering: flat band interiors stay solid.
+        let in_window = pixel_art.dither_band_window <= 0.0
+            || band_distance <= pixel_art.dither_band_window;
+        if (pixel_art.debug_stage != 3u && params.dither_strength > 0.0 && in_window) {
             // Screen-space Bayer dithering: aligns with pixel grid, no surface distortion.
             // blend ∈ [0, 0.5]: 0 = exact palette match, 0.5 = equidistant between two colors.
             // At blend=0.5, 50% of Bayer cells pick the second color → ordered dithering.
-            let threshold = bayer4x4(floor(in.position.xy * pixel_art.dither_density));
-            if (threshold < pm.blend * pixel_art.dither_strength) {
+            let threshold = bayer(dither_position(in));
+            if (threshold < pm.blend * params.dither_strength) {
                 quantized = pm.second_rgb;
+                index = pm.second_index;
             }
         }
 
-        color = mix(color, quantized, pixel_art.palette_strength);
+        color = mix(color, quantized, params.palette_strength);
+        palette_index_rgb = palette_index_color(index);
     }
+    // --- Hit flash over the quantized result, faded through the Bayer
+    // pattern so partial flashes stay on-palette ---
+    if (params.flash_amount > 0.0 && bayer(dither_position(in)) < params.flash_amount) {
+        var flash_rgb = params.flash_color.rgb;
+        if (revealed_palette > 0u) {
+            flash_rgb = find_palette_match(flash_rgb, revealed_palette).nearest_rgb;
+        }
+        color = flash_rgb;
+    }
+    if (index_stage) {
+        // Exact false colors: skip fog and tonemapping.
+        out.color = vec4<f32>(palette_index_rgb, out.color.a);
+        return out;
+    }
+#ifndef TONEMAP_IN_SHADER
+    color *= hdr_scale;
+#endif
+
+#ifdef DEPTH_PREPASS
+    // Per-object outline color (material or OutlineColor instance slot),
+    // drawn after quantization so the (already palette-snapped) line color is
+    // not dithered.
+    if (params.outline_color.a > 0.0 && on_outline_edge(in)) {
+        color = mix(color, params.outline_color.rgb, params.outline_color.a);
+    }
+#endif
 
     out.color = vec4<f32>(color, out.color.a);
 
-    // --- 5. Post-lighting (fog, tonemapping, etc.) ---
+    // --- 6. Post-lighting (fog, tonemapping, etc.) ---
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
 #endif
 
@@ -0,0 +1,303 @@
+//! Shared declarations for the pixel art forward and prepass fragments: the
+//! extension uniform, per-entity overrides, flipbook UVs and the Bayer dither
+//! pattern.
+
+#define_import_path bevy_pixel_art_shader::common
+
+#import bevy_pbr::{
+    mesh_view_bindings::{view, globals},
+    mesh_functions,
+}
+
+// ============================================================================
+// Extension uniform (binding 100)
+// ============================================================================
+
+struct PixelArtSubParams {
+    tint: vec4<f32>,
+    toon_bands: f32,
+    toon_shadow_floor: f32,
+    palette_strength: f32,
+    dither_strength: f32,
+}
+
+struct PixelArtParams {
+    base_tint: vec4<f32>,
+    toon_bands: f32,
+    toon_softness: f32,
+    toon_shadow_floor: f32,
+    dither_density: f32,
+    palette_count: u32,
+    palette_strength: f32,
+    dither_strength: f32,
+    debug_stage: u32,              // 0=full, 1=PBR, 2=+toon, 3=+palette, 4=+dither, 5=palette index
+    reveal_origin: vec3<f32>,
+    reveal_radius: f32,
+    reveal_count: u32,
+    stylize_matrix: mat3x3<f32>,
+    stylize_strength: f32,
+    stylize_palette_tolerance: f32,
+    quantization_space: u32,       // 0=CIELAB, 1=OKLab, 2=linear RGB, 3=sRGB
+    sub_material_source: u32,      // 0=off, 1=mask texture, 2=vertex color
+    sub_params: array<PixelArtSubParams, 4>,
+    lightness_weight: f32,
+    chroma_weight: f32,
+    shadow_hue_shift: f32,
+    highlight_hue_shift: f32,
+    dither_direction: vec3<f32>,
+    dither_direction_space: u32,   // 0=screen, 1=world
+    dither_stretch: f32,
+    dither_skew: f32,
+    toon_threshold_count: u32,
+    toon_thresholds: array<vec4<f32>, 2>,
+    toon_levels: array<vec4<f32>, 2>,
+    rim_strength: f32,
+    rim_width: f32,
+    rim_color: vec4<f32>,
+    point_light_bands: f32,
+    spot_light_bands: f32,
+    shadow_edge: u32,              // 0=soft (PCF), 1=hard, 2=Bayer-dithered
+    no_quantize: u32,
+    bayer_size: u32,               // 2, 4 or 8
+    dither_anchor: u32,            // 0=screen, 1=object, 2=world, 3=UV
+    dither_anchor_scale: f32,
+    exposure: f32,
+    dither_temporal_cycle: u32,
+    dither_alpha: u32,             // 0=off, 1=screen-door
+    prepass_alpha: f32,
+    outline_participation: f32,    // scales prepass_alpha (0 = no edge detection)
+    dither_band_window: f32,
+    dissolve_amount: f32,
+    dissolve_edge_width: f32,
+    dissolve_edge_color: vec4<f32>,
+    palette_blend_source: u32,     // 0=off, 1-4=vertex color RGBA, 5=UV1.x
+    palette_b_offset: u32,
+    palette_b_count: u32,
+    outline_color: vec4<f32>,
+    outline_depth_threshold: f32,
+    outline_normal_threshold: f32,
+    outline_normal_map: u32,
+    emissive_bands: f32,
+    normal_map_strength: f32,
+    wind_strength: f32,
+    wind_frequency: f32,
+    wind_direction: vec3<f32>,
+    wind_wavelength: f32,
+    wind_fps: f32,
+    wind_weight: u32,              // 0=local height, 1=vertex color red, 2=uniform
+    wind_height: f32,
+    unlit: u32,                    // 0=lit, 1=unlit (palette + dither only)
+    flipbook_tiles_x: u32,
+    flipbook_tiles_y: u32,
+    flipbook_frame_count: u32,
+    flipbook_frame: u32,
+    flipbook_fps: f32,
+    flash_color: vec4<f32>,
+    flash_amount: f32,
+    ssao_bands: f32,
+    ssao_dither: f32,
+    ssao_strength: f32,
+    light_falloff_bands: f32,
+    light_falloff_dither: f32,
+    dither_density_space: u32,     // 0=screen pixels, 1=world units
+    dither_texels_per_uv: f32,
+#ifndef PALETTE_STORAGE_BUFFER
+    palette_colors: array<vec4<f32>, 64>,   // WebGL2 only
+#endif
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> pixel_art: PixelArtParams;
+
+// ============================================================================
+// Per-entity overrides (binding 104), addressed by the mesh tag
+// ============================================================================
+
+struct PixelArtInstance {
+    tint: vec4<f32>,
+    dither_strength: f32,          // < 0 = material value
+    flash_color: vec4<f32>,
+    flash_amount: f32,             // < 0 = material value
+    outline_color: vec4<f32>,      // alpha 0 = material value
+    dissolve_amount: f32,          // < 0 = material value
+}
+
+#ifdef PIXEL_ART_INSTANCE_PARAMS
+@group(#{MATERIAL_BIND_GROUP}) @binding(104)
+var<storage, read> instance_params: array<PixelArtInstance>;
+#endif
+
+// Overrides of the drawn instance; tag 0 (untagged meshes) is the identity.
+fn instance_overrides(instance_index: u32) -> PixelArtInstance {
+    var o: PixelArtInstance;
+    o.tint = vec4<f32>(1.0);
+    o.dither_strength = -1.0;
+    o.flash_color = vec4<f32>(1.0);
+    o.flash_amount = -1.0;
+    o.outline_color = vec4<f32>(0.0);
+    o.dissolve_amount = -1.0;
+#ifdef PIXEL_ART_INSTANCE_PARAMS
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    let tag = mesh_functions::get_tag(instance_index);
+    if (tag != 0u && tag < arrayLength(&instance_params)) {
+        o = instance_params[tag];
+    }
+#endif
+#endif
+    return o;
+}
+
+// ============================================================================
+// Flipbook (sprite-sheet tiles on the first UV set)
+// ============================================================================
+
+// `uv` mapped into the current tile of a flipbook_tiles_x × flipbook_tiles_y
+// sheet, frames numbered row-major from the top left.
+fn flipbook_uv(uv: vec2<f32>) -> vec2<f32> {
+    let tiles = max(vec2<u32>(pixel_art.flipbook_tiles_x, pixel_art.flipbook_tiles_y), vec2<u32>(1u));
+    if (tiles.x == 1u && tiles.y == 1u) {
+        return uv;
+    }
+    var count = tiles.x * tiles.y;
+    if (pixel_art.flipbook_frame_count > 0u) {
+        count = min(pixel_art.flipbook_frame_count, count);
+    }
+    var frame = pixel_art.flipbook_frame;
+    if (pixel_art.flipbook_fps > 0.0) {
+        frame += u32(floor(globals.time * pixel_art.flipbook_fps));
+    }
+    frame = frame % count;
+    let tile = vec2<f32>(f32(frame % tiles.x), f32(frame / tiles.x));
+    return (tile + fract(uv)) / vec2<f32>(tiles);
+}
+
+// ============================================================================
+// Bayer dithering matrix (2x2 / 4x4 / 8x8)
+// ============================================================================
+
+// Threshold in [0, 1) of the recursive Bayer matrix of size `bayer_size`.
+// Each coordinate bit pair adds one 2x2 level; the lowest bits are the most
+// significant digit, which reproduces the classic 4x4 table at size 4.
+fn bayer(pos: vec2<f32>) -> f32 {
+    var levels = 2u;
+    if (pixel_art.bayer_size <= 2u) {
+        levels = 1u;
+    } else if (pixel_art.bayer_size >= 8u) {
+        levels = 3u;
+    }
+    let x = u32(pos.x);
+    let y = u32(pos.y);
+    var index = 0u;
+    for (var i = 0u; i < levels; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / f32(1u << (2u * levels));
+}
+
+// ============================================================================
+// Dither coordinates (screen / object / world / UV space, optionally anisotropic)
+// ============================================================================
+
+#ifdef PIXEL_ART_DITHER_ANCHOR
+// Surface position in Bayer cells, in object or world space, projected along
+// the dominant axis of the surface normal (triplanar-style).
+fn anchored_dither_position(world_position: vec3<f32>, world_normal: vec3<f32>, instance_index: u32) -> vec2<f32> {
+    var p = world_position;
+    var n = world_normal;
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    if (pixel_art.dither_anchor == 1u) {
+        let world_from_local = mesh_functions::get_world_from_local(instance_index);
+        let m = mat3x3<f32>(world_from_local[0].xyz, world_from_local[1].xyz, world_from_local[2].xyz);
+        let scale_sq = vec3<f32>(dot(m[0], m[0]), dot(m[1], m[1]), dot(m[2], m[2]));
+        // Inverse of a TRS transform: local = S⁻² Mᵀ (p - t); normals map by Mᵀ.
+        p = (transpose(m) * (p - world_from_local[3].xyz)) / scale_sq;
+        n = transpose(m) * n;
+    }
+#endif
+    let a = abs(n);
+    var uv = p.xy;
+    if (a.x >= a.y && a.x >= a.z) {
+        uv = p.zy;
+    } else if (a.y >= a.z) {
+        uv = p.xz;
+    }
+    // World y is up, framebuffer y is down.
+    return vec2<f32>(uv.x, -uv.y) * pixel_art.dither_anchor_scale;
+}
+#endif
+
+// Screen-space direction of a world-space axis at `world_pos`.
+fn project_world_axis(world_pos: vec3<f32>, axis: vec3<f32>) -> vec2<f32> {
+    let a = view.clip_from_world * vec4<f32>(world_pos, 1.0);
+    let b = view.clip_from_world * vec4<f32>(world_pos + axis, 1.0);
+    let d = b.xy / b.w - a.xy / a.w;
+    // NDC y is up, framebuffer y is down.
+    return vec2<f32>(d.x, -d.y);
+}
+
+// Bayer cells per screen pixel for `dither_density` cells per world unit at
+// `world_position`, snapped to a power of two so each depth range keeps an
+// undistorted grid (at most one cell per pixel).
+fn world_dither_scale(world_position: vec3<f32>) -> f32 {
+    // Screen pixels per world unit at this depth.
+    var pixels_per_unit = 0.5 * view.viewport.w * view.clip_from_view[1][1];
+    if (view.clip_from_view[3].w != 1.0) {
+        let view_z = -(view.view_from_world * vec4<f32>(world_position, 1.0)).z;
+        pixels_per_unit /= max(view_z, 1e-4);
+    }
+    let scale = pixel_art.dither_density / max(pixels_per_unit, 1e-4);
+    return min(exp2(round(log2(max(scale, 1e-6)))), 1.0);
+}
+
+// Cell coordinates fed to the Bayer matrix. Shared by the forward and prepass
+// fragments so both see the same pattern (screen-door holes must line up with
+// the depth prepass).
+fn dither_cell(
+    frag_coord: vec2<f32>,
+    world_position: vec3<f32>,
+    world_normal: vec3<f32>,
+    uv: vec2<f32>,
+    instance_index: u32,
+) -> vec2<f32> {
+    var pos = frag_coord * pixel_art.dither_density;
+    if (pixel_art.dither_density_space == 1u) {
+        pos = frag_coord * world_dither_scale(world_position);
+    }
+#ifdef PIXEL_ART_DITHER_ANCHOR
+    if (pixel_art.dither_anchor == 3u) {
+        pos = uv * pixel_art.dither_texels_per_uv;
+    } else if (pixel_art.dither_anchor != 0u) {
+        pos = anchored_dither_position(world_position, world_normal, instance_index);
+    }
+#endif
+
+#ifdef PIXEL_ART_DIRECTIONAL_DITHER
+    if (pixel_art.dither_stretch != 1.0 || pixel_art.dither_skew != 0.0) {
+        var dir = pixel_art.dither_direction.xy;
+        if (pixel_art.dither_direction_space == 1u) {
+            dir = project_world_axis(world_position, pixel_art.dither_direction);
+        }
+        if (length(dir) > 1e-5) {
+            dir = normalize(dir);
+            let perp = vec2<f32>(-dir.y, dir.x);
+            let v = dot(pos, perp);
+            let u = dot(pos, dir) / max(pixel_art.dither_stretch, 1e-3) + v * pixel_art.dither_skew;
+            pos = vec2<f32>(u, v);
+        }
+    }
+#endif
+
+    // Temporal dithering: shift the pattern by an R2 low-discrepancy offset
+    // per frame, so consecutive frames cover different thresholds.
+    if (pixel_art.dither_temporal_cycle > 0u) {
+        let k = f32(globals.frame_count % pixel_art.dither_temporal_cycle);
+        pos += floor(fract(k * vec2<f32>(0.7548777, 0.5698403)) * 8.0);
+    }
+
+    // Wrap to one 8x8 tile (the largest Bayer matrix) so negative rotated or
+    // world-space coordinates stay in the u32 range.
+    let cell = floor(pos);
+    return cell - 8.0 * floor(cell / 8.0);
+}
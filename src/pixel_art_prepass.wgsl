@@ -1,18 +1,151 @@
-//! Custom prepass fragment for pixel art models: writes alpha=1.0 to the normal
-//! prepass texture so the edge detection shader draws outlines on these pixels.
+//! Custom prepass fragment for pixel art models: writes `prepass_alpha`
+//! (default 1.0) to the normal prepass texture so the edge detection shader
+//! draws outlines on these pixels.
 //! (Mirrors terrain_prepass.wgsl but with alpha=1.0 instead of 0.0)
 
 #import bevy_pbr::{
     prepass_io::{VertexOutput, FragmentOutput},
     pbr_prepass_functions,
+    pbr_bindings,
+    pbr_functions,
+    pbr_types,
+    mesh_view_bindings::view,
+}
+
+#import bevy_pixel_art_shader::common::{pixel_art, bayer, dither_cell, instance_overrides, flipbook_uv}
+
+// Base color alpha as the forward pass sees it (material × tint × instance
+// tint × texture × vertex color). Sub-material tints are not applied here.
+fn base_alpha(in: VertexOutput) -> f32 {
+    var alpha = pbr_bindings::material.base_color.a * pixel_art.base_tint.a;
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    alpha *= instance_overrides(in.instance_index).tint.a;
+#endif
+#ifdef VERTEX_COLORS
+    alpha *= in.color.a;
+#endif
+#ifdef VERTEX_UVS_A
+    if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
+        let uv = (pbr_bindings::material.uv_transform * vec3(in.uv, 1.0)).xy;
+        alpha *= textureSampleBias(
+            pbr_bindings::base_color_texture,
+            pbr_bindings::base_color_sampler,
+            uv,
+            view.mip_bias,
+        ).a;
+    }
+#endif
+    return alpha;
+}
+
+// AlphaMode::Mask cutoff on the same alpha the forward pass tests (including
+// base_tint), so cutout holes carry no depth and outlines follow the cutout.
+// Bevy's prepass_alpha_discard then covers the blend modes.
+fn mask_discard(in: VertexOutput) {
+#ifdef MAY_DISCARD
+    let alpha_mode = pbr_bindings::material.flags
+        & pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_RESERVED_BITS;
+    if (alpha_mode == pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK
+        && base_alpha(in) < pbr_bindings::material.alpha_cutoff) {
+        discard;
+    }
+#endif
+}
+
+// Discards the same Bayer cells as the forward pass (screen-door alpha and
+// dissolve), so the holes carry no depth or outline.
+fn dither_discard(in: VertexOutput, face_normal: vec3<f32>) {
+    var dissolve_amount = pixel_art.dissolve_amount;
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    let instance_dissolve = instance_overrides(in.instance_index).dissolve_amount;
+    if (instance_dissolve >= 0.0) {
+        dissolve_amount = instance_dissolve;
+    }
+#endif
+    if (pixel_art.dither_alpha == 0u && dissolve_amount <= 0.0) {
+        return;
+    }
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    let n = in.world_normal;
+#else
+    let n = face_normal;
+#endif
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    let instance_index = in.instance_index;
+#else
+    let instance_index = 0u;
+#endif
+#ifdef VERTEX_UVS_A
+    let uv = in.uv;
+#else
+    let uv = vec2<f32>(0.0);
+#endif
+    let threshold = bayer(dither_cell(in.position.xy, in.world_position.xyz, n, uv, instance_index));
+    if (threshold < dissolve_amount) {
+        discard;
+    }
+    if (pixel_art.dither_alpha != 0u && threshold >= base_alpha(in)) {
+        discard;
+    }
+}
+
+#ifdef NORMAL_PREPASS
+// Normal written to the prepass: geometric, or normal-mapped when
+// `outline_normal_map` is set so painted creases reach edge detection.
+fn prepass_normal(in: VertexOutput, is_front: bool) -> vec3<f32> {
+    var n = normalize(in.world_normal);
+#ifdef VERTEX_TANGENTS
+#ifdef VERTEX_UVS_A
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+    if (pixel_art.outline_normal_map != 0u) {
+        let uv = (pbr_bindings::material.uv_transform * vec3(in.uv, 1.0)).xy;
+        let nt = textureSampleBias(
+            pbr_bindings::normal_map_texture,
+            pbr_bindings::normal_map_sampler,
+            uv,
+            view.mip_bias,
+        ).rgb;
+        let double_sided = (pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT) != 0u;
+        let tbn = pbr_functions::calculate_tbn_mikktspace(n, in.world_tangent);
+        let mapped = pbr_functions::apply_normal_mapping(
+            pbr_bindings::material.flags, tbn, double_sided, is_front, nt,
+        );
+        // Same attenuation as the lit normal in the main pass.
+        n = normalize(mix(n, mapped, clamp(pixel_art.normal_map_strength, 0.0, 1.0)));
+    }
+#endif
+#endif
+#endif
+    return n;
+}
+#endif
+
+// Flat normal from screen-space derivatives, for anchored dithering when the
+// prepass has no interpolated normal. Only the dominant axis matters.
+fn face_normal(in: VertexOutput) -> vec3<f32> {
+    let p = in.world_position.xyz;
+    return normalize(cross(dpdy(p), dpdx(p)));
+}
+
+// Vertex output with the first UV set in the current flipbook tile, so
+// cutouts match the frame the forward pass shows.
+fn flipbook_input(mesh_in: VertexOutput) -> VertexOutput {
+    var in = mesh_in;
+#ifdef VERTEX_UVS_A
+    in.uv = flipbook_uv(mesh_in.uv);
+#endif
+    return in;
 }
 
 #ifdef PREPASS_FRAGMENT
 @fragment
 fn fragment(
-    in: VertexOutput,
+    mesh_in: VertexOutput,
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
+    let in = flipbook_input(mesh_in);
+    dither_discard(in, face_normal(in));
+    mask_discard(in);
     pbr_prepass_functions::prepass_alpha_discard(in);
 
     var out: FragmentOutput;
@@ -22,9 +155,18 @@ fn fragment(
 #endif
 
 #ifdef NORMAL_PREPASS
-    // Write correct normal (world space, packed to [0,1]) with alpha = 1.0
-    // Alpha 1.0 tells the edge detection shader to draw outlines on this pixel
-    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), 1.0);
+    // Write correct normal (world space, packed to [0,1]) with prepass_alpha
+    // scaled by outline_participation. Alpha 1.0 (default) tells the edge
+    // detection shader to draw outlines here
+    var mask = pixel_art.prepass_alpha * pixel_art.outline_participation;
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    // OutlineColor entities draw their own lines: ⅔ makes PixelArtOutline
+    // skip them (mask encoding: `HOLDOUT_MASK_ALPHA`, lib.rs).
+    if (mask > 0.5 && instance_overrides(in.instance_index).outline_color.a > 0.0) {
+        mask = 2.0 / 3.0;
+    }
+#endif
+    out.normal = vec4(prepass_normal(in, is_front) * 0.5 + vec3(0.5), mask);
 #endif
 
 #ifdef MOTION_VECTOR_PREPASS
@@ -37,7 +179,10 @@ fn fragment(
 }
 #else
 @fragment
-fn fragment(in: VertexOutput) {
+fn fragment(mesh_in: VertexOutput) {
+    let in = flipbook_input(mesh_in);
+    dither_discard(in, face_normal(in));
+    mask_discard(in);
     pbr_prepass_functions::prepass_alpha_discard(in);
 }
 #endif
@@ -0,0 +1,231 @@
+//! Vertex shader for pixel art models (forward and prepass/shadow pipelines).
+//!
+//! Bevy's mesh vertex stage (morph targets, skinning, motion vectors) plus
+//! wind sway: a world-space offset weighted per vertex, advanced in discrete
+//! time steps so the motion reads as pixel-art frames.
+
+#import bevy_pbr::{
+    mesh_bindings::mesh,
+    mesh_functions,
+    skinning,
+    morph::morph,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_pixel_art_shader::common::pixel_art
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+#else
+#import bevy_pbr::forward_io::{Vertex, VertexOutput}
+#endif
+
+#import bevy_pbr::mesh_view_bindings::globals
+
+#ifdef MORPH_TARGETS
+fn morph_vertex(vertex_in: Vertex) -> Vertex {
+    var vertex = vertex_in;
+    let first_vertex = mesh[vertex.instance_index].first_vertex_index;
+    let vertex_index = vertex.index - first_vertex;
+
+    let weight_count = bevy_pbr::morph::layer_count();
+    for (var i: u32 = 0u; i < weight_count; i++) {
+        let weight = bevy_pbr::morph::weight_at(i);
+        if (weight == 0.0) {
+            continue;
+        }
+        vertex.position += weight * morph(vertex_index, bevy_pbr::morph::position_offset, i);
+#ifdef VERTEX_NORMALS
+        vertex.normal += weight * morph(vertex_index, bevy_pbr::morph::normal_offset, i);
+#endif
+#ifdef VERTEX_TANGENTS
+        vertex.tangent += vec4<f32>(weight * morph(vertex_index, bevy_pbr::morph::tangent_offset, i), 0.0);
+#endif
+    }
+    return vertex;
+}
+#endif
+
+// ============================================================================
+// Wind
+// ============================================================================
+
+// Sway weight of a vertex: squared local height, painted vertex color red
+// (`color_red` < 0 when the mesh has no vertex colors) or uniform.
+fn wind_weight(local_position: vec3<f32>, color_red: f32) -> f32 {
+    if (pixel_art.wind_weight == 2u) {
+        return 1.0;
+    }
+    if (pixel_art.wind_weight == 1u && color_red >= 0.0) {
+        return color_red;
+    }
+    let h = saturate(local_position.y / max(pixel_art.wind_height, 1e-3));
+    return h * h;
+}
+
+// World-space sway offset at `time`, held between `wind_fps` steps.
+fn wind_offset(world_position: vec3<f32>, weight: f32, time: f32) -> vec3<f32> {
+#ifdef PIXEL_ART_WIND
+    if (pixel_art.wind_strength <= 0.0 || weight <= 0.0) {
+        return vec3<f32>(0.0);
+    }
+    var t = time;
+    if (pixel_art.wind_fps > 0.0) {
+        t = floor(t * pixel_art.wind_fps) / pixel_art.wind_fps;
+    }
+    var dir = pixel_art.wind_direction;
+    dir = select(vec3<f32>(1.0, 0.0, 0.0), normalize(dir), length(dir) > 1e-5);
+    // Gusts roll along the wind: phase advances with distance downwind.
+    let phase = dot(world_position, dir) * 6.2831853 / max(pixel_art.wind_wavelength, 1e-3);
+    let w = 6.2831853 * pixel_art.wind_frequency * t - phase;
+    // Lean downwind plus a faster flutter.
+    let sway = 0.5 + 0.5 * sin(w) + 0.2 * sin(2.3 * w + 1.7);
+    return dir * (sway * pixel_art.wind_strength * weight);
+#else
+    return vec3<f32>(0.0);
+#endif
+}
+
+fn vertex_color_red(vertex: Vertex) -> f32 {
+#ifdef VERTEX_COLORS
+    return vertex.color.r;
+#else
+    return -1.0;
+#endif
+}
+
+// ============================================================================
+// Vertex stage
+// ============================================================================
+
+@vertex
+fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+#ifdef MORPH_TARGETS
+    var vertex = morph_vertex(vertex_no_morph);
+#else
+    var vertex = vertex_no_morph;
+#endif
+
+    let mesh_world_from_local = mesh_functions::get_world_from_local(vertex_no_morph.instance_index);
+
+#ifdef SKINNED
+    var world_from_local = skinning::skin_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex_no_morph.instance_index,
+    );
+#else
+    // vertex_no_morph.instance_index works around a wgpu DX12 bug (naga#2416).
+    var world_from_local = mesh_world_from_local;
+#endif
+
+    let weight = wind_weight(vertex.position, vertex_color_red(vertex));
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    out.world_position += vec4<f32>(wind_offset(out.world_position.xyz, weight, globals.time), 0.0);
+    out.position = position_world_to_clip(out.world_position.xyz);
+
+#ifdef PREPASS_PIPELINE
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0);
+#endif
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+    // Normals and tangents: the prepass only outputs them for the normal /
+    // deferred prepass.
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef VERTEX_NORMALS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(world_from_local, vertex.normal);
+#else
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex_no_morph.instance_index,
+    );
+#endif
+#endif
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex_no_morph.instance_index,
+    );
+#endif
+#endif
+#else
+#ifdef VERTEX_NORMALS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(world_from_local, vertex.normal);
+#else
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex_no_morph.instance_index,
+    );
+#endif
+#endif
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex_no_morph.instance_index,
+    );
+#endif
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef PREPASS_PIPELINE
+#ifdef MOTION_VECTOR_PREPASS
+    // Where the vertex was last frame, wind included.
+#ifdef SKINNED
+#ifdef HAS_PREVIOUS_SKIN
+    let prev_world_from_local = skinning::skin_prev_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex_no_morph.instance_index,
+    );
+#else
+    let prev_world_from_local = world_from_local;
+#endif
+#else
+    let prev_world_from_local = mesh_functions::get_previous_world_from_local(vertex_no_morph.instance_index);
+#endif
+    out.previous_world_position = mesh_functions::mesh_position_local_to_world(
+        prev_world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    out.previous_world_position += vec4<f32>(
+        wind_offset(out.previous_world_position.xyz, weight, globals.time - globals.delta_time),
+        0.0,
+    );
+#endif
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex_no_morph.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex_no_morph.instance_index,
+        mesh_world_from_local[3],
+    );
+#endif
+
+    return out;
+}
@@ -0,0 +1,189 @@
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::{CameraUpdateSystems, RenderTarget};
+use bevy::prelude::*;
+use bevy::render::render_resource::Extent3d;
+
+use crate::{
+    PixelArtPostQuantize, PixelArtPostQuantizePlugin, PixelArtUpscale, PixelArtUpscalePlugin,
+    lowres_target,
+};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Single-camera pixel art: the camera renders at `resolution` instead of to
+/// its window, gets palette quantization and dither, and is upscaled back.
+///
+/// A lower-friction entry point than the low-res camera + `PixelArtCompositor`
+/// pair, for projects where the whole scene is pixel art and any materials
+/// are fine. On insert, the camera's target is swapped for a `lowres_target`
+/// image, a `PixelArtPostQuantize` is added (unless present; tune it there)
+/// and a presenter camera is spawned on the original target with a
+/// `PixelArtUpscale` of the image. Removing the component despawns the
+/// presenter, restores the target and removes the `PixelArtPostQuantize` if
+/// it was added here. Needs `PixelArtCameraPlugin`.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtCamera {
+    /// Internal render resolution (default: 320×180).
+    pub resolution: UVec2,
+    /// Scale by whole multiples only; see `PixelArtUpscale::integer_scaling`
+    /// (default: true).
+    pub integer_scaling: bool,
+    /// Border around the upscaled image (default: black).
+    pub letterbox_color: Color,
+}
+
+impl Default for PixelArtCamera {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(320, 180),
+            integer_scaling: true,
+            letterbox_color: Color::BLACK,
+        }
+    }
+}
+
+impl PixelArtCamera {
+    /// Render at `width`×`height`.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            resolution: UVec2::new(width, height),
+            ..default()
+        }
+    }
+}
+
+/// Set up by `setup_pixel_art_cameras`: the presenter camera, the target
+/// the camera had before, and whether `PixelArtPostQuantize` was added with it.
+#[derive(Component, Clone)]
+pub struct PixelArtCameraOutput {
+    pub presenter: Entity,
+    pub original_target: RenderTarget,
+    /// `PixelArtPostQuantize` was inserted by setup, so teardown removes it.
+    pub inserted_quantize: bool,
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+/// Adds `PixelArtCamera`, with the upscale and post-quantize passes it uses.
+pub struct PixelArtCameraPlugin;
+
+impl Plugin for PixelArtCameraPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PixelArtUpscalePlugin>() {
+            app.add_plugins(PixelArtUpscalePlugin);
+        }
+        if !app.is_plugin_added::<PixelArtPostQuantizePlugin>() {
+            app.add_plugins(PixelArtPostQuantizePlugin);
+        }
+
+        app.register_type::<PixelArtCamera>();
+        app.add_systems(
+            PostUpdate,
+            (setup_pixel_art_cameras, teardown_pixel_art_cameras).before(CameraUpdateSystems),
+        );
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// `PixelArtCamera` with its target and what `setup_pixel_art_cameras`
+/// already attached to it.
+type PixelArtCameraData = (
+    Entity,
+    &'static PixelArtCamera,
+    &'static Camera,
+    &'static mut RenderTarget,
+    Option<&'static PixelArtCameraOutput>,
+    Has<PixelArtPostQuantize>,
+);
+
+/// Redirects new `PixelArtCamera`s to a low-res image and spawns their
+/// presenters; keeps image size and presenter settings in sync on change.
+pub fn setup_pixel_art_cameras(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<PixelArtCameraData, Changed<PixelArtCamera>>,
+    mut presenters: Query<&mut PixelArtUpscale>,
+) {
+    for (entity, pixel_camera, camera, mut target, output, has_quantize) in &mut cameras {
+        let size = pixel_camera.resolution.max(UVec2::ONE);
+
+        if let Some(output) = output {
+            // Already set up: follow resolution and presenter settings.
+            if let RenderTarget::Image(image) = &*target
+                && let Some(image) = images.get_mut(&image.handle)
+                && image.size() != size
+            {
+                image.resize(Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                });
+            }
+            if let Ok(mut upscale) = presenters.get_mut(output.presenter) {
+                upscale.integer_scaling = pixel_camera.integer_scaling;
+                upscale.letterbox_color = pixel_camera.letterbox_color;
+            }
+            continue;
+        }
+
+        let image = images.add(lowres_target(size, false));
+        let original_target =
+            std::mem::replace(&mut *target, RenderTarget::Image(image.clone().into()));
+        let presenter = commands
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    order: camera.order + 1,
+                    ..default()
+                },
+                original_target.clone(),
+                // Nothing to draw: the presenter only shows the upscaled image.
+                RenderLayers::none(),
+                PixelArtUpscale {
+                    lowres_image: image,
+                    integer_scaling: pixel_camera.integer_scaling,
+                    letterbox_color: pixel_camera.letterbox_color,
+                },
+            ))
+            .id();
+
+        let mut entity = commands.entity(entity);
+        entity.insert(PixelArtCameraOutput {
+            presenter,
+            original_target,
+            inserted_quantize: !has_quantize,
+        });
+        if !has_quantize {
+            entity.insert(PixelArtPostQuantize::default());
+        }
+    }
+}
+
+/// Restores the original target, despawns the presenter and removes the
+/// auto-inserted `PixelArtPostQuantize` when `PixelArtCamera` is removed.
+pub fn teardown_pixel_art_cameras(
+    mut commands: Commands,
+    mut removed: RemovedComponents<PixelArtCamera>,
+    mut cameras: Query<(&PixelArtCameraOutput, &mut RenderTarget)>,
+) {
+    for entity in removed.read() {
+        let Ok((output, mut target)) = cameras.get_mut(entity) else {
+            continue;
+        };
+        *target = output.original_target.clone();
+        commands.entity(output.presenter).try_despawn();
+        let mut entity = commands.entity(entity);
+        entity.remove::<PixelArtCameraOutput>();
+        if output.inserted_quantize {
+            entity.remove::<PixelArtPostQuantize>();
+        }
+    }
+}
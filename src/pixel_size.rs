@@ -0,0 +1,92 @@
+use bevy::camera::{RenderTarget, ScalingMode};
+use bevy::prelude::*;
+use bevy::render::render_resource::Extent3d;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Derives the low-res camera's target resolution from a world-space pixel
+/// size, so world pixel density stays constant when the framing changes.
+///
+/// Put it on an orthographic low-res camera rendering to an image. Whenever
+/// the projection changes (zoom, scaling mode, aspect), the target image is
+/// resized to `visible area / world_units_per_pixel`. Use a scaling mode that
+/// does not depend on the target's pixel size (`FixedVertical`, `AutoMin`, …);
+/// `ScalingMode::WindowSize` would feed back into itself and is ignored.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PhysicalPixelSize {
+    /// World units covered by one low-res pixel (default: 1/16).
+    pub world_units_per_pixel: f32,
+    /// Upper bound for the derived resolution (default: 1920×1080).
+    pub max_resolution: UVec2,
+}
+
+impl Default for PhysicalPixelSize {
+    fn default() -> Self {
+        Self {
+            world_units_per_pixel: 1.0 / 16.0,
+            max_resolution: UVec2::new(1920, 1080),
+        }
+    }
+}
+
+impl PhysicalPixelSize {
+    /// `pixels_per_unit` low-res pixels per world unit.
+    pub fn pixels_per_unit(pixels_per_unit: f32) -> Self {
+        Self {
+            world_units_per_pixel: 1.0 / pixels_per_unit,
+            ..default()
+        }
+    }
+
+    /// Target resolution for an orthographic view covering `area_size` world units.
+    pub fn resolution_for(&self, area_size: Vec2) -> UVec2 {
+        (area_size / self.world_units_per_pixel.max(1e-6))
+            .ceil()
+            .as_uvec2()
+            .clamp(UVec2::ONE, self.max_resolution.max(UVec2::ONE))
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Cameras whose projection or pixel size changed.
+type ProjectionChanged = Or<(Changed<Projection>, Changed<PhysicalPixelSize>)>;
+
+/// Resizes the render target of every `PhysicalPixelSize` camera whose
+/// projection changed. Runs after Bevy has updated the projection area.
+pub fn apply_physical_pixel_size(
+    mut images: ResMut<Assets<Image>>,
+    cameras: Query<(&PhysicalPixelSize, &Projection, &RenderTarget), ProjectionChanged>,
+) {
+    for (pixel_size, projection, target) in &cameras {
+        let Projection::Orthographic(ortho) = projection else {
+            continue;
+        };
+        if matches!(ortho.scaling_mode, ScalingMode::WindowSize) {
+            continue;
+        }
+        let RenderTarget::Image(target) = target else {
+            continue;
+        };
+
+        let size = pixel_size.resolution_for(ortho.area.size());
+        let Some(image) = images.get(&target.handle) else {
+            continue;
+        };
+        if image.size() == size {
+            continue;
+        }
+        if let Some(image) = images.get_mut(&target.handle) {
+            image.resize(Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            });
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::{DebugStage, PixelArtPalette, PixelArtShaderParams};
+
+// ──────────────────────────────────────────────
+//  Dither preview swatch
+// ──────────────────────────────────────────────
+
+/// CPU preview of how a gradient quantizes under a set of params.
+///
+/// Runs the stylize → palette match → Bayer dither steps of `pixel_art.wgsl`
+/// on a horizontal `from` → `to` gradient, so tools and debug UIs can show the
+/// result before applying settings scene-wide. Lighting, toon banding and
+/// world-space effects (reveal, world-axis dither direction) are not simulated;
+/// each swatch pixel stands for one screen pixel of the low-res target.
+#[derive(Clone, Debug)]
+pub struct DitherPreview {
+    /// Gradient start color (left edge).
+    pub from: Color,
+    /// Gradient end color (right edge).
+    pub to: Color,
+    /// Swatch size in pixels (default: 128×16).
+    pub size: UVec2,
+}
+
+impl Default for DitherPreview {
+    fn default() -> Self {
+        Self {
+            from: Color::BLACK,
+            to: Color::WHITE,
+            size: UVec2::new(128, 16),
+        }
+    }
+}
+
+impl DitherPreview {
+    /// Gradient swatch between two colors.
+    pub fn gradient(from: Color, to: Color, size: UVec2) -> Self {
+        Self { from, to, size }
+    }
+
+    /// Quantized swatch in linear RGB, row-major.
+    pub fn pixels(&self, params: &PixelArtShaderParams, palette: &PixelArtPalette) -> Vec<Vec3> {
+        let from = linear_vec3(self.from);
+        let to = linear_vec3(self.to);
+        let width = self.size.x.max(1);
+
+        (0..self.size.y)
+            .flat_map(|y| (0..self.size.x).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let t = if width > 1 {
+                    x as f32 / (width - 1) as f32
+                } else {
+                    0.0
+                };
+                let pos = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                quantize_pixel(params, palette, from.lerp(to, t), pos)
+            })
+            .collect()
+    }
+
+    /// Quantized swatch as an sRGB image, ready for an `ImageNode` or egui texture.
+    pub fn render(&self, params: &PixelArtShaderParams, palette: &PixelArtPalette) -> Image {
+        let data = self
+            .pixels(params, palette)
+            .into_iter()
+            .flat_map(|c| Color::linear_rgb(c.x, c.y, c.z).to_srgba().to_u8_array())
+            .collect();
+
+        Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+fn linear_vec3(color: Color) -> Vec3 {
+    let c = color.to_linear();
+    Vec3::new(c.red, c.green, c.blue)
+}
+
+// ──────────────────────────────────────────────
+//  CPU mirror of the shader's quantization steps
+// ──────────────────────────────────────────────
+
+/// Quantizes one linear RGB color at screen position `pos` exactly as steps
+/// 4–5 of the fragment shader do.
+pub fn quantize_pixel(
+    params: &PixelArtShaderParams,
+    palette: &PixelArtPalette,
+    color: Vec3,
+    pos: Vec2,
+) -> Vec3 {
+    let stage = DebugStage::from_u32(params.debug_stage).unwrap_or_default();
+    let mut color = color.clamp(Vec3::ZERO, Vec3::ONE);
+    if matches!(stage, DebugStage::PbrOnly | DebugStage::Toon) {
+        return color;
+    }
+
+    if params.stylize_strength > 0.0 {
+        color = stylize(params, color).clamp(Vec3::ZERO, Vec3::ONE);
+    }
+
+    let count = params.palette_count.min(palette.count());
+    if count == 0 {
+        return color;
+    }
+
+    let Some((nearest, second, blend)) = find_palette_match(params, palette, color, count)
+    else {
+        return color;
+    };
+
+    let mut quantized = nearest;
+    if stage != DebugStage::Palette && params.dither_strength > 0.0 {
+        let threshold = bayer(params, dither_position(params, pos));
+        if threshold < blend * params.dither_strength {
+            quantized = second;
+        }
+    }
+
+    color.lerp(quantized, params.palette_strength)
+}
+
+/// Nearest palette entry to a linear RGB color, matched in CIELAB with the
+/// default weights (how overlay colors such as outlines are kept on-palette).
+pub(crate) fn nearest_palette_color(palette: &PixelArtPalette, color: Vec3) -> Vec3 {
+    let params = PixelArtShaderParams::default();
+    find_palette_match(&params, palette, color, palette.count())
+        .map_or(color, |(nearest, _, _)| nearest)
+}
+
+fn stylize(params: &PixelArtShaderParams, color: Vec3) -> Vec3 {
+    color.lerp(params.stylize_matrix * color, params.stylize_strength)
+}
+
+fn chromaticity(rgb: Vec3) -> Vec3 {
+    rgb / (rgb.x + rgb.y + rgb.z).max(0.0001)
+}
+
+fn palette_entry_allowed(params: &PixelArtShaderParams, pal_rgb: Vec3) -> bool {
+    if params.stylize_strength > 0.0 {
+        let shift = chromaticity(pal_rgb).distance(chromaticity(stylize(params, pal_rgb)));
+        return shift <= params.stylize_palette_tolerance;
+    }
+    true
+}
+
+/// Nearest and second-nearest palette entries plus the dither blend factor.
+fn find_palette_match(
+    params: &PixelArtShaderParams,
+    palette: &PixelArtPalette,
+    color: Vec3,
+    count: u32,
+) -> Option<(Vec3, Vec3, f32)> {
+    let target = to_quantization_space(params, color);
+
+    let (mut d1, mut d2) = (f32::MAX, f32::MAX);
+    let (mut c1, mut c2) = (color, color);
+
+    for entry in palette.colors.iter().take(count as usize) {
+        let pal_rgb = entry.truncate();
+        if !palette_entry_allowed(params, pal_rgb) {
+            continue;
+        }
+        let dist = palette_distance(params, target, to_quantization_space(params, pal_rgb));
+        if dist < d1 {
+            (d2, c2) = (d1, c1);
+            (d1, c1) = (dist, pal_rgb);
+        } else if dist < d2 {
+            (d2, c2) = (dist, pal_rgb);
+        }
+    }
+
+    if d1 == f32::MAX {
+        return None;
+    }
+    let total = d1 + d2;
+    let blend = if total > 0.001 { d1 / total } else { 0.0 };
+    Some((c1, c2, blend))
+}
+
+fn to_quantization_space(params: &PixelArtShaderParams, rgb: Vec3) -> Vec3 {
+    match params.quantization_space {
+        1 => linear_rgb_to_oklab(rgb),
+        2 => rgb,
+        3 => linear_to_srgb(rgb),
+        _ => linear_rgb_to_lab(rgb),
+    }
+}
+
+fn palette_distance(params: &PixelArtShaderParams, a: Vec3, b: Vec3) -> f32 {
+    let d = a - b;
+    if params.quantization_space > 1 {
+        return d.length();
+    }
+    (params.lightness_weight * d.x * d.x + params.chroma_weight * (d.y * d.y + d.z * d.z)).sqrt()
+}
+
+fn linear_rgb_to_lab(rgb: Vec3) -> Vec3 {
+    let xyz = Vec3::new(
+        Vec3::new(0.4124564, 0.3575761, 0.1804375).dot(rgb),
+        Vec3::new(0.2126729, 0.7151522, 0.0721750).dot(rgb),
+        Vec3::new(0.0193339, 0.119192, 0.9503041).dot(rgb),
+    );
+    let lab_f = |t: f32| {
+        let delta = 6.0 / 29.0;
+        if t > delta * delta * delta {
+            t.cbrt()
+        } else {
+            t / (3.0 * delta * delta) + 4.0 / 29.0
+        }
+    };
+    let scaled = xyz / Vec3::new(0.95047, 1.0, 1.08883);
+    let (fx, fy, fz) = (lab_f(scaled.x), lab_f(scaled.y), lab_f(scaled.z));
+    Vec3::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+fn linear_rgb_to_oklab(rgb: Vec3) -> Vec3 {
+    let lms = Vec3::new(
+        Vec3::new(0.41222146, 0.53633255, 0.051445995).dot(rgb),
+        Vec3::new(0.2119035, 0.6806995, 0.10739696).dot(rgb),
+        Vec3::new(0.08830246, 0.28171885, 0.6299787).dot(rgb),
+    )
+    .max(Vec3::ZERO)
+    .powf(1.0 / 3.0);
+    Vec3::new(
+        Vec3::new(0.21045426, 0.7936178, -0.004072047).dot(lms),
+        Vec3::new(1.9779985, -2.4285922, 0.4505937).dot(lms),
+        Vec3::new(0.025904037, 0.78277177, -0.80867577).dot(lms),
+    )
+}
+
+fn linear_to_srgb(rgb: Vec3) -> Vec3 {
+    let encode = |c: f32| {
+        let c = c.max(0.0);
+        if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    Vec3::new(encode(rgb.x), encode(rgb.y), encode(rgb.z))
+}
+
+/// Recursive Bayer threshold matching the shader's `bayer()`.
+fn bayer(params: &PixelArtShaderParams, pos: Vec2) -> f32 {
+    let levels = match params.bayer_size {
+        0..=2 => 1,
+        3..=7 => 2,
+        _ => 3,
+    };
+    let (x, y) = (pos.x as u32, pos.y as u32);
+    let mut index = 0;
+    for i in 0..levels {
+        let (xb, yb) = ((x >> i) & 1, (y >> i) & 1);
+        index = index * 4 + 2 * (xb ^ yb) + yb;
+    }
+    index as f32 / (1u32 << (2 * levels)) as f32
+}
+
+/// Screen-space dither cell coordinates (world-space directions fall back
+/// to their XY components).
+fn dither_position(params: &PixelArtShaderParams, pos: Vec2) -> Vec2 {
+    let mut pos = pos * params.dither_density;
+
+    if params.dither_stretch != 1.0 || params.dither_skew != 0.0 {
+        let dir = params.dither_direction.truncate();
+        if dir.length() > 1e-5 {
+            let dir = dir.normalize();
+            let perp = Vec2::new(-dir.y, dir.x);
+            let v = pos.dot(perp);
+            let u = pos.dot(dir) / params.dither_stretch.max(1e-3) + v * params.dither_skew;
+            pos = Vec2::new(u, v);
+        }
+    }
+
+    let cell = pos.floor();
+    cell - 8.0 * (cell / 8.0).floor()
+}
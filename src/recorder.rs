@@ -0,0 +1,362 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use bevy::camera::RenderTarget;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Animated file format written by `PixelArtRecorder`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum RecordingFormat {
+    #[default]
+    Gif,
+    /// Animated PNG (indexed, same palette cap as the GIF).
+    Apng,
+}
+
+/// Records the camera's low-res render target to an animated GIF/APNG at
+/// native resolution.
+///
+/// Put it on a low-res camera rendering to an 8-bit RGBA image
+/// (`lowres_target(.., false)`). Every `frame_step` frames the target is read
+/// back until `frames` are captured; the clip is then encoded on the IO task
+/// pool with a global palette of at most `max_colors` entries (the most
+/// frequent colors; rarer ones map to their nearest kept color), a
+/// `RecordingFinished` message is written and the component removes itself.
+/// Pixels with alpha below 0.5 become transparent.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtRecorder {
+    /// Output file.
+    pub path: PathBuf,
+    /// Number of frames to capture (default: 120).
+    pub frames: u32,
+    /// Capture every n-th rendered frame (default: 2, 30 fps at 60 Hz).
+    pub frame_step: u32,
+    /// Output format (default: GIF).
+    pub format: RecordingFormat,
+    /// Palette size cap, transparency included (2..=256, default: 256).
+    pub max_colors: u16,
+    /// Loop forever (default: true); otherwise play once.
+    pub looping: bool,
+    #[reflect(ignore)]
+    state: RecorderState,
+}
+
+#[derive(Clone, Debug, Default)]
+struct RecorderState {
+    requested: u32,
+    frames_until_capture: u32,
+    last_capture: Option<f32>,
+    size: UVec2,
+    frames: Vec<Option<CapturedFrame>>,
+}
+
+#[derive(Clone, Debug)]
+struct CapturedFrame {
+    rgba: Vec<u8>,
+    delay_secs: f32,
+}
+
+impl PixelArtRecorder {
+    /// Record 120 frames to `path` as a GIF.
+    pub fn gif(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            frames: 120,
+            frame_step: 2,
+            format: RecordingFormat::Gif,
+            max_colors: 256,
+            looping: true,
+            state: RecorderState::default(),
+        }
+    }
+
+    /// Record 120 frames to `path` as an APNG.
+    pub fn apng(path: impl Into<PathBuf>) -> Self {
+        Self {
+            format: RecordingFormat::Apng,
+            ..Self::gif(path)
+        }
+    }
+
+    /// Set the number of frames to capture.
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Frames captured so far.
+    pub fn captured(&self) -> usize {
+        self.state.frames.iter().flatten().count()
+    }
+}
+
+/// A `PixelArtRecorder` finished writing `path` (`error` is set on failure).
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct RecordingFinished {
+    pub path: PathBuf,
+    pub error: Option<String>,
+}
+
+/// Encodes still running on the IO task pool.
+#[derive(Resource, Default)]
+pub struct PendingRecordings(Vec<(PathBuf, Task<Result<(), String>>)>);
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Reads back each recording camera's target every `frame_step` frames.
+pub fn capture_recording_frames(
+    mut commands: Commands,
+    time: Res<Time>,
+    images: Res<Assets<Image>>,
+    mut cameras: Query<(Entity, &mut PixelArtRecorder, &RenderTarget)>,
+) {
+    for (camera, mut recorder, target) in &mut cameras {
+        let RenderTarget::Image(target) = target else {
+            continue;
+        };
+        if recorder.state.requested >= recorder.frames {
+            continue;
+        }
+        if recorder.state.frames_until_capture > 0 {
+            recorder.state.frames_until_capture -= 1;
+            continue;
+        }
+        let Some(image) = images.get(&target.handle) else {
+            continue;
+        };
+
+        let now = time.elapsed_secs();
+        let delay_secs = recorder
+            .state
+            .last_capture
+            .map_or(time.delta_secs() * recorder.frame_step as f32, |last| {
+                now - last
+            });
+        let index = recorder.state.requested as usize;
+        let frame_step = recorder.frame_step;
+        let state = &mut recorder.state;
+        state.last_capture = Some(now);
+        state.frames_until_capture = frame_step.saturating_sub(1);
+        state.requested += 1;
+        state.size = image.size();
+        state.frames.push(None);
+
+        commands
+            .spawn(Readback::texture(target.handle.clone()))
+            .observe(
+                move |event: On<ReadbackComplete>,
+                      mut commands: Commands,
+                      mut cameras: Query<&mut PixelArtRecorder>| {
+                    if let Ok(mut recorder) = cameras.get_mut(camera) {
+                        let size = recorder.state.size;
+                        recorder.state.frames[index] = Some(CapturedFrame {
+                            rgba: strip_row_padding(&event.data, size),
+                            delay_secs,
+                        });
+                    }
+                    commands.entity(event.entity).despawn();
+                },
+            );
+    }
+}
+
+/// Starts encoding finished recordings on the IO task pool, removes their
+/// `PixelArtRecorder`, and reports completed encodes.
+pub fn finish_recordings(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut PixelArtRecorder)>,
+    mut pending: ResMut<PendingRecordings>,
+    mut finished: MessageWriter<RecordingFinished>,
+) {
+    for (camera, mut recorder) in &mut cameras {
+        if recorder.state.requested < recorder.frames
+            || recorder.captured() < recorder.frames as usize
+        {
+            continue;
+        }
+        let state = std::mem::take(&mut recorder.state);
+        let frames: Vec<_> = state.frames.into_iter().flatten().collect();
+        let path = recorder.path.clone();
+        let (format, max_colors, looping) =
+            (recorder.format, recorder.max_colors, recorder.looping);
+        commands.entity(camera).remove::<PixelArtRecorder>();
+
+        let task = IoTaskPool::get().spawn({
+            let path = path.clone();
+            async move { encode(&path, format, state.size, &frames, max_colors, looping) }
+        });
+        pending.0.push((path, task));
+    }
+
+    pending.0.retain_mut(|(path, task)| {
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return true;
+        };
+        match &result {
+            Ok(()) => info!("PixelArtRecorder: wrote {}", path.display()),
+            Err(error) => error!("PixelArtRecorder: {}: {error}", path.display()),
+        }
+        finished.write(RecordingFinished {
+            path: path.clone(),
+            error: result.err(),
+        });
+        false
+    });
+}
+
+// ──────────────────────────────────────────────
+//  Encoding
+// ──────────────────────────────────────────────
+
+/// Tightly packed RGBA8 rows from readback data padded to the copy alignment.
+fn strip_row_padding(data: &[u8], size: UVec2) -> Vec<u8> {
+    let row = size.x as usize * 4;
+    let stride = data.len() / (size.y as usize).max(1);
+    if stride == row {
+        return data.to_vec();
+    }
+    data.chunks(stride)
+        .take(size.y as usize)
+        .flat_map(|chunk| &chunk[..row.min(chunk.len())])
+        .copied()
+        .collect()
+}
+
+/// Global palette of at most `max_colors` entries (index 0 transparent when
+/// any pixel is) and each frame as palette indices.
+fn quantize(frames: &[CapturedFrame], max_colors: u16) -> (Vec<[u8; 3]>, Option<u8>, Vec<Vec<u8>>) {
+    let max_colors = max_colors.clamp(2, 256) as usize;
+    let opaque = |px: &[u8]| px[3] >= 128;
+
+    let mut counts: HashMap<[u8; 3], u32> = HashMap::default();
+    let mut transparent = false;
+    for frame in frames {
+        for px in frame.rgba.chunks_exact(4) {
+            if opaque(px) {
+                *counts.entry([px[0], px[1], px[2]]).or_default() += 1;
+            } else {
+                transparent = true;
+            }
+        }
+    }
+
+    let mut colors: Vec<_> = counts.into_iter().collect();
+    colors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let keep = max_colors - transparent as usize;
+    let mut palette: Vec<[u8; 3]> = Vec::with_capacity(max_colors);
+    if transparent {
+        palette.push([0, 0, 0]);
+    }
+    palette.extend(colors.iter().take(keep).map(|(c, _)| *c));
+
+    let offset = transparent as usize;
+    let mut lookup: HashMap<[u8; 3], u8> = palette[offset..]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (*c, (i + offset) as u8))
+        .collect();
+    for (color, _) in colors.iter().skip(keep) {
+        let nearest = palette[offset..]
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, p)| {
+                (0..3)
+                    .map(|i| (p[i] as i32 - color[i] as i32).pow(2))
+                    .sum::<i32>()
+            })
+            .map_or(0, |(i, _)| i + offset);
+        lookup.insert(*color, nearest as u8);
+    }
+
+    let indexed = frames
+        .iter()
+        .map(|frame| {
+            frame
+                .rgba
+                .chunks_exact(4)
+                .map(|px| {
+                    if opaque(px) {
+                        lookup[&[px[0], px[1], px[2]]]
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    (palette, transparent.then_some(0), indexed)
+}
+
+fn encode(
+    path: &std::path::Path,
+    format: RecordingFormat,
+    size: UVec2,
+    frames: &[CapturedFrame],
+    max_colors: u16,
+    looping: bool,
+) -> Result<(), String> {
+    if frames.is_empty() {
+        return Err("no frames captured".into());
+    }
+    let (palette, transparent, indexed) = quantize(frames, max_colors);
+    let file = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+    let (width, height) = (size.x as u16, size.y as u16);
+
+    match format {
+        RecordingFormat::Gif => {
+            let flat: Vec<u8> = palette.iter().flatten().copied().collect();
+            let mut encoder =
+                gif::Encoder::new(file, width, height, &flat).map_err(|e| e.to_string())?;
+            let repeat = if looping {
+                gif::Repeat::Infinite
+            } else {
+                gif::Repeat::Finite(0)
+            };
+            encoder.set_repeat(repeat).map_err(|e| e.to_string())?;
+            for (frame, indices) in frames.iter().zip(&indexed) {
+                let mut out =
+                    gif::Frame::from_indexed_pixels(width, height, indices.clone(), transparent);
+                out.delay = (frame.delay_secs * 100.0).round().max(1.0) as u16;
+                out.dispose = gif::DisposalMethod::Background;
+                encoder.write_frame(&out).map_err(|e| e.to_string())?;
+            }
+        }
+        RecordingFormat::Apng => {
+            let mut encoder = png::Encoder::new(file, size.x, size.y);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(palette.iter().flatten().copied().collect::<Vec<u8>>());
+            if transparent.is_some() {
+                encoder.set_trns(vec![0u8]);
+            }
+            encoder
+                .set_animated(frames.len() as u32, if looping { 0 } else { 1 })
+                .map_err(|e| e.to_string())?;
+            let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+            for (frame, indices) in frames.iter().zip(&indexed) {
+                let millis = (frame.delay_secs * 1000.0)
+                    .round()
+                    .clamp(1.0, u16::MAX as f32);
+                writer
+                    .set_frame_delay(millis as u16, 1000)
+                    .map_err(|e| e.to_string())?;
+                writer
+                    .write_image_data(indices)
+                    .map_err(|e| e.to_string())?;
+            }
+            writer.finish().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
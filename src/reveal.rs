@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::{PaletteTransitionCompleted, PixelArtMaterials};
+
+// ──────────────────────────────────────────────
+//  Public resource
+// ──────────────────────────────────────────────
+
+/// Scene-wide progressive palette reveal.
+///
+/// While this resource exists, every `PixelArtMaterial` is limited to the
+/// first `count` palette entries (at least the first one, so a count of 0
+/// shows the world in the first palette color). The limit can grow over time (`target` /
+/// `speed`) and can be lifted inside a world-space sphere around `origin`
+/// whose radius grows at `radius_speed` — e.g. a grayscale world that gains
+/// colors as the player restores it. Materials are updated in place, never
+/// swapped. Order the palette so the earliest entries are the ones to show
+/// first.
+#[derive(Resource, Clone, Debug)]
+pub struct PaletteReveal {
+    /// Current number of revealed palette entries (fractional while animating).
+    pub count: f32,
+    /// Entry count `count` animates toward.
+    pub target: f32,
+    /// Entries revealed per second (0 = jump straight to `target`).
+    pub speed: f32,
+    /// World-space center of the fully revealed zone.
+    pub origin: Vec3,
+    /// Radius of the fully revealed zone (0 = limit applies everywhere).
+    pub radius: f32,
+    /// Growth of `radius` in world units per second.
+    pub radius_speed: f32,
+}
+
+impl Default for PaletteReveal {
+    fn default() -> Self {
+        Self {
+            count: 0.0,
+            target: 0.0,
+            speed: 0.0,
+            origin: Vec3::ZERO,
+            radius: 0.0,
+            radius_speed: 0.0,
+        }
+    }
+}
+
+impl PaletteReveal {
+    /// Reveal palette entries from `from` to `to` at `speed` entries per second.
+    pub fn over_time(from: u32, to: u32, speed: f32) -> Self {
+        Self {
+            count: from as f32,
+            target: to as f32,
+            speed,
+            ..default()
+        }
+    }
+
+    /// Limit the scene to `count` entries except inside a sphere around
+    /// `origin` that expands at `radius_speed` world units per second.
+    pub fn from_point(count: u32, origin: Vec3, radius_speed: f32) -> Self {
+        Self {
+            count: count as f32,
+            target: count as f32,
+            origin,
+            radius_speed,
+            ..default()
+        }
+    }
+
+    /// Whether the count has reached its target.
+    pub fn is_finished(&self) -> bool {
+        self.count == self.target
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Advances the reveal animation and writes the limit into every material
+/// whose parameters are out of date. Emits `PaletteTransitionCompleted` when
+/// the count reaches its target.
+pub fn update_palette_reveal(
+    time: Res<Time>,
+    mut reveal: ResMut<PaletteReveal>,
+    mut materials: PixelArtMaterials,
+    mut completed: MessageWriter<PaletteTransitionCompleted>,
+) {
+    let dt = time.delta_secs();
+    if !reveal.is_finished() {
+        let step = if reveal.speed > 0.0 {
+            reveal.speed * dt
+        } else {
+            f32::INFINITY
+        };
+        let delta = (reveal.target - reveal.count).clamp(-step, step);
+        reveal.count += delta;
+        if reveal.is_finished() {
+            completed.write(PaletteTransitionCompleted {
+                count: reveal.count.max(0.0).floor() as u32,
+            });
+        }
+    }
+    if reveal.radius_speed != 0.0 {
+        reveal.radius = (reveal.radius + reveal.radius_speed * dt).max(0.0);
+    }
+
+    let reveal_count = reveal.count.max(0.0).floor() as u32;
+    materials.update_all(|p| {
+        p.reveal_count = reveal_count;
+        p.reveal_origin = reveal.origin;
+        p.reveal_radius = reveal.radius;
+    });
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+
+use crate::LowResPixelArtCamera;
+
+// ──────────────────────────────────────────────
+//  Public API
+// ──────────────────────────────────────────────
+
+/// Saves the low-res render target `image` to `path` at its native
+/// resolution (e.g. a 320×180 PNG), pixel-exact rather than a capture of the
+/// upscaled window. The format follows the file extension. The target must be
+/// an 8-bit format (`lowres_target(.., false)`); alpha is dropped.
+pub fn capture_lowres_screenshot(
+    commands: &mut Commands,
+    image: Handle<Image>,
+    path: impl Into<PathBuf>,
+) {
+    commands
+        .spawn(Screenshot::image(image))
+        .observe(save_to_disk(path.into()));
+}
+
+/// Message requesting a native-resolution screenshot of the first
+/// `LowResPixelArtCamera`'s render target (see `capture_lowres_screenshot`).
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct CaptureLowResScreenshot {
+    pub path: PathBuf,
+}
+
+impl CaptureLowResScreenshot {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Handles `CaptureLowResScreenshot` messages.
+pub fn capture_lowres_screenshots(
+    mut commands: Commands,
+    mut requests: MessageReader<CaptureLowResScreenshot>,
+    cameras: Query<&RenderTarget, With<LowResPixelArtCamera>>,
+) {
+    for request in requests.read() {
+        let Some(image) = cameras.iter().find_map(|target| match target {
+            RenderTarget::Image(target) => Some(target.handle.clone()),
+            _ => None,
+        }) else {
+            warn!(
+                "CaptureLowResScreenshot: no LowResPixelArtCamera renders to an image; {} not written",
+                request.path.display()
+            );
+            continue;
+        };
+        capture_lowres_screenshot(&mut commands, image, request.path.clone());
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::{PixelArtMaterial, PixelArtMaterials, PixelArtShaderParams};
+
+// ──────────────────────────────────────────────
+//  Public resource
+// ──────────────────────────────────────────────
+
+/// Optional scene-wide toon, dither and palette settings.
+///
+/// While present, its values are written into every `PixelArtMaterial`
+/// whenever it changes, and into materials added later, so a settings menu or
+/// debug panel edits one resource instead of looping over material assets.
+/// Materials with `PixelArtExtension::ignore_settings` keep their own values.
+/// `palette_count` is owned by the palette (see `SharedPalette`) and is not
+/// part of the settings.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PixelArtSettings {
+    /// See `PixelArtShaderParams::toon_bands`.
+    pub toon_bands: f32,
+    /// See `PixelArtShaderParams::toon_softness`.
+    pub toon_softness: f32,
+    /// See `PixelArtShaderParams::toon_shadow_floor`.
+    pub toon_shadow_floor: f32,
+    /// See `PixelArtShaderParams::dither_density`.
+    pub dither_density: f32,
+    /// See `PixelArtShaderParams::dither_strength`.
+    pub dither_strength: f32,
+    /// See `PixelArtShaderParams::bayer_size`.
+    pub bayer_size: u32,
+    /// See `PixelArtShaderParams::palette_strength`.
+    pub palette_strength: f32,
+}
+
+impl Default for PixelArtSettings {
+    fn default() -> Self {
+        Self::from_params(&PixelArtShaderParams::default())
+    }
+}
+
+impl PixelArtSettings {
+    /// Settings matching `params` (e.g. to seed the resource from a material).
+    pub fn from_params(params: &PixelArtShaderParams) -> Self {
+        Self {
+            toon_bands: params.toon_bands,
+            toon_softness: params.toon_softness,
+            toon_shadow_floor: params.toon_shadow_floor,
+            dither_density: params.dither_density,
+            dither_strength: params.dither_strength,
+            bayer_size: params.bayer_size,
+            palette_strength: params.palette_strength,
+        }
+    }
+
+    /// Write the settings into `params`.
+    pub fn apply(&self, params: &mut PixelArtShaderParams) {
+        params.toon_bands = self.toon_bands;
+        params.toon_softness = self.toon_softness;
+        params.toon_shadow_floor = self.toon_shadow_floor;
+        params.dither_density = self.dither_density;
+        params.dither_strength = self.dither_strength;
+        params.bayer_size = self.bayer_size;
+        params.palette_strength = self.palette_strength;
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Pushes `PixelArtSettings` to every material when it changes, and to newly
+/// added materials.
+pub fn sync_pixel_art_settings(
+    settings: Res<PixelArtSettings>,
+    mut events: MessageReader<AssetEvent<PixelArtMaterial>>,
+    mut materials: PixelArtMaterials,
+) {
+    if settings.is_changed() {
+        events.clear();
+        materials.update_where(|ext| !ext.ignore_settings, |params| settings.apply(params));
+        return;
+    }
+
+    for event in events.read() {
+        if let AssetEvent::Added { id } = event
+            && materials
+                .extension(*id)
+                .is_some_and(|ext| !ext.ignore_settings)
+        {
+            materials.update(*id, |params| settings.apply(params));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Trauma-based camera shake in whole low-res texels, for the low-res camera.
+///
+/// Sub-texel shake only blurs edges at 320×180, so the offset along the
+/// camera's right/up axes is rounded to whole texels (of the orthographic
+/// area, or of the view plane at `focus_distance` for perspective cameras).
+/// The shake is `trauma²` times `max_offset`, and trauma decays over time;
+/// call `add_trauma` on hits. `mirror` (typically the full-res camera) gets
+/// the same world offset, so both layers shake together. Like
+/// `SnapToPixelGrid`, the offset is applied before transform propagation and
+/// removed at the start of the next frame.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PixelShake {
+    /// Current trauma (0..=1).
+    pub trauma: f32,
+    /// Trauma lost per second (default: 1.0).
+    pub decay: f32,
+    /// Largest offset in texels at full trauma (default: 4.0).
+    pub max_offset: f32,
+    /// Shake speed in oscillations per second (default: 15.0).
+    pub frequency: f32,
+    /// Distance of the reference plane for perspective cameras (default: 10.0).
+    pub focus_distance: f32,
+    /// Camera moved by the same world offset (not a child of this one).
+    pub mirror: Option<Entity>,
+    /// Seconds of shake time, for the noise.
+    #[reflect(ignore)]
+    elapsed: f32,
+    /// World offset applied last frame and the resulting translations of the
+    /// camera and its mirror, to remove next frame.
+    #[reflect(ignore)]
+    applied: Option<(Vec3, Vec3, Option<Vec3>)>,
+}
+
+impl Default for PixelShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.0,
+            max_offset: 4.0,
+            frequency: 15.0,
+            focus_distance: 10.0,
+            mirror: None,
+            elapsed: 0.0,
+            applied: None,
+        }
+    }
+}
+
+impl PixelShake {
+    /// Shake that also moves `mirror` (e.g. the full-res camera).
+    pub fn mirrored(mirror: Entity) -> Self {
+        Self {
+            mirror: Some(mirror),
+            ..default()
+        }
+    }
+
+    /// Add `amount` of trauma (clamped to 1).
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Offset in texels for the current trauma, rounded to whole texels.
+    pub fn texel_offset(&self) -> Vec2 {
+        let amount = self.trauma * self.trauma * self.max_offset;
+        let t = self.elapsed * self.frequency;
+        // Two incommensurate sines per axis: smooth, non-repeating wobble.
+        let noise = |seed: f32| 0.6 * (t * 1.3 + seed).sin() + 0.4 * (t * 2.9 + seed * 1.7).sin();
+        (Vec2::new(noise(0.0), noise(17.0)) * amount).round()
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Removes last frame's shake offset from the camera and its mirror, unless
+/// something else wrote their transforms since.
+pub fn restore_pixel_shake(
+    mut cameras: Query<(&mut PixelShake, &mut Transform)>,
+    mut mirrors: Query<&mut Transform, Without<PixelShake>>,
+) {
+    for (mut shake, mut transform) in &mut cameras {
+        let Some((offset, shaken, mirror_shaken)) = shake.applied.take() else {
+            continue;
+        };
+        if transform.translation == shaken {
+            transform.bypass_change_detection().translation -= offset;
+        }
+        if let (Some(mirror), Some(mirror_shaken)) = (shake.mirror, mirror_shaken)
+            && let Ok(mut mirror) = mirrors.get_mut(mirror)
+            && mirror.translation == mirror_shaken
+        {
+            mirror.bypass_change_detection().translation -= offset;
+        }
+    }
+}
+
+/// Decays trauma and offsets every `PixelShake` camera (and its mirror) by
+/// whole texels. Runs before transform propagation.
+pub fn apply_pixel_shake(
+    time: Res<Time>,
+    mut cameras: Query<(&mut PixelShake, &mut Transform, &Projection, &Camera)>,
+    mut mirrors: Query<&mut Transform, Without<PixelShake>>,
+) {
+    let dt = time.delta_secs();
+    for (mut shake, mut transform, projection, camera) in &mut cameras {
+        shake.trauma = (shake.trauma - shake.decay * dt).max(0.0);
+        if shake.trauma <= 0.0 {
+            continue;
+        }
+        shake.elapsed += dt;
+
+        let Some(target) = camera.physical_target_size() else {
+            continue;
+        };
+        let height = target.y.max(1) as f32;
+        let texel = match projection {
+            Projection::Orthographic(ortho) => ortho.area.height() / height,
+            Projection::Perspective(persp) => {
+                2.0 * shake.focus_distance * (persp.fov * 0.5).tan() / height
+            }
+            _ => continue,
+        };
+        let texels = shake.texel_offset();
+        if texels == Vec2::ZERO || texel <= 0.0 {
+            continue;
+        }
+
+        let right = transform.rotation * Vec3::X;
+        let up = transform.rotation * Vec3::Y;
+        let offset = (right * texels.x + up * texels.y) * texel;
+        transform.translation += offset;
+
+        let mirror_shaken = shake
+            .mirror
+            .and_then(|mirror| mirrors.get_mut(mirror).ok())
+            .map(|mut mirror| {
+                mirror.translation += offset;
+                mirror.translation
+            });
+        shake.applied = Some((offset, transform.translation, mirror_shaken));
+    }
+}
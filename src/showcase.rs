@@ -0,0 +1,442 @@
+//! Reusable demo scenes.
+//!
+//! Scene builders shared by the crate's examples, so each feature is shown
+//! through the same code a game would write instead of one monolithic demo.
+//! Every scene spawns under a single root entity and returns it.
+
+use bevy::camera::RenderTarget;
+use bevy::ecs::system::SystemParam;
+use bevy::pbr::ExtendedMaterial;
+use bevy::prelude::*;
+
+use crate::{
+    HoldoutExtension, HoldoutMaterial, LowResPixelArtCamera, PixelArtCompositor, PixelArtExtension,
+    PixelArtLayers, PixelArtMaterial, PixelArtShaderParams, lowres_target,
+};
+
+// ──────────────────────────────────────────────
+//  Cameras
+// ──────────────────────────────────────────────
+
+/// Low-res camera spawned by `Showcase::spawn_cameras`; follows the window camera.
+#[derive(Component)]
+pub struct ShowcaseLowResCamera;
+
+/// Full-res window camera spawned by `Showcase::spawn_cameras`.
+#[derive(Component)]
+pub struct ShowcaseWindowCamera {
+    /// Point the camera orbits around.
+    pub focus: Vec3,
+    /// Orbit speed in radians per second (0 = static).
+    pub orbit_speed: f32,
+}
+
+/// Entities and target image of the showcase camera pair.
+#[derive(Clone, Debug)]
+pub struct ShowcaseCameras {
+    pub low_res: Entity,
+    pub window: Entity,
+    pub image: Handle<Image>,
+}
+
+/// Keeps showcase cameras in sync and slowly orbits the window camera.
+pub struct ShowcasePlugin;
+
+impl Plugin for ShowcasePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (orbit_showcase_camera, sync_showcase_cameras).chain(),
+        );
+    }
+}
+
+fn orbit_showcase_camera(
+    time: Res<Time>,
+    mut cameras: Query<(&ShowcaseWindowCamera, &mut Transform)>,
+) {
+    for (orbit, mut transform) in &mut cameras {
+        if orbit.orbit_speed == 0.0 {
+            continue;
+        }
+        let rotation = Quat::from_rotation_y(orbit.orbit_speed * time.delta_secs());
+        transform.rotate_around(orbit.focus, rotation);
+        transform.look_at(orbit.focus, Vec3::Y);
+    }
+}
+
+fn sync_showcase_cameras(
+    window: Query<&Transform, (With<ShowcaseWindowCamera>, Changed<Transform>)>,
+    mut low_res: Query<&mut Transform, (With<ShowcaseLowResCamera>, Without<ShowcaseWindowCamera>)>,
+) {
+    let Ok(window) = window.single() else {
+        return;
+    };
+    for mut transform in &mut low_res {
+        *transform = *window;
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Scene builder
+// ──────────────────────────────────────────────
+
+/// System param with everything the showcase scenes spawn.
+#[derive(SystemParam)]
+pub struct Showcase<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub pixel_materials: ResMut<'w, Assets<PixelArtMaterial>>,
+    pub holdout_materials: ResMut<'w, Assets<HoldoutMaterial>>,
+    pub std_materials: ResMut<'w, Assets<StandardMaterial>>,
+    pub images: ResMut<'w, Assets<Image>>,
+    pub layers: Res<'w, PixelArtLayers>,
+}
+
+impl Showcase<'_, '_> {
+    /// Matte pixel art material with the default palette.
+    pub fn pixel_material(&mut self, color: Color) -> Handle<PixelArtMaterial> {
+        self.pixel_material_with(color, PixelArtShaderParams::default())
+    }
+
+    /// Matte pixel art material from `params`, tinted `color`.
+    pub fn pixel_material_with(
+        &mut self,
+        color: Color,
+        params: PixelArtShaderParams,
+    ) -> Handle<PixelArtMaterial> {
+        self.pixel_materials.add(ExtendedMaterial {
+            base: StandardMaterial {
+                perceptual_roughness: 1.0,
+                reflectance: 0.0,
+                ..default()
+            },
+            extension: PixelArtExtension {
+                params: PixelArtShaderParams {
+                    base_tint: color.to_linear().to_vec4(),
+                    ..params
+                },
+                ..default()
+            },
+        })
+    }
+
+    /// Holdout material: occludes pixel art with full-res geometry.
+    pub fn holdout_material(&mut self) -> Handle<HoldoutMaterial> {
+        self.holdout_materials.add(ExtendedMaterial {
+            base: StandardMaterial::default(),
+            extension: HoldoutExtension::default(),
+        })
+    }
+
+    /// Low-res camera rendering to a `resolution` image, plus the window camera
+    /// compositing it over the full-res scene. Both start at `transform`; the
+    /// window camera orbits `focus` at `orbit_speed` rad/s.
+    pub fn spawn_cameras(
+        &mut self,
+        resolution: UVec2,
+        transform: Transform,
+        focus: Vec3,
+        orbit_speed: f32,
+    ) -> ShowcaseCameras {
+        let image = self.images.add(lowres_target(resolution, false));
+
+        let low_res = self
+            .commands
+            .spawn((
+                Name::new("Showcase Low-Res Camera"),
+                Camera3d::default(),
+                Camera {
+                    order: -1,
+                    clear_color: Color::NONE.into(),
+                    ..default()
+                },
+                RenderTarget::Image(image.clone().into()),
+                Msaa::Off,
+                transform,
+                self.layers.pixel_layers(),
+                LowResPixelArtCamera,
+                ShowcaseLowResCamera,
+            ))
+            .id();
+
+        let window = self
+            .commands
+            .spawn((
+                Name::new("Showcase Window Camera"),
+                Camera3d::default(),
+                Camera {
+                    order: 0,
+                    clear_color: Color::srgb(0.15, 0.15, 0.18).into(),
+                    ..default()
+                },
+                Msaa::Off,
+                transform,
+                self.layers.full_res_layers(),
+                PixelArtCompositor {
+                    lowres_image: image.clone(),
+                    ..default()
+                },
+                ShowcaseWindowCamera { focus, orbit_speed },
+            ))
+            .id();
+
+        ShowcaseCameras {
+            low_res,
+            window,
+            image,
+        }
+    }
+
+    /// Directional light on both layers.
+    pub fn spawn_sun(&mut self, illuminance: f32) -> Entity {
+        self.commands
+            .spawn((
+                Name::new("Sun"),
+                DirectionalLight {
+                    illuminance,
+                    shadows_enabled: true,
+                    ..default()
+                },
+                Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.5, 0.0)),
+                self.layers.both(),
+            ))
+            .id()
+    }
+
+    /// Full-res ground plane with a matching holdout so pixel art is occluded
+    /// by it and sits on it.
+    fn spawn_ground(&mut self, root: Entity, size: f32, color: Color) {
+        let mesh = self.meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(size)));
+        let ground = self.std_materials.add(StandardMaterial {
+            base_color: color,
+            perceptual_roughness: 1.0,
+            ..default()
+        });
+        let holdout = self.holdout_material();
+        let (full_res, pixel) = (self.layers.full_res_layers(), self.layers.pixel_layers());
+
+        self.commands.entity(root).with_children(|root| {
+            root.spawn((
+                Name::new("Ground"),
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(ground),
+                full_res,
+            ));
+            root.spawn((
+                Name::new("Ground Holdout"),
+                Mesh3d(mesh),
+                MeshMaterial3d(holdout),
+                pixel,
+            ));
+        });
+    }
+
+    /// Blocky character (body, head, arms, legs) on a ground plane — shows
+    /// toon bands, rim light and holdout occlusion.
+    pub fn spawn_character_scene(&mut self, origin: Vec3) -> Entity {
+        let root = self
+            .commands
+            .spawn((
+                Name::new("Character Scene"),
+                Transform::from_translation(origin),
+                Visibility::default(),
+            ))
+            .id();
+        self.spawn_ground(root, 8.0, Color::srgb(0.3, 0.32, 0.3));
+
+        let body = self.meshes.add(Capsule3d::new(0.45, 0.9));
+        let head = self.meshes.add(Sphere::new(0.4).mesh().ico(4).unwrap());
+        let limb = self.meshes.add(Cuboid::new(0.22, 0.8, 0.22));
+        let pillar = self.meshes.add(Cylinder::new(0.3, 3.0));
+
+        let outfit = self.pixel_material_with(
+            Color::srgb(0.2, 0.35, 0.8),
+            PixelArtShaderParams {
+                rim_strength: 1.0,
+                rim_width: 0.15,
+                ..default()
+            },
+        );
+        let skin = self.pixel_material(Color::srgb(0.95, 0.75, 0.6));
+        let pillar_mat = self.std_materials.add(Color::srgb(0.55, 0.5, 0.45));
+        let holdout = self.holdout_material();
+        let (full_res, pixel) = (self.layers.full_res_layers(), self.layers.pixel_layers());
+
+        self.commands.entity(root).with_children(|root| {
+            root.spawn((
+                Mesh3d(body),
+                MeshMaterial3d(outfit.clone()),
+                Transform::from_xyz(0.0, 1.35, 0.0),
+                pixel.clone(),
+            ));
+            root.spawn((
+                Mesh3d(head),
+                MeshMaterial3d(skin.clone()),
+                Transform::from_xyz(0.0, 2.35, 0.0),
+                pixel.clone(),
+            ));
+            for x in [-0.65, 0.65] {
+                root.spawn((
+                    Mesh3d(limb.clone()),
+                    MeshMaterial3d(skin.clone()),
+                    Transform::from_xyz(x, 1.5, 0.0),
+                    pixel.clone(),
+                ));
+            }
+            for x in [-0.2, 0.2] {
+                root.spawn((
+                    Mesh3d(limb.clone()),
+                    MeshMaterial3d(outfit.clone()),
+                    Transform::from_xyz(x, 0.4, 0.0),
+                    pixel.clone(),
+                ));
+            }
+
+            // Full-res pillar in front of the character, cut out of the pixel layer.
+            let pillar_tf = Transform::from_xyz(1.2, 1.5, 1.2);
+            root.spawn((
+                Mesh3d(pillar.clone()),
+                MeshMaterial3d(pillar_mat),
+                pillar_tf,
+                full_res,
+            ));
+            root.spawn((Mesh3d(pillar), MeshMaterial3d(holdout), pillar_tf, pixel));
+        });
+        root
+    }
+
+    /// Walled room lit by banded torch point lights — shows per-light bands
+    /// and dithered shadow edges.
+    pub fn spawn_dungeon_scene(&mut self, origin: Vec3) -> Entity {
+        let root = self
+            .commands
+            .spawn((
+                Name::new("Dungeon Scene"),
+                Transform::from_translation(origin),
+                Visibility::default(),
+            ))
+            .id();
+
+        let params = PixelArtShaderParams {
+            point_light_bands: 4.0,
+            shadow_edge: crate::ShadowEdge::Dithered as u32,
+            ..default()
+        };
+        let floor_mesh = self.meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(6.0)));
+        let wall_mesh = self.meshes.add(Cuboid::new(12.0, 3.0, 0.5));
+        let pillar_mesh = self.meshes.add(Cuboid::new(0.8, 3.0, 0.8));
+        let crate_mesh = self.meshes.add(Cuboid::new(0.9, 0.9, 0.9));
+        let floor = self.pixel_material_with(Color::srgb(0.35, 0.3, 0.28), params.clone());
+        let stone = self.pixel_material_with(Color::srgb(0.5, 0.5, 0.55), params.clone());
+        let wood = self.pixel_material_with(Color::srgb(0.55, 0.35, 0.15), params);
+        let pixel = self.layers.pixel_layers();
+        let both = self.layers.both();
+
+        self.commands.entity(root).with_children(|root| {
+            root.spawn((Mesh3d(floor_mesh), MeshMaterial3d(floor), pixel.clone()));
+            for (pos, rot) in [
+                (Vec3::new(0.0, 1.5, -6.0), 0.0),
+                (Vec3::new(-6.0, 1.5, 0.0), std::f32::consts::FRAC_PI_2),
+                (Vec3::new(6.0, 1.5, 0.0), std::f32::consts::FRAC_PI_2),
+            ] {
+                root.spawn((
+                    Mesh3d(wall_mesh.clone()),
+                    MeshMaterial3d(stone.clone()),
+                    Transform::from_translation(pos).with_rotation(Quat::from_rotation_y(rot)),
+                    pixel.clone(),
+                ));
+            }
+            for x in [-3.0, 3.0] {
+                root.spawn((
+                    Mesh3d(pillar_mesh.clone()),
+                    MeshMaterial3d(stone.clone()),
+                    Transform::from_xyz(x, 1.5, -2.0),
+                    pixel.clone(),
+                ));
+            }
+            for pos in [
+                Vec3::new(-1.5, 0.45, 1.0),
+                Vec3::new(-0.6, 0.45, 1.6),
+                Vec3::new(-1.1, 1.35, 1.2),
+            ] {
+                root.spawn((
+                    Mesh3d(crate_mesh.clone()),
+                    MeshMaterial3d(wood.clone()),
+                    Transform::from_translation(pos),
+                    pixel.clone(),
+                ));
+            }
+            for x in [-4.5, 4.5] {
+                root.spawn((
+                    Name::new("Torch"),
+                    PointLight {
+                        color: Color::srgb(1.0, 0.6, 0.25),
+                        intensity: 200_000.0,
+                        range: 8.0,
+                        shadows_enabled: true,
+                        ..default()
+                    },
+                    Transform::from_xyz(x, 2.2, -5.2),
+                    both.clone(),
+                ));
+            }
+        });
+        root
+    }
+
+    /// Full-res terrain with pixel art trees and rocks — shows the hybrid
+    /// full-res/low-res composite.
+    pub fn spawn_outdoor_scene(&mut self, origin: Vec3) -> Entity {
+        let root = self
+            .commands
+            .spawn((
+                Name::new("Outdoor Scene"),
+                Transform::from_translation(origin),
+                Visibility::default(),
+            ))
+            .id();
+        self.spawn_ground(root, 20.0, Color::srgb(0.3, 0.5, 0.25));
+
+        let trunk_mesh = self.meshes.add(Cylinder::new(0.18, 1.2));
+        let crown_mesh = self.meshes.add(Cone::new(0.9, 2.0));
+        let rock_mesh = self.meshes.add(Sphere::new(0.5).mesh().ico(1).unwrap());
+        let trunk = self.pixel_material(Color::srgb(0.45, 0.3, 0.15));
+        let crown = self.pixel_material(Color::srgb(0.15, 0.55, 0.2));
+        let rock = self.pixel_material(Color::srgb(0.55, 0.55, 0.6));
+        let pixel = self.layers.pixel_layers();
+
+        self.commands.entity(root).with_children(|root| {
+            for i in 0..12 {
+                // Deterministic scatter on a golden-angle spiral.
+                let angle = i as f32 * 2.3999632;
+                let radius = 2.5 + i as f32 * 0.9;
+                let pos = Vec3::new(angle.cos() * radius, 0.0, angle.sin() * radius);
+                let scale = Vec3::splat(0.8 + (i % 3) as f32 * 0.2);
+                let tree = Transform::from_translation(pos).with_scale(scale);
+
+                root.spawn((
+                    Mesh3d(trunk_mesh.clone()),
+                    MeshMaterial3d(trunk.clone()),
+                    tree * Transform::from_xyz(0.0, 0.6, 0.0),
+                    pixel.clone(),
+                ));
+                root.spawn((
+                    Mesh3d(crown_mesh.clone()),
+                    MeshMaterial3d(crown.clone()),
+                    tree * Transform::from_xyz(0.0, 2.1, 0.0),
+                    pixel.clone(),
+                ));
+
+                let rock_pos = Vec3::new(-pos.z, 0.2, pos.x) * 0.7;
+                root.spawn((
+                    Mesh3d(rock_mesh.clone()),
+                    MeshMaterial3d(rock.clone()),
+                    Transform::from_translation(rock_pos).with_scale(Vec3::new(1.0, 0.6, 0.8)),
+                    pixel.clone(),
+                ));
+            }
+        });
+        root
+    }
+}
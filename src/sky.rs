@@ -0,0 +1,159 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, Face, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+
+use crate::{DEFAULT_PALETTE_BUFFER, default_pixel_art_palette};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Sky backdrop for the low-res layer: a vertical gradient banded into the
+/// palette, with optional Bayer-dithered band transitions and a stylized
+/// sun or moon disk.
+///
+/// Put it on a sphere (e.g. `Sphere::new(1.0)`) around the low-res camera;
+/// the gradient follows the view direction, so the radius does not matter and
+/// the dome is always drawn at the far plane, behind everything else. It is
+/// unlit and skips the prepass and shadows.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub struct PixelArtSkyMaterial {
+    #[uniform(0)]
+    pub params: PixelArtSkyParams,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(1, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+}
+
+/// GPU-side sky parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType)]
+pub struct PixelArtSkyParams {
+    /// Color straight up (linear RGB).
+    pub zenith_color: Vec4,
+    /// Color at the horizon (linear RGB).
+    pub horizon_color: Vec4,
+    /// Color below the horizon (linear RGB).
+    pub ground_color: Vec4,
+    /// Gradient steps between horizon and zenith (default: 6.0).
+    pub bands: f32,
+    /// Gradient exponent; > 1 keeps the horizon color higher up (default: 0.6).
+    pub curve: f32,
+    /// Bayer-dithered transition between neighboring bands (0 = hard band
+    /// edges, 1 = full ordered dither across each band, default: 0.5).
+    pub dither_strength: f32,
+    /// Angular radius of the sun/moon disk in degrees (0 = no disk,
+    /// default: 4.0).
+    pub disk_radius: f32,
+    /// World-space direction towards the disk.
+    pub disk_direction: Vec3,
+    /// Moon phase: fraction of the disk diameter in shadow (0 = full disk,
+    /// ~0.7 = crescent, 1 = new moon, default: 0.0).
+    pub disk_crescent: f32,
+    /// Disk color (linear RGB).
+    pub disk_color: Vec4,
+    /// Number of active palette colors (0 = no palette snapping, default:
+    /// the default palette's count).
+    pub palette_count: u32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArtSkyParams {
+    fn default() -> Self {
+        let (palette, count) = default_pixel_art_palette();
+        let linear = |r: u8, g: u8, b: u8| {
+            let c = Color::srgb_u8(r, g, b).to_linear();
+            Vec4::new(c.red, c.green, c.blue, 1.0)
+        };
+        Self {
+            zenith_color: linear(6, 90, 181),
+            horizon_color: linear(255, 204, 170),
+            ground_color: linear(29, 43, 83),
+            bands: 6.0,
+            curve: 0.6,
+            dither_strength: 0.5,
+            disk_radius: 4.0,
+            disk_direction: Vec3::new(0.5, 0.35, -0.8).normalize(),
+            disk_crescent: 0.0,
+            disk_color: linear(255, 241, 232),
+            palette_count: count,
+            palette_colors: palette,
+        }
+    }
+}
+
+impl Default for PixelArtSkyMaterial {
+    fn default() -> Self {
+        Self {
+            params: PixelArtSkyParams::default(),
+            palette: DEFAULT_PALETTE_BUFFER,
+        }
+    }
+}
+
+impl PixelArtSkyMaterial {
+    /// Sky with a sun disk towards `sun_direction`.
+    pub fn with_sun(mut self, sun_direction: Vec3, color: Color) -> Self {
+        self.params.disk_direction = sun_direction.normalize_or(Vec3::Y);
+        self.params.disk_color = color.to_linear().to_vec4();
+        self.params.disk_crescent = 0.0;
+        self
+    }
+
+    /// Sky with a moon disk towards `moon_direction`, `crescent` of its
+    /// diameter in shadow (0 = full moon).
+    pub fn with_moon(mut self, moon_direction: Vec3, color: Color, crescent: f32) -> Self {
+        self.params.disk_direction = moon_direction.normalize_or(Vec3::Y);
+        self.params.disk_color = color.to_linear().to_vec4();
+        self.params.disk_crescent = crescent;
+        self
+    }
+
+    /// Bind `palette` (see `PixelArtPalette::to_storage_buffer`) with `count`
+    /// active colors.
+    pub fn with_palette(mut self, palette: Handle<ShaderStorageBuffer>, count: u32) -> Self {
+        self.palette = palette;
+        self.params.palette_count = count;
+        self
+    }
+}
+
+impl Material for PixelArtSkyMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/sky.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/sky.wgsl".into()
+    }
+
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // Seen from inside the dome.
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+        Ok(())
+    }
+}
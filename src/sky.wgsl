@@ -0,0 +1,159 @@
+//! Pixel art sky dome: palette-banded vertical gradient with dithered band
+//! transitions and a sun/moon disk. Unlit, drawn at the far plane.
+
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput},
+    mesh_functions,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_core_pipeline::tonemapping::tone_mapping
+
+struct PixelArtSkyParams {
+    zenith_color: vec4<f32>,
+    horizon_color: vec4<f32>,
+    ground_color: vec4<f32>,
+    bands: f32,
+    curve: f32,
+    dither_strength: f32,
+    disk_radius: f32,
+    disk_direction: vec3<f32>,
+    disk_crescent: f32,
+    disk_color: vec4<f32>,
+    palette_count: u32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0)
+var<uniform> sky: PixelArtSkyParams;
+
+#ifdef PALETTE_STORAGE_BUFFER
+@group(#{MATERIAL_BIND_GROUP}) @binding(1)
+var<storage, read> palette_storage: array<vec4<f32>>;
+#endif
+
+fn palette_len() -> u32 {
+#ifdef PALETTE_STORAGE_BUFFER
+    return arrayLength(&palette_storage);
+#else
+    return 64u;
+#endif
+}
+
+fn palette_color(i: u32) -> vec3<f32> {
+#ifdef PALETTE_STORAGE_BUFFER
+    return palette_storage[i].rgb;
+#else
+    return sky.palette_colors[i].rgb;
+#endif
+}
+
+// 4x4 Bayer threshold in [0, 1).
+fn bayer4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) & 3u;
+    let y = u32(pos.y) & 3u;
+    var index = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / 16.0;
+}
+
+fn linear_rgb_to_oklab(rgb: vec3<f32>) -> vec3<f32> {
+    let l = dot(vec3<f32>(0.4122214708, 0.5363325363, 0.0514459929), rgb);
+    let m = dot(vec3<f32>(0.2119034982, 0.6806995451, 0.1073969566), rgb);
+    let s = dot(vec3<f32>(0.0883024619, 0.2817188376, 0.6299787005), rgb);
+    let lms = pow(max(vec3<f32>(l, m, s), vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        dot(vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468), lms),
+        dot(vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099), lms),
+        dot(vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660), lms),
+    );
+}
+
+// Nearest palette entry in OKLab.
+fn snap_to_palette(color: vec3<f32>) -> vec3<f32> {
+    let lab = linear_rgb_to_oklab(color);
+    var best = color;
+    var best_distance = 1e10;
+    let n = min(sky.palette_count, palette_len());
+    for (var i = 0u; i < n; i++) {
+        let pal_rgb = palette_color(i);
+        let d = distance(lab, linear_rgb_to_oklab(pal_rgb));
+        if (d < best_distance) {
+            best_distance = d;
+            best = pal_rgb;
+        }
+    }
+    return best;
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    out.world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    // Far plane (reversed-Z): behind everything, whatever the dome radius.
+    let clip = position_world_to_clip(out.world_position.xyz);
+    out.position = vec4<f32>(clip.xy, 0.0, clip.w);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let dir = normalize(in.world_position.xyz - view.world_position);
+    let cell = floor(in.position.xy);
+
+    var color = sky.ground_color.rgb;
+    if (dir.y >= 0.0) {
+        // Banded gradient; the fractional part of each band is dithered into
+        // the next one.
+        let bands = max(sky.bands, 1.0);
+        let t = pow(dir.y, max(sky.curve, 1e-3)) * bands;
+        var band = floor(t);
+        let s = saturate(sky.dither_strength);
+        let step_up = select(0.0, saturate((fract(t) - (1.0 - s)) / s), s > 0.0);
+        if (bayer4(cell) < step_up) {
+            band += 1.0;
+        }
+        color = mix(sky.horizon_color.rgb, sky.zenith_color.rgb, min(band / bands, 1.0));
+    }
+
+    // Sun / moon disk, optionally shadowed into a crescent.
+    if (sky.disk_radius > 0.0) {
+        let disk_dir = normalize(sky.disk_direction);
+        let radius = radians(sky.disk_radius);
+        let angle = acos(clamp(dot(dir, disk_dir), -1.0, 1.0));
+        if (angle < radius) {
+            var lit = true;
+            if (sky.disk_crescent > 0.0) {
+                // Shadow disk of the same size, shifted sideways so that it
+                // covers `disk_crescent` of the diameter.
+                var side = cross(disk_dir, vec3<f32>(0.0, 1.0, 0.0));
+                side = select(vec3<f32>(1.0, 0.0, 0.0), normalize(side), length(side) > 1e-4);
+                let offset = 2.0 * radius * (1.0 - saturate(sky.disk_crescent));
+                let shadow_dir = normalize(disk_dir + side * tan(offset));
+                lit = acos(clamp(dot(dir, shadow_dir), -1.0, 1.0)) >= radius;
+            }
+            if (lit) {
+                color = sky.disk_color.rgb;
+            }
+        }
+    }
+
+    if (sky.palette_count > 0u) {
+        color = snap_to_palette(color);
+    }
+
+    var out = vec4<f32>(color, 1.0);
+#ifdef TONEMAP_IN_SHADER
+    // Same tonemapping as the pixel art materials, so palette colors match.
+    out = tone_mapping(out, view.color_grading);
+#endif
+    return out;
+}
@@ -0,0 +1,395 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+        prepass::{DepthPrepass, ViewPrepassTextures},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{texture_2d, texture_depth_2d, uniform_buffer},
+            *,
+        },
+        renderer::RenderContext,
+        view::ViewTarget,
+    },
+};
+
+use crate::palette::MAX_UNIFORM_PALETTE_COLORS;
+use crate::pass_order::order_low_res_passes;
+use crate::{PixelArtPalette, QuantizationSpace, SharedPalette};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Palette quantization and dither for `Skybox` pixels of the low-res camera.
+///
+/// `PixelArtMaterial` quantizes meshes, but a `Skybox` (or any HDRI
+/// background) is drawn by Bevy directly, so its colors reach the screen
+/// unquantized and clash with the scene palette. This pass, after tonemapping,
+/// matches only the pixels where the depth prepass holds nothing (the sky) to
+/// the palette with the same Bayer dither as the material; geometry is left
+/// alone. Not needed with `PixelArtPostQuantize`, which already covers the
+/// whole frame. Requires the depth prepass.
+///
+/// Uses the first 64 colors of the `SharedPalette` (or the default palette).
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+#[require(DepthPrepass)]
+pub struct PixelArtSkyboxQuantize {
+    /// Luminance bands, for a stepped gradient (0 = off, default: 0.0).
+    pub bands: f32,
+    /// Color space used for nearest-palette matching (default: CIELAB).
+    #[reflect(ignore)]
+    pub quantization_space: QuantizationSpace,
+    /// Weight of the lightness term in CIELAB/OKLab palette distance (default: 1.0).
+    pub lightness_weight: f32,
+    /// Weight of the chroma (a/b) terms in CIELAB/OKLab palette distance (default: 1.0).
+    pub chroma_weight: f32,
+    /// Blend strength toward palette colors (0 = off, default: 1.0).
+    pub palette_strength: f32,
+    /// Bayer dither strength (0 = off, 1.0 = full, default: 0.3).
+    pub dither_strength: f32,
+    /// Bayer matrix size: 2, 4 (default) or 8.
+    pub bayer_size: u32,
+    /// Palette colors maintained by `resolve_skybox_quantize_palette`.
+    #[reflect(ignore)]
+    palette: Vec<Vec4>,
+}
+
+impl Default for PixelArtSkyboxQuantize {
+    fn default() -> Self {
+        Self {
+            bands: 0.0,
+            quantization_space: QuantizationSpace::CieLab,
+            lightness_weight: 1.0,
+            chroma_weight: 1.0,
+            palette_strength: 1.0,
+            dither_strength: 0.3,
+            bayer_size: 4,
+            palette: PixelArtPalette::default().colors,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct SkyboxQuantizeUniform {
+    pub bands: f32,
+    pub quantization_space: u32,
+    pub lightness_weight: f32,
+    pub chroma_weight: f32,
+    pub palette_count: u32,
+    pub palette_strength: f32,
+    pub dither_strength: f32,
+    pub bayer_size: u32,
+    pub palette_colors: [Vec4; MAX_UNIFORM_PALETTE_COLORS],
+}
+
+impl ExtractComponent for SkyboxQuantizeUniform {
+    type QueryData = &'static PixelArtSkyboxQuantize;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        quantize: bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        let mut palette_colors = [Vec4::ZERO; MAX_UNIFORM_PALETTE_COLORS];
+        let count = quantize.palette.len().min(MAX_UNIFORM_PALETTE_COLORS);
+        palette_colors[..count].copy_from_slice(&quantize.palette[..count]);
+        Some(SkyboxQuantizeUniform {
+            bands: quantize.bands.max(0.0),
+            quantization_space: quantize.quantization_space as u32,
+            lightness_weight: quantize.lightness_weight,
+            chroma_weight: quantize.chroma_weight,
+            palette_count: count as u32,
+            palette_strength: quantize.palette_strength.clamp(0.0, 1.0),
+            dither_strength: quantize.dither_strength.max(0.0),
+            bayer_size: quantize.bayer_size,
+            palette_colors,
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtSkyboxQuantizePlugin;
+
+impl Plugin for PixelArtSkyboxQuantizePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "skybox_quantize.wgsl");
+
+        app.register_type::<PixelArtSkyboxQuantize>();
+        app.add_plugins((
+            ExtractComponentPlugin::<SkyboxQuantizeUniform>::default(),
+            UniformComponentPlugin::<SkyboxQuantizeUniform>::default(),
+        ));
+        app.add_systems(PostUpdate, resolve_skybox_quantize_palette);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<SkyboxQuantizePipeline>>()
+            .add_systems(
+                Render,
+                prepare_skybox_quantize_pipelines.in_set(RenderSystems::Prepare),
+            )
+            .add_render_graph_node::<SkyboxQuantizeNode>(Core3d, PixelArtSkyboxQuantizeLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::Tonemapping,
+                    PixelArtSkyboxQuantizeLabel,
+                    Node3d::Fxaa,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<SkyboxQuantizePipeline>();
+        order_low_res_passes(app);
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Main-world system
+// ──────────────────────────────────────────────
+
+/// Copies the active palette into each `PixelArtSkyboxQuantize` when it or the
+/// palette changes.
+pub fn resolve_skybox_quantize_palette(
+    shared: Option<Res<SharedPalette>>,
+    mut passes: Query<&mut PixelArtSkyboxQuantize>,
+    mut default_palette: Local<Option<PixelArtPalette>>,
+) {
+    let palette_changed = shared.as_ref().is_some_and(|s| s.is_changed());
+    let palette = match &shared {
+        Some(shared) => &shared.0,
+        None => default_palette.get_or_insert_with(PixelArtPalette::default),
+    };
+
+    for mut pass in &mut passes {
+        if !pass.is_added() && !palette_changed {
+            continue;
+        }
+        pass.palette = palette.colors.clone();
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct SkyboxQuantizePipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtSkyboxQuantizeLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct SkyboxQuantizePipeline {
+    pub shader: Handle<Shader>,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for SkyboxQuantizePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "skybox_quantize.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_skybox_quantize: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: source color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: prepass depth
+                    texture_depth_2d(),
+                    // 2: skybox quantize uniform
+                    uniform_buffer::<SkyboxQuantizeUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            shader,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SkyboxQuantizeKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for SkyboxQuantizePipeline {
+    type Key = SkyboxQuantizeKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_skybox_quantize: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_skybox_quantize_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxQuantizePipeline>>,
+    skybox_quantize_pipeline: Res<SkyboxQuantizePipeline>,
+    query: Query<(Entity, &ViewTarget), With<SkyboxQuantizeUniform>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &skybox_quantize_pipeline,
+            SkyboxQuantizeKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(SkyboxQuantizePipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct SkyboxQuantizeNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static ViewPrepassTextures,
+        &'static SkyboxQuantizePipelineId,
+        &'static DynamicUniformIndex<SkyboxQuantizeUniform>,
+    )>,
+}
+
+impl FromWorld for SkyboxQuantizeNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for SkyboxQuantizeNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, prepass, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let skybox_quantize_pipeline = world.resource::<SkyboxQuantizePipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(depth) = &prepass.depth else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<SkyboxQuantizeUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_skybox_quantize_bind_group",
+            &pipeline_cache.get_bind_group_layout(&skybox_quantize_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &depth.texture.default_view,
+                uniform_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_skybox_quantize_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Skybox quantization for the low-res camera: palette matching and Bayer
+//! dither (optionally luminance bands) for pixels with no prepass depth, i.e.
+//! the sky. Same matching and dither as post_quantize.wgsl.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source_color: texture_2d<f32>;
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+
+struct SkyboxQuantizeParams {
+    bands: f32,                    // luminance bands (0 = off)
+    quantization_space: u32,
+    lightness_weight: f32,
+    chroma_weight: f32,
+    palette_count: u32,
+    palette_strength: f32,
+    dither_strength: f32,
+    bayer_size: u32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+@group(0) @binding(2) var<uniform> params: SkyboxQuantizeParams;
+
+fn palette_color(i: u32) -> vec3<f32> {
+    return params.palette_colors[i].rgb;
+}
+
+// ============================================================================
+// Bayer dithering matrix (2x2 / 4x4 / 8x8)
+// ============================================================================
+
+fn bayer(pos: vec2<f32>) -> f32 {
+    var levels = 2u;
+    if (params.bayer_size <= 2u) {
+        levels = 1u;
+    } else if (params.bayer_size >= 8u) {
+        levels = 3u;
+    }
+    let x = u32(pos.x) & 7u;
+    let y = u32(pos.y) & 7u;
+    var index = 0u;
+    for (var i = 0u; i < levels; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / f32(1u << (2u * levels));
+}
+
+// ============================================================================
+// Color spaces
+// ============================================================================
+
+fn lab_f(t: f32) -> f32 {
+    let delta: f32 = 6.0 / 29.0;
+    if (t > delta * delta * delta) {
+        return pow(t, 1.0 / 3.0);
+    } else {
+        return t / (3.0 * delta * delta) + 4.0 / 29.0;
+    }
+}
+
+fn linear_rgb_to_lab(rgb: vec3<f32>) -> vec3<f32> {
+    let x = dot(vec3<f32>(0.4124564, 0.3575761, 0.1804375), rgb) / 0.95047;
+    let y = dot(vec3<f32>(0.2126729, 0.7151522, 0.0721750), rgb);
+    let z = dot(vec3<f32>(0.0193339, 0.1191920, 0.9503041), rgb) / 1.08883;
+    let fx = lab_f(x);
+    let fy = lab_f(y);
+    let fz = lab_f(z);
+    return vec3<f32>(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz));
+}
+
+fn linear_rgb_to_oklab(rgb: vec3<f32>) -> vec3<f32> {
+    let l = dot(vec3<f32>(0.4122214708, 0.5363325363, 0.0514459929), rgb);
+    let m = dot(vec3<f32>(0.2119034982, 0.6806995451, 0.1073969566), rgb);
+    let s = dot(vec3<f32>(0.0883024619, 0.2817188376, 0.6299787005), rgb);
+    let lms = pow(max(vec3<f32>(l, m, s), vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        dot(vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468), lms),
+        dot(vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099), lms),
+        dot(vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660), lms),
+    );
+}
+
+fn linear_to_srgb(rgb: vec3<f32>) -> vec3<f32> {
+    let c = max(rgb, vec3<f32>(0.0));
+    let lo = c * 12.92;
+    let hi = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(hi, lo, c <= vec3<f32>(0.0031308));
+}
+
+fn to_quantization_space(rgb: vec3<f32>) -> vec3<f32> {
+    switch (params.quantization_space) {
+        case 1u: { return linear_rgb_to_oklab(rgb); }
+        case 2u: { return rgb; }
+        case 3u: { return linear_to_srgb(rgb); }
+        default: { return linear_rgb_to_lab(rgb); }
+    }
+}
+
+fn palette_distance(a: vec3<f32>, b: vec3<f32>) -> f32 {
+    let d = a - b;
+    if (params.quantization_space > 1u) {
+        return length(d);
+    }
+    return sqrt(params.lightness_weight * d.x * d.x + params.chroma_weight * (d.y * d.y + d.z * d.z));
+}
+
+// ============================================================================
+// Palette quantization
+// ============================================================================
+
+// Nearest palette entry, dithered toward the second nearest.
+fn quantize(color: vec3<f32>, cell: vec2<f32>) -> vec3<f32> {
+    let lab = to_quantization_space(color);
+    var d1 = 1e10;
+    var d2 = 1e10;
+    var c1 = color;
+    var c2 = color;
+    let n = min(params.palette_count, 64u);
+    for (var i = 0u; i < n; i++) {
+        let pal_rgb = palette_color(i);
+        let d = palette_distance(lab, to_quantization_space(pal_rgb));
+        if (d < d1) {
+            d2 = d1;
+            c2 = c1;
+            d1 = d;
+            c1 = pal_rgb;
+        } else if (d < d2) {
+            d2 = d;
+            c2 = pal_rgb;
+        }
+    }
+    let blend = select(0.0, d1 / (d1 + d2), d1 + d2 > 0.001);
+    if (params.dither_strength > 0.0 && bayer(cell) < blend * params.dither_strength) {
+        return c2;
+    }
+    return c1;
+}
+
+// Steps luminance into `bands` levels, keeping the hue.
+fn band_luminance(rgb: vec3<f32>) -> vec3<f32> {
+    let luminance = dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    if (luminance <= 1e-4) {
+        return rgb;
+    }
+    let stepped = ceil(luminance * params.bands) / params.bands;
+    return rgb * (stepped / luminance);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = vec2<i32>(in.position.xy);
+    let color = textureLoad(source_color, texel, 0);
+    // Geometry (reverse-Z depth > 0) is left to its materials; a transparent
+    // background without a skybox stays transparent for the compositor.
+    if (textureLoad(depth_texture, texel, 0) > 0.0 || color.a <= 0.0) {
+        return color;
+    }
+
+    var rgb = max(color.rgb, vec3<f32>(0.0));
+    if (params.bands > 0.0) {
+        rgb = band_luminance(rgb);
+    }
+    rgb = clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+    if (params.palette_count > 0u) {
+        rgb = mix(rgb, quantize(rgb, floor(in.position.xy)), params.palette_strength);
+    }
+    return vec4<f32>(rgb, color.a);
+}
@@ -0,0 +1,124 @@
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+
+use crate::PixelArtCompositor;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Snaps the low-res camera's translation to whole low-res texels.
+///
+/// Sub-texel camera motion makes every edge in the low-res image crawl. Before
+/// transform propagation the camera is moved along its own right/up axes to
+/// the nearest texel (one texel of the orthographic area, or of the view plane
+/// at `focus_distance` for perspective cameras), and the unsnapped translation
+/// is put back at the start of the next frame, so gameplay code keeps
+/// integrating the exact position.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SnapToPixelGrid {
+    /// Distance of the reference plane for perspective cameras (default: 10.0).
+    pub focus_distance: f32,
+    /// Offset removed by the last snap, in texels along the camera's right/up
+    /// axes (read-only; useful for sub-pixel compositing).
+    pub residual: Vec2,
+    /// Unsnapped and snapped translation of the last snap, to restore next
+    /// frame.
+    #[reflect(ignore)]
+    unsnapped: Option<(Vec3, Vec3)>,
+}
+
+impl Default for SnapToPixelGrid {
+    fn default() -> Self {
+        Self {
+            focus_distance: 10.0,
+            residual: Vec2::ZERO,
+            unsnapped: None,
+        }
+    }
+}
+
+impl SnapToPixelGrid {
+    /// World size of one low-res texel for `projection` on a target
+    /// `target_height` pixels tall.
+    pub fn texel_size(&self, projection: &Projection, target_height: u32) -> Option<f32> {
+        let height = target_height.max(1) as f32;
+        match projection {
+            Projection::Orthographic(ortho) => Some(ortho.area.height() / height),
+            Projection::Perspective(persp) => {
+                Some(2.0 * self.focus_distance * (persp.fov * 0.5).tan() / height)
+            }
+            _ => None,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Puts back the translation the snap replaced, unless something else wrote
+/// the transform since.
+pub fn restore_pixel_grid_snap(mut cameras: Query<(&mut SnapToPixelGrid, &mut Transform)>) {
+    for (mut snap, mut transform) in &mut cameras {
+        if let Some((unsnapped, snapped)) = snap.unsnapped.take()
+            && transform.translation == snapped
+        {
+            transform.bypass_change_detection().translation = unsnapped;
+        }
+    }
+}
+
+/// Snaps every `SnapToPixelGrid` camera to its texel grid. Runs before
+/// transform propagation.
+pub fn snap_to_pixel_grid(
+    mut cameras: Query<(&mut SnapToPixelGrid, &mut Transform, &Projection, &Camera)>,
+) {
+    for (mut snap, mut transform, projection, camera) in &mut cameras {
+        let Some(target) = camera.physical_target_size() else {
+            continue;
+        };
+        let Some(texel) = snap.texel_size(projection, target.y) else {
+            continue;
+        };
+        if texel <= 0.0 {
+            continue;
+        }
+
+        let right = transform.rotation * Vec3::X;
+        let up = transform.rotation * Vec3::Y;
+        let local = Vec2::new(
+            transform.translation.dot(right),
+            transform.translation.dot(up),
+        );
+        let snapped = (local / texel).round() * texel;
+
+        let unsnapped = transform.translation;
+        transform.translation += right * (snapped.x - local.x) + up * (snapped.y - local.y);
+        snap.unsnapped = Some((unsnapped, transform.translation));
+        snap.residual = (local - snapped) / texel;
+    }
+}
+
+/// Copies each snapped camera's residual into the `PixelArtCompositor` that
+/// displays its target, when that compositor has an overscan border to shift
+/// into.
+pub fn sync_compositor_subpixel_offset(
+    cameras: Query<(&SnapToPixelGrid, &RenderTarget)>,
+    mut compositors: Query<&mut PixelArtCompositor>,
+) {
+    for (snap, target) in &cameras {
+        let RenderTarget::Image(target) = target else {
+            continue;
+        };
+        for mut compositor in &mut compositors {
+            if compositor.overscan > 0
+                && compositor.lowres_image == target.handle
+                && compositor.subpixel_offset != snap.residual
+            {
+                compositor.subpixel_offset = snap.residual;
+            }
+        }
+    }
+}
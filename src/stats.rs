@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use bevy::camera::primitives::Aabb;
+use bevy::camera::visibility::{RenderLayers, VisibilitySystems};
+use bevy::prelude::*;
+
+use crate::{HoldoutMaterial, LowResPixelArtCamera, PixelArtMaterial};
+
+/// Resolution of the coverage estimate grid, in cells.
+const COVERAGE_GRID: UVec2 = UVec2::new(64, 36);
+
+// ──────────────────────────────────────────────
+//  Public types
+// ──────────────────────────────────────────────
+
+/// Per-frame pixel pipeline statistics, updated by `PixelArtStatsPlugin`.
+///
+/// Draw calls, overdraw and coverage are CPU estimates from the visible
+/// meshes' screen-space bounding boxes on the low-res camera, not GPU
+/// counters: good for spotting trends, not for exact numbers.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PixelArtStats {
+    /// Loaded `PixelArtMaterial` assets.
+    pub materials: usize,
+    /// Distinct palettes (buffer + count) across those materials.
+    pub unique_palettes: usize,
+    /// Visible pixel art and holdout meshes on the low-res camera.
+    pub draw_calls: usize,
+    /// Summed mesh screen area divided by covered area (1.0 = no overdraw).
+    pub overdraw: f32,
+    /// Fraction of the low-res target covered by geometry, i.e. the share of
+    /// pixels the compositor actually blends (0..1).
+    pub coverage: f32,
+}
+
+/// Marker for the stats overlay text.
+#[derive(Component)]
+pub struct PixelArtStatsOverlay;
+
+/// Collects `PixelArtStats` every frame and shows them in a corner overlay.
+///
+/// Set `overlay: false` to only fill the resource (e.g. for a custom UI).
+pub struct PixelArtStatsPlugin {
+    pub overlay: bool,
+}
+
+impl Default for PixelArtStatsPlugin {
+    fn default() -> Self {
+        Self { overlay: true }
+    }
+}
+
+impl Plugin for PixelArtStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PixelArtStats>().add_systems(
+            PostUpdate,
+            collect_pixel_art_stats.after(VisibilitySystems::CheckVisibility),
+        );
+
+        if self.overlay {
+            app.add_systems(Startup, spawn_stats_overlay)
+                .add_systems(Update, update_stats_overlay);
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Meshes drawn with a pixel art or holdout material.
+type PixelArtMeshFilter = Or<(
+    With<MeshMaterial3d<PixelArtMaterial>>,
+    With<MeshMaterial3d<HoldoutMaterial>>,
+)>;
+
+pub fn collect_pixel_art_stats(
+    mut stats: ResMut<PixelArtStats>,
+    materials: Res<Assets<PixelArtMaterial>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>), With<LowResPixelArtCamera>>,
+    meshes: Query<
+        (
+            &ViewVisibility,
+            &GlobalTransform,
+            &Aabb,
+            Option<&RenderLayers>,
+        ),
+        PixelArtMeshFilter,
+    >,
+) {
+    stats.materials = materials.len();
+    stats.unique_palettes = materials
+        .iter()
+        .map(|(_, mat)| {
+            (
+                mat.extension.palette.id(),
+                mat.extension.params.palette_count,
+            )
+        })
+        .collect::<HashSet<_>>()
+        .len();
+
+    let Some((camera, camera_transform, camera_layers)) = cameras.iter().next() else {
+        stats.draw_calls = 0;
+        stats.overdraw = 0.0;
+        stats.coverage = 0.0;
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    let camera_layers = camera_layers.cloned().unwrap_or_default();
+
+    let mut grid = vec![0u16; (COVERAGE_GRID.x * COVERAGE_GRID.y) as usize];
+    let mut draw_calls = 0;
+    for (visibility, transform, aabb, layers) in &meshes {
+        if !visibility.get()
+            || !camera_layers.intersects(layers.unwrap_or(&RenderLayers::default()))
+        {
+            continue;
+        }
+        draw_calls += 1;
+
+        // Screen-space bounds of the eight AABB corners.
+        let center = Vec3::from(aabb.center);
+        let half = Vec3::from(aabb.half_extents);
+        let mut min = Vec2::MAX;
+        let mut max = Vec2::MIN;
+        for i in 0..8 {
+            let sign = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            let corner = transform.transform_point(center + half * sign);
+            if let Ok(p) = camera.world_to_viewport(camera_transform, corner) {
+                min = min.min(p);
+                max = max.max(p);
+            }
+        }
+        if min.x > max.x {
+            continue;
+        }
+
+        let cell = viewport / COVERAGE_GRID.as_vec2();
+        let lo = (min / cell).floor().max(Vec2::ZERO).as_uvec2();
+        let hi = (max / cell).ceil().as_uvec2().min(COVERAGE_GRID);
+        for y in lo.y..hi.y {
+            for x in lo.x..hi.x {
+                let c = &mut grid[(y * COVERAGE_GRID.x + x) as usize];
+                *c = c.saturating_add(1);
+            }
+        }
+    }
+
+    let covered = grid.iter().filter(|&&c| c > 0).count();
+    let total: u32 = grid.iter().map(|&c| c as u32).sum();
+    stats.draw_calls = draw_calls;
+    stats.coverage = covered as f32 / grid.len() as f32;
+    stats.overdraw = if covered > 0 {
+        total as f32 / covered as f32
+    } else {
+        0.0
+    };
+}
+
+fn spawn_stats_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Pixel Art Stats"),
+        PixelArtStatsOverlay,
+        Text::default(),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.0),
+            bottom: Val::Px(8.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+    ));
+}
+
+fn update_stats_overlay(
+    stats: Res<PixelArtStats>,
+    mut overlay: Query<&mut Text, With<PixelArtStatsOverlay>>,
+) {
+    for mut text in &mut overlay {
+        text.0 = format!(
+            "pixel art materials: {}\nunique palettes: {}\nlow-res draws: {}\nlow-res overdraw: {:.2}x\ncompositor coverage: {:.0}%",
+            stats.materials,
+            stats.unique_palettes,
+            stats.draw_calls,
+            stats.overdraw,
+            stats.coverage * 100.0,
+        );
+    }
+}
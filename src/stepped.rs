@@ -0,0 +1,95 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Shows the entity's motion and skeletal pose at a fixed low frame rate
+/// (e.g. `AnimateAtFps(10)`), for the hand-animated sprite feel on 3D
+/// characters while the rest of the scene moves smoothly.
+///
+/// The `Transform` of the entity and all its descendants (skeleton joints
+/// included) is sampled `fps` times per second and held in between: after
+/// animation and before transform propagation the held transforms are shown,
+/// and the live ones are put back at the start of the next frame, so gameplay
+/// code and animation players keep advancing normally. `0` disables stepping.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[require(SteppedPose)]
+pub struct AnimateAtFps(pub u8);
+
+/// Held and live transforms of an `AnimateAtFps` hierarchy (added
+/// automatically).
+#[derive(Component, Clone, Debug, Default)]
+pub struct SteppedPose {
+    /// Time since the last sample, in seconds.
+    elapsed: f32,
+    /// Transforms shown until the next sample.
+    held: HashMap<Entity, Transform>,
+    /// `(entity, live, shown)` of the transforms replaced this frame, to
+    /// restore next frame.
+    replaced: Vec<(Entity, Transform, Transform)>,
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Puts back the live transforms the held pose replaced, unless something else
+/// wrote the transform since.
+pub fn restore_stepped_poses(
+    mut poses: Query<&mut SteppedPose>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for mut pose in &mut poses {
+        for (entity, live, shown) in pose.replaced.drain(..) {
+            if let Ok(mut transform) = transforms.get_mut(entity)
+                && *transform == shown
+            {
+                transform.set_if_neq(live);
+            }
+        }
+    }
+}
+
+/// Samples or holds each `AnimateAtFps` hierarchy. Runs after animation and
+/// before transform propagation.
+pub fn step_animated_poses(
+    time: Res<Time>,
+    mut poses: Query<(Entity, &AnimateAtFps, &mut SteppedPose)>,
+    children: Query<&Children>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (root, fps, mut pose) in &mut poses {
+        if fps.0 == 0 {
+            pose.held.clear();
+            continue;
+        }
+        let interval = 1.0 / fps.0 as f32;
+        pose.elapsed += time.delta_secs();
+
+        let hierarchy = std::iter::once(root).chain(children.iter_descendants(root));
+        if pose.held.is_empty() || pose.elapsed >= interval {
+            // Keep the remainder so the average rate matches `fps`, but drop
+            // backlog after a hitch.
+            pose.elapsed = (pose.elapsed - interval).clamp(0.0, interval);
+            let held = hierarchy
+                .filter_map(|entity| Some((entity, *transforms.get(entity).ok()?)))
+                .collect();
+            pose.held = held;
+            continue;
+        }
+
+        let pose = &mut *pose;
+        for entity in hierarchy {
+            let (Some(held), Ok(mut transform)) =
+                (pose.held.get(&entity), transforms.get_mut(entity))
+            else {
+                continue;
+            };
+            pose.replaced.push((entity, *transform, *held));
+            transform.set_if_neq(*held);
+        }
+    }
+}
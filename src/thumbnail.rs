@@ -0,0 +1,162 @@
+use bevy::camera::RenderTarget;
+use bevy::ecs::system::SystemParam;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+
+use crate::{PixelArtLayers, PixelArtMaterial};
+
+/// Spacing between simultaneous thumbnail rigs, far from any scene content.
+const RIG_SPACING: f32 = 100.0;
+const RIG_ORIGIN: Vec3 = Vec3::new(-100_000.0, -100_000.0, 0.0);
+
+// ──────────────────────────────────────────────
+//  Public types
+// ──────────────────────────────────────────────
+
+/// Preview mesh for a material thumbnail.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThumbnailShape {
+    #[default]
+    Sphere,
+    Cube,
+}
+
+/// Offscreen rig (camera + preview mesh + light) rendering one thumbnail.
+///
+/// Despawned, with its children, once `remaining_frames` reaches zero; the
+/// thumbnail image keeps the last rendered frame. `None` keeps the rig alive
+/// (live preview) until it is despawned manually.
+#[derive(Component, Clone, Debug)]
+pub struct ThumbnailRig {
+    pub remaining_frames: Option<u32>,
+}
+
+/// System param rendering `PixelArtMaterial` thumbnails into small images for
+/// editors and asset browsers.
+///
+/// Each thumbnail spawns a rig on `PixelArtLayers::thumbnail`, far away from
+/// the scene, that renders the material on a sphere or cube through the
+/// regular material pipeline. Pipelines compile asynchronously, so the first
+/// frames of a new material may be blank — keep the rig alive for a handful
+/// of frames (the default) rather than one.
+#[derive(SystemParam)]
+pub struct PixelArtThumbnails<'w, 's> {
+    commands: Commands<'w, 's>,
+    images: ResMut<'w, Assets<Image>>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    layers: Res<'w, PixelArtLayers>,
+    next_slot: Local<'s, u32>,
+}
+
+impl PixelArtThumbnails<'_, '_> {
+    /// Frames a one-shot rig stays alive by default.
+    pub const DEFAULT_FRAMES: u32 = 10;
+
+    /// Render `material` on `shape` into a new `size` image. The rig despawns
+    /// after `DEFAULT_FRAMES` frames.
+    pub fn render(
+        &mut self,
+        material: Handle<PixelArtMaterial>,
+        shape: ThumbnailShape,
+        size: UVec2,
+    ) -> Handle<Image> {
+        self.spawn(material, shape, size, Some(Self::DEFAULT_FRAMES)).1
+    }
+
+    /// Like `render`, but the rig keeps rendering until its entity is despawned,
+    /// so the thumbnail follows live material edits.
+    pub fn render_live(
+        &mut self,
+        material: Handle<PixelArtMaterial>,
+        shape: ThumbnailShape,
+        size: UVec2,
+    ) -> (Entity, Handle<Image>) {
+        self.spawn(material, shape, size, None)
+    }
+
+    fn spawn(
+        &mut self,
+        material: Handle<PixelArtMaterial>,
+        shape: ThumbnailShape,
+        size: UVec2,
+        remaining_frames: Option<u32>,
+    ) -> (Entity, Handle<Image>) {
+        let mut target = Image::new_target_texture(
+            size.x.max(1),
+            size.y.max(1),
+            TextureFormat::Rgba8Unorm,
+            Some(TextureFormat::Rgba8UnormSrgb),
+        );
+        target.sampler = ImageSampler::nearest();
+        let image = self.images.add(target);
+
+        let mesh = match shape {
+            ThumbnailShape::Sphere => self.meshes.add(Sphere::new(1.0).mesh().ico(4).unwrap()),
+            ThumbnailShape::Cube => self.meshes.add(Cuboid::new(1.3, 1.3, 1.3)),
+        };
+
+        let slot = *self.next_slot;
+        *self.next_slot = (slot + 1) % 1024;
+        let origin = RIG_ORIGIN + Vec3::X * RIG_SPACING * slot as f32;
+        let layers = self.layers.thumbnail_layers();
+
+        let rig = self
+            .commands
+            .spawn((
+                Name::new("Pixel Art Thumbnail"),
+                ThumbnailRig { remaining_frames },
+                Transform::from_translation(origin),
+                Visibility::default(),
+            ))
+            .with_children(|rig| {
+                rig.spawn((
+                    Mesh3d(mesh),
+                    MeshMaterial3d(material),
+                    Transform::from_rotation(Quat::from_euler(EulerRot::YXZ, 0.6, 0.4, 0.0)),
+                    layers.clone(),
+                ));
+                rig.spawn((
+                    DirectionalLight {
+                        illuminance: 8000.0,
+                        ..default()
+                    },
+                    Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.5, 0.0)),
+                    layers.clone(),
+                ));
+                rig.spawn((
+                    Camera3d::default(),
+                    Camera {
+                        order: -10,
+                        clear_color: Color::NONE.into(),
+                        ..default()
+                    },
+                    RenderTarget::Image(image.clone().into()),
+                    Msaa::Off,
+                    Transform::from_xyz(0.0, 0.0, 3.2).looking_at(Vec3::ZERO, Vec3::Y),
+                    layers,
+                ));
+            })
+            .id();
+
+        (rig, image)
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Counts down one-shot thumbnail rigs and despawns finished ones.
+pub fn tick_thumbnail_rigs(mut commands: Commands, mut rigs: Query<(Entity, &mut ThumbnailRig)>) {
+    for (entity, mut rig) in &mut rigs {
+        let Some(frames) = rig.remaining_frames.as_mut() else {
+            continue;
+        };
+        if *frames == 0 {
+            commands.entity(entity).despawn();
+        } else {
+            *frames -= 1;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, RenderPipelineDescriptor, ShaderType};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+use bevy::ui_render::ui_material::{UiMaterial, UiMaterialKey};
+
+use crate::{DEFAULT_PALETTE_BUFFER, PixelArtShaderParams};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Bevy UI material for HUD panels and bars (use with `MaterialNode`): a
+/// vertical gradient times an optional texture, cut to `fill` from the left,
+/// then palette-quantized with the same color matching and Bayer dither as
+/// `PixelArtExtension`, so HUD gradients band like the game view.
+///
+/// The UI is drawn at window resolution; set `params.pixel_scale` to the
+/// upscale factor of the low-res layer so the panel is drawn in cells the
+/// size of the game's pixels.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub struct PixelArtUiMaterial {
+    #[uniform(0)]
+    pub params: PixelArtUiParams,
+    /// Texture multiplied with the gradient (white when unset).
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Option<Handle<Image>>,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(3, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+}
+
+/// GPU-side UI parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType)]
+pub struct PixelArtUiParams {
+    /// Color at the top of the node (linear RGBA, default: white).
+    pub color: Vec4,
+    /// Color at the bottom of the node (linear RGBA, default: white).
+    pub gradient_color: Vec4,
+    /// Filled fraction of the node width, for bars (0..1, default: 1.0).
+    pub fill: f32,
+    /// Window pixels per cell; gradient, texture and dither are evaluated
+    /// once per cell (default: 1.0).
+    pub pixel_scale: f32,
+    /// Color space used for nearest-palette matching (`QuantizationSpace as u32`,
+    /// default: CIELAB).
+    pub quantization_space: u32,
+    /// Weight of the lightness term in CIELAB/OKLab palette distance (default: 1.0).
+    pub lightness_weight: f32,
+    /// Weight of the chroma (a/b) terms in CIELAB/OKLab palette distance (default: 1.0).
+    pub chroma_weight: f32,
+    /// Number of active palette colors (0 = no quantization, default: the
+    /// default palette's count).
+    pub palette_count: u32,
+    /// Blend strength toward palette colors (0.0..1.0, default: 1.0).
+    pub palette_strength: f32,
+    /// Bayer dither strength (0 = off, 1.0 = full, default: 0.3).
+    pub dither_strength: f32,
+    /// Bayer matrix size: 2, 4 (default) or 8.
+    pub bayer_size: u32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArtUiParams {
+    fn default() -> Self {
+        Self::from(&PixelArtShaderParams::default())
+    }
+}
+
+impl From<&PixelArtShaderParams> for PixelArtUiParams {
+    /// Palette and dither settings of a 3D material, with a white fill.
+    fn from(params: &PixelArtShaderParams) -> Self {
+        Self {
+            color: Vec4::ONE,
+            gradient_color: Vec4::ONE,
+            fill: 1.0,
+            pixel_scale: 1.0,
+            quantization_space: params.quantization_space,
+            lightness_weight: params.lightness_weight,
+            chroma_weight: params.chroma_weight,
+            palette_count: params.palette_count,
+            palette_strength: params.palette_strength,
+            dither_strength: params.dither_strength,
+            bayer_size: params.bayer_size,
+            #[cfg(feature = "webgl2")]
+            palette_colors: params.palette_colors,
+            #[cfg(not(feature = "webgl2"))]
+            palette_colors: [Vec4::ZERO; 64],
+        }
+    }
+}
+
+impl Default for PixelArtUiMaterial {
+    fn default() -> Self {
+        Self {
+            params: PixelArtUiParams::default(),
+            texture: None,
+            palette: DEFAULT_PALETTE_BUFFER,
+        }
+    }
+}
+
+impl PixelArtUiMaterial {
+    /// Panel with a vertical gradient from `top` to `bottom`.
+    pub fn gradient(top: Color, bottom: Color) -> Self {
+        let mut material = Self::default();
+        material.params.color = top.to_linear().to_vec4();
+        material.params.gradient_color = bottom.to_linear().to_vec4();
+        material
+    }
+
+    /// Set `pixel_scale` (window pixels per cell).
+    pub fn with_pixel_scale(mut self, pixel_scale: f32) -> Self {
+        self.params.pixel_scale = pixel_scale;
+        self
+    }
+
+    /// Bind `palette` (see `PixelArtPalette::to_storage_buffer`) with `count`
+    /// active colors.
+    pub fn with_palette(mut self, palette: Handle<ShaderStorageBuffer>, count: u32) -> Self {
+        self.palette = palette;
+        self.params.palette_count = count;
+        self
+    }
+}
+
+impl UiMaterial for PixelArtUiMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/pixel_art_ui.wgsl".into()
+    }
+
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, _key: UiMaterialKey<Self>) {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::graph::{Core3d, Node3d},
+    },
+    ecs::query::QueryState,
+    prelude::*,
+    render::{
+        Extract, Render, RenderApp, RenderSystems,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_graph::{Node, NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel},
+        render_resource::{
+            binding_types::{texture_2d, uniform_buffer},
+            *,
+        },
+        renderer::RenderContext,
+        sync_world::RenderEntity,
+        texture::GpuImage,
+        view::ViewTarget,
+    },
+};
+
+// ──────────────────────────────────────────────
+//  Public component
+// ──────────────────────────────────────────────
+
+/// Presents the low-res target on a window camera without a full-res scene.
+///
+/// For games that are entirely pixel art: the camera's output is replaced by
+/// `lowres_image`, nearest-upscaled and centered, with the remaining border
+/// filled by `letterbox_color`. Unlike `PixelArtCompositor` there is no depth
+/// test, so no prepass is needed on either camera. Give the window camera a
+/// `RenderLayers` with nothing on it so its own scene pass stays empty.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PixelArtUpscale {
+    /// Target of the low-res camera to present.
+    pub lowres_image: Handle<Image>,
+    /// Scale by whole multiples only, so every low-res pixel is the same size
+    /// on screen (default: true). Otherwise the image fills the window's
+    /// shorter axis.
+    pub integer_scaling: bool,
+    /// Color of the border around the image (default: black).
+    pub letterbox_color: Color,
+}
+
+impl Default for PixelArtUpscale {
+    fn default() -> Self {
+        Self {
+            lowres_image: Handle::default(),
+            integer_scaling: true,
+            letterbox_color: Color::BLACK,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  GPU uniform
+// ──────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct UpscaleUniform {
+    /// Linear RGB letterbox color + alpha.
+    pub letterbox_color: Vec4,
+    /// Viewport size in physical pixels.
+    pub viewport_size: Vec2,
+    /// 0 = fit, 1 = integer multiples.
+    pub integer_scaling: u32,
+}
+
+impl ExtractComponent for UpscaleUniform {
+    type QueryData = (&'static PixelArtUpscale, &'static Camera);
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        (upscale, camera): bevy::ecs::query::QueryItem<'_, '_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        Some(UpscaleUniform {
+            letterbox_color: upscale.letterbox_color.to_linear().to_vec4(),
+            viewport_size: camera.physical_viewport_size()?.as_vec2(),
+            integer_scaling: upscale.integer_scaling as u32,
+        })
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Plugin
+// ──────────────────────────────────────────────
+
+pub struct PixelArtUpscalePlugin;
+
+impl Plugin for PixelArtUpscalePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "upscale.wgsl");
+
+        app.register_type::<PixelArtUpscale>();
+        app.add_plugins((
+            ExtractComponentPlugin::<UpscaleUniform>::default(),
+            UniformComponentPlugin::<UpscaleUniform>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<UpscalePipeline>>()
+            .add_systems(ExtractSchedule, extract_upscale)
+            .add_systems(
+                Render,
+                prepare_upscale_pipelines.in_set(RenderSystems::Prepare),
+            )
+            .add_render_graph_node::<UpscaleNode>(Core3d, PixelArtUpscaleLabel)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::Fxaa,
+                    PixelArtUpscaleLabel,
+                    Node3d::EndMainPassPostProcessing,
+                ),
+            )
+            .add_render_graph_edge(Core3d, Node3d::Smaa, PixelArtUpscaleLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<UpscalePipeline>();
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render-world types
+// ──────────────────────────────────────────────
+
+/// Extracted each frame from `PixelArtUpscale`.
+#[derive(Component, Clone)]
+pub struct ExtractedUpscale {
+    pub lowres_image: Handle<Image>,
+}
+
+/// Per-view cached pipeline id.
+#[derive(Component, Clone, Copy)]
+pub struct UpscalePipelineId(CachedRenderPipelineId);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PixelArtUpscaleLabel;
+
+// ──────────────────────────────────────────────
+//  Pipeline resource
+// ──────────────────────────────────────────────
+
+#[derive(Resource)]
+pub struct UpscalePipeline {
+    pub shader: Handle<Shader>,
+    pub layout: BindGroupLayoutDescriptor,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for UpscalePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "upscale.wgsl");
+
+        let layout = BindGroupLayoutDescriptor::new(
+            "pixel_art_upscale: bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // 0: lowres color
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // 1: upscale uniform
+                    uniform_buffer::<UpscaleUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            shader,
+            layout,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Specialization
+// ──────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpscaleKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for UpscalePipeline {
+    type Key = UpscaleKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("pixel_art_upscale: pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Extract system
+// ──────────────────────────────────────────────
+
+pub fn extract_upscale(
+    mut commands: Commands,
+    upscale_query: Extract<Query<(RenderEntity, &PixelArtUpscale)>>,
+) {
+    for (entity, upscale) in upscale_query.iter() {
+        commands
+            .get_entity(entity)
+            .expect("Upscale entity wasn't synced.")
+            .insert(ExtractedUpscale {
+                lowres_image: upscale.lowres_image.clone(),
+            });
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Prepare system
+// ──────────────────────────────────────────────
+
+pub fn prepare_upscale_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UpscalePipeline>>,
+    upscale_pipeline: Res<UpscalePipeline>,
+    query: Query<(Entity, &ViewTarget), With<ExtractedUpscale>>,
+) {
+    for (entity, view_target) in &query {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &upscale_pipeline,
+            UpscaleKey {
+                hdr: view_target.is_hdr(),
+            },
+        );
+        commands.entity(entity).insert(UpscalePipelineId(id));
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Render node
+// ──────────────────────────────────────────────
+
+pub struct UpscaleNode {
+    view_query: QueryState<(
+        &'static ViewTarget,
+        &'static ExtractedUpscale,
+        &'static UpscalePipelineId,
+        &'static DynamicUniformIndex<UpscaleUniform>,
+    )>,
+}
+
+impl FromWorld for UpscaleNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for UpscaleNode {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Ok((view_target, extracted, pipeline_id, uniform_index)) =
+            self.view_query.get_manual(world, graph.view_entity())
+        else {
+            return Ok(());
+        };
+
+        let upscale_pipeline = world.resource::<UpscalePipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(lowres_image) = world
+            .resource::<RenderAssets<GpuImage>>()
+            .get(&extracted.lowres_image)
+        else {
+            return Ok(());
+        };
+        let Some(uniform_binding) = world
+            .resource::<ComponentUniforms<UpscaleUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        // The scene pass is discarded; only the destination is written.
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "pixel_art_upscale_bind_group",
+            &pipeline_cache.get_bind_group_layout(&upscale_pipeline.layout),
+            &BindGroupEntries::sequential((&lowres_image.texture_view, uniform_binding)),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("pixel_art_upscale_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var lowres_color: texture_2d<f32>;
+
+struct UpscaleSettings {
+    letterbox_color: vec4<f32>,    // linear rgb + alpha
+    viewport_size: vec2<f32>,      // physical pixels
+    integer_scaling: u32,          // 0 = fit, 1 = whole multiples
+}
+@group(0) @binding(1) var<uniform> settings: UpscaleSettings;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let lowres_size = vec2<f32>(textureDimensions(lowres_color));
+    let screen_size = settings.viewport_size;
+
+    var scale = min(screen_size.x / lowres_size.x, screen_size.y / lowres_size.y);
+    if (settings.integer_scaling != 0u) {
+        scale = max(floor(scale), 1.0);
+    }
+    let image_size = lowres_size * scale;
+    let origin = floor((screen_size - image_size) * 0.5);
+
+    let local = (in.position.xy - origin) / scale;
+    if (any(local < vec2<f32>(0.0)) || any(local >= lowres_size)) {
+        return settings.letterbox_color;
+    }
+    return textureLoad(lowres_color, vec2<i32>(local), 0);
+}
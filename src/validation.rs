@@ -0,0 +1,644 @@
+use bevy::prelude::*;
+
+use crate::{
+    DebugStage, MAX_TOON_THRESHOLDS, PixelArtMaterial, PixelArtShaderParams, PixelArtSubParams,
+};
+
+/// Palette capacity of the active palette binding.
+#[cfg(not(feature = "webgl2"))]
+const PALETTE_CAPACITY: u32 = crate::MAX_PALETTE_COLORS as u32;
+#[cfg(feature = "webgl2")]
+const PALETTE_CAPACITY: u32 = crate::palette::MAX_UNIFORM_PALETTE_COLORS as u32;
+
+// ──────────────────────────────────────────────
+//  Parameter sanitizing
+// ──────────────────────────────────────────────
+
+/// One parameter that `PixelArtShaderParams::sanitize` had to fix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamIssue {
+    /// Field name, e.g. `"toon_bands"`.
+    pub field: &'static str,
+    /// What was wrong and what it was replaced with.
+    pub message: String,
+}
+
+/// Collects fixes while sanitizing.
+struct Fixer(Vec<ParamIssue>);
+
+impl Fixer {
+    /// Replaces non-finite values with `fallback` and clamps to `min..=max`.
+    fn float(&mut self, field: &'static str, value: &mut f32, min: f32, max: f32, fallback: f32) {
+        let fixed = if value.is_finite() {
+            value.clamp(min, max)
+        } else {
+            fallback
+        };
+        if fixed != *value {
+            self.0.push(ParamIssue {
+                field,
+                message: format!("{value} is outside {min}..={max}, using {fixed}"),
+            });
+            *value = fixed;
+        }
+    }
+
+    fn uint(&mut self, field: &'static str, value: &mut u32, max: u32) {
+        if *value > max {
+            self.0.push(ParamIssue {
+                field,
+                message: format!("{value} exceeds {max}, using {max}"),
+            });
+            *value = max;
+        }
+    }
+
+    fn vec(&mut self, field: &'static str, value: &mut Vec4, fallback: Vec4) {
+        if !value.is_finite() {
+            self.0.push(ParamIssue {
+                field,
+                message: format!("{value} is not finite, using {fallback}"),
+            });
+            *value = fallback;
+        }
+    }
+
+    fn vec3(&mut self, field: &'static str, value: &mut Vec3, fallback: Vec3) {
+        if !value.is_finite() {
+            self.0.push(ParamIssue {
+                field,
+                message: format!("{value} is not finite, using {fallback}"),
+            });
+            *value = fallback;
+        }
+    }
+
+    fn mat3(&mut self, field: &'static str, value: &mut Mat3, fallback: Mat3) {
+        if !value.is_finite() {
+            self.0.push(ParamIssue {
+                field,
+                message: format!("{value} is not finite, using {fallback}"),
+            });
+            *value = fallback;
+        }
+    }
+}
+
+impl PixelArtShaderParams {
+    /// Clamps out-of-range and non-finite parameters in place and reports
+    /// every field that was changed. Invalid values otherwise reach the GPU
+    /// and produce garbage output with no diagnostics.
+    pub fn sanitize(&mut self) -> Vec<ParamIssue> {
+        let defaults = Self::default();
+        let sub_defaults = PixelArtSubParams::default();
+        let mut fix = Fixer(Vec::new());
+
+        fix.vec("base_tint", &mut self.base_tint, defaults.base_tint);
+        fix.float(
+            "toon_bands",
+            &mut self.toon_bands,
+            1.0,
+            256.0,
+            defaults.toon_bands,
+        );
+        fix.float(
+            "toon_softness",
+            &mut self.toon_softness,
+            0.0,
+            1.0,
+            defaults.toon_softness,
+        );
+        fix.float(
+            "toon_shadow_floor",
+            &mut self.toon_shadow_floor,
+            0.0,
+            1.0,
+            defaults.toon_shadow_floor,
+        );
+        fix.float(
+            "dither_density",
+            &mut self.dither_density,
+            0.01,
+            1024.0,
+            defaults.dither_density,
+        );
+        fix.uint("palette_count", &mut self.palette_count, PALETTE_CAPACITY);
+        fix.float(
+            "palette_strength",
+            &mut self.palette_strength,
+            0.0,
+            1.0,
+            defaults.palette_strength,
+        );
+        fix.float(
+            "dither_strength",
+            &mut self.dither_strength,
+            0.0,
+            1.0,
+            defaults.dither_strength,
+        );
+        fix.uint(
+            "debug_stage",
+            &mut self.debug_stage,
+            DebugStage::ALL.len() as u32 - 1,
+        );
+        fix.float(
+            "reveal_radius",
+            &mut self.reveal_radius,
+            0.0,
+            f32::MAX,
+            defaults.reveal_radius,
+        );
+        fix.vec3(
+            "reveal_origin",
+            &mut self.reveal_origin,
+            defaults.reveal_origin,
+        );
+        fix.mat3(
+            "stylize_matrix",
+            &mut self.stylize_matrix,
+            defaults.stylize_matrix,
+        );
+        fix.float(
+            "stylize_strength",
+            &mut self.stylize_strength,
+            0.0,
+            1.0,
+            defaults.stylize_strength,
+        );
+        fix.float(
+            "stylize_palette_tolerance",
+            &mut self.stylize_palette_tolerance,
+            0.0,
+            1.0,
+            defaults.stylize_palette_tolerance,
+        );
+        fix.uint("quantization_space", &mut self.quantization_space, 3);
+        fix.uint("sub_material_source", &mut self.sub_material_source, 2);
+        fix.float(
+            "lightness_weight",
+            &mut self.lightness_weight,
+            0.0,
+            100.0,
+            defaults.lightness_weight,
+        );
+        fix.float(
+            "chroma_weight",
+            &mut self.chroma_weight,
+            0.0,
+            100.0,
+            defaults.chroma_weight,
+        );
+        fix.float(
+            "shadow_hue_shift",
+            &mut self.shadow_hue_shift,
+            -360.0,
+            360.0,
+            defaults.shadow_hue_shift,
+        );
+        fix.float(
+            "highlight_hue_shift",
+            &mut self.highlight_hue_shift,
+            -360.0,
+            360.0,
+            defaults.highlight_hue_shift,
+        );
+        fix.vec3(
+            "dither_direction",
+            &mut self.dither_direction,
+            defaults.dither_direction,
+        );
+        fix.uint(
+            "dither_direction_space",
+            &mut self.dither_direction_space,
+            1,
+        );
+        fix.float(
+            "dither_stretch",
+            &mut self.dither_stretch,
+            0.001,
+            1024.0,
+            defaults.dither_stretch,
+        );
+        fix.float(
+            "dither_skew",
+            &mut self.dither_skew,
+            -16.0,
+            16.0,
+            defaults.dither_skew,
+        );
+        fix.uint(
+            "toon_threshold_count",
+            &mut self.toon_threshold_count,
+            MAX_TOON_THRESHOLDS as u32,
+        );
+        fix.float(
+            "rim_strength",
+            &mut self.rim_strength,
+            0.0,
+            1.0,
+            defaults.rim_strength,
+        );
+        fix.float(
+            "rim_width",
+            &mut self.rim_width,
+            0.0,
+            1.0,
+            defaults.rim_width,
+        );
+        fix.vec("rim_color", &mut self.rim_color, defaults.rim_color);
+        fix.float(
+            "point_light_bands",
+            &mut self.point_light_bands,
+            0.0,
+            256.0,
+            defaults.point_light_bands,
+        );
+        fix.float(
+            "spot_light_bands",
+            &mut self.spot_light_bands,
+            0.0,
+            256.0,
+            defaults.spot_light_bands,
+        );
+        fix.uint("shadow_edge", &mut self.shadow_edge, 2);
+        fix.uint("no_quantize", &mut self.no_quantize, 1);
+        fix.uint("dither_anchor", &mut self.dither_anchor, 3);
+        fix.float(
+            "exposure",
+            &mut self.exposure,
+            0.0,
+            1024.0,
+            defaults.exposure,
+        );
+        fix.uint(
+            "dither_temporal_cycle",
+            &mut self.dither_temporal_cycle,
+            1024,
+        );
+        fix.uint("dither_alpha", &mut self.dither_alpha, 1);
+        fix.float(
+            "prepass_alpha",
+            &mut self.prepass_alpha,
+            0.0,
+            1.0,
+            defaults.prepass_alpha,
+        );
+        fix.float(
+            "outline_participation",
+            &mut self.outline_participation,
+            0.0,
+            1.0,
+            defaults.outline_participation,
+        );
+        fix.float(
+            "dither_band_window",
+            &mut self.dither_band_window,
+            0.0,
+            1.0,
+            defaults.dither_band_window,
+        );
+        fix.float(
+            "dissolve_amount",
+            &mut self.dissolve_amount,
+            0.0,
+            1.0,
+            defaults.dissolve_amount,
+        );
+        fix.float(
+            "dissolve_edge_width",
+            &mut self.dissolve_edge_width,
+            0.0,
+            1.0,
+            defaults.dissolve_edge_width,
+        );
+        fix.vec(
+            "dissolve_edge_color",
+            &mut self.dissolve_edge_color,
+            defaults.dissolve_edge_color,
+        );
+        fix.uint("palette_blend_source", &mut self.palette_blend_source, 5);
+        fix.vec(
+            "outline_color",
+            &mut self.outline_color,
+            defaults.outline_color,
+        );
+        fix.uint("outline_normal_map", &mut self.outline_normal_map, 1);
+        fix.float(
+            "emissive_bands",
+            &mut self.emissive_bands,
+            0.0,
+            256.0,
+            defaults.emissive_bands,
+        );
+        fix.float(
+            "normal_map_strength",
+            &mut self.normal_map_strength,
+            0.0,
+            1.0,
+            defaults.normal_map_strength,
+        );
+        fix.float(
+            "outline_depth_threshold",
+            &mut self.outline_depth_threshold,
+            0.0,
+            1.0,
+            defaults.outline_depth_threshold,
+        );
+        fix.float(
+            "outline_normal_threshold",
+            &mut self.outline_normal_threshold,
+            0.0,
+            2.0,
+            defaults.outline_normal_threshold,
+        );
+        fix.uint(
+            "palette_b_offset",
+            &mut self.palette_b_offset,
+            PALETTE_CAPACITY,
+        );
+        fix.uint(
+            "palette_b_count",
+            &mut self.palette_b_count,
+            PALETTE_CAPACITY - self.palette_b_offset,
+        );
+        fix.float(
+            "dither_anchor_scale",
+            &mut self.dither_anchor_scale,
+            0.01,
+            1024.0,
+            defaults.dither_anchor_scale,
+        );
+        fix.float(
+            "wind_strength",
+            &mut self.wind_strength,
+            0.0,
+            1024.0,
+            defaults.wind_strength,
+        );
+        fix.float(
+            "wind_frequency",
+            &mut self.wind_frequency,
+            0.0,
+            1024.0,
+            defaults.wind_frequency,
+        );
+        fix.float(
+            "wind_wavelength",
+            &mut self.wind_wavelength,
+            0.01,
+            1.0e6,
+            defaults.wind_wavelength,
+        );
+        fix.float(
+            "wind_fps",
+            &mut self.wind_fps,
+            0.0,
+            1024.0,
+            defaults.wind_fps,
+        );
+        fix.uint("wind_weight", &mut self.wind_weight, 2);
+        fix.vec3(
+            "wind_direction",
+            &mut self.wind_direction,
+            defaults.wind_direction,
+        );
+        fix.uint("unlit", &mut self.unlit, 1);
+        fix.float(
+            "flipbook_fps",
+            &mut self.flipbook_fps,
+            0.0,
+            1024.0,
+            defaults.flipbook_fps,
+        );
+        fix.vec("flash_color", &mut self.flash_color, defaults.flash_color);
+        fix.float(
+            "flash_amount",
+            &mut self.flash_amount,
+            0.0,
+            1.0,
+            defaults.flash_amount,
+        );
+        fix.float(
+            "ssao_bands",
+            &mut self.ssao_bands,
+            0.0,
+            64.0,
+            defaults.ssao_bands,
+        );
+        fix.float(
+            "ssao_dither",
+            &mut self.ssao_dither,
+            0.0,
+            1.0,
+            defaults.ssao_dither,
+        );
+        fix.float(
+            "ssao_strength",
+            &mut self.ssao_strength,
+            0.0,
+            1.0,
+            defaults.ssao_strength,
+        );
+        fix.float(
+            "light_falloff_bands",
+            &mut self.light_falloff_bands,
+            0.0,
+            64.0,
+            defaults.light_falloff_bands,
+        );
+        fix.float(
+            "light_falloff_dither",
+            &mut self.light_falloff_dither,
+            0.0,
+            1.0,
+            defaults.light_falloff_dither,
+        );
+        fix.uint("dither_density_space", &mut self.dither_density_space, 1);
+        fix.float(
+            "dither_texels_per_uv",
+            &mut self.dither_texels_per_uv,
+            0.01,
+            4096.0,
+            defaults.dither_texels_per_uv,
+        );
+        fix.float(
+            "wind_height",
+            &mut self.wind_height,
+            0.001,
+            1.0e6,
+            defaults.wind_height,
+        );
+        if ![2, 4, 8].contains(&self.bayer_size) {
+            let fixed = match self.bayer_size {
+                0..=2 => 2,
+                3..=5 => 4,
+                _ => 8,
+            };
+            fix.0.push(ParamIssue {
+                field: "bayer_size",
+                message: format!("{} is not 2, 4 or 8, using {fixed}", self.bayer_size),
+            });
+            self.bayer_size = fixed;
+        }
+
+        for sub in &mut self.sub_params {
+            fix.vec("sub_params.tint", &mut sub.tint, sub_defaults.tint);
+            fix.float(
+                "sub_params.toon_bands",
+                &mut sub.toon_bands,
+                1.0,
+                256.0,
+                sub_defaults.toon_bands,
+            );
+            fix.float(
+                "sub_params.toon_shadow_floor",
+                &mut sub.toon_shadow_floor,
+                0.0,
+                1.0,
+                sub_defaults.toon_shadow_floor,
+            );
+            fix.float(
+                "sub_params.palette_strength",
+                &mut sub.palette_strength,
+                0.0,
+                1.0,
+                sub_defaults.palette_strength,
+            );
+            fix.float(
+                "sub_params.dither_strength",
+                &mut sub.dither_strength,
+                0.0,
+                1.0,
+                sub_defaults.dither_strength,
+            );
+        }
+
+        fix.0
+    }
+}
+
+// ──────────────────────────────────────────────
+//  Systems
+// ──────────────────────────────────────────────
+
+/// Sanitizes every added or modified `PixelArtMaterial` and warns about each
+/// fixed field. Enabled in debug builds and with the `validation` feature.
+pub fn validate_pixel_art_materials(
+    mut events: MessageReader<AssetEvent<PixelArtMaterial>>,
+    mut materials: ResMut<Assets<PixelArtMaterial>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = *event else {
+            continue;
+        };
+        let Some(material) = materials.get(id) else {
+            continue;
+        };
+
+        // Check on a copy so valid materials are not marked modified again.
+        let mut params = material.extension.params.clone();
+        let issues = params.sanitize();
+        if issues.is_empty() {
+            continue;
+        }
+        for issue in &issues {
+            warn!("PixelArtMaterial {id}: `{}` {}", issue.field, issue.message);
+        }
+        if let Some(material) = materials.get_mut(id) {
+            material.extension.params = params;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(issues: &[ParamIssue]) -> Vec<&'static str> {
+        issues.iter().map(|issue| issue.field).collect()
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(PixelArtShaderParams::default().sanitize().is_empty());
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let mut params = PixelArtShaderParams {
+            toon_bands: 0.0,
+            dither_strength: 2.5,
+            palette_count: PALETTE_CAPACITY + 10,
+            dither_anchor: 7,
+            bayer_size: 3,
+            ..default()
+        };
+        let issues = params.sanitize();
+
+        assert_eq!(params.toon_bands, 1.0);
+        assert_eq!(params.dither_strength, 1.0);
+        assert_eq!(params.palette_count, PALETTE_CAPACITY);
+        assert_eq!(params.dither_anchor, 3);
+        assert_eq!(params.bayer_size, 4);
+        assert_eq!(
+            fields(&issues),
+            [
+                "toon_bands",
+                "palette_count",
+                "dither_strength",
+                "dither_anchor",
+                "bayer_size",
+            ]
+        );
+        assert_eq!(issues[0].message, "0 is outside 1..=256, using 1");
+    }
+
+    #[test]
+    fn non_finite_values_use_defaults() {
+        let defaults = PixelArtShaderParams::default();
+        let mut params = PixelArtShaderParams {
+            toon_softness: f32::NAN,
+            reveal_origin: Vec3::new(0.0, f32::INFINITY, 0.0),
+            stylize_matrix: Mat3::from_diagonal(Vec3::new(1.0, f32::NAN, 1.0)),
+            dither_direction: Vec3::NAN,
+            wind_direction: Vec3::new(f32::NEG_INFINITY, 0.0, 0.0),
+            flash_color: Vec4::new(1.0, f32::NAN, 1.0, 1.0),
+            ..default()
+        };
+        params.sub_params[1].tint = Vec4::INFINITY;
+        let issues = params.sanitize();
+
+        assert_eq!(params.toon_softness, defaults.toon_softness);
+        assert_eq!(params.reveal_origin, defaults.reveal_origin);
+        assert_eq!(params.stylize_matrix, defaults.stylize_matrix);
+        assert_eq!(params.dither_direction, defaults.dither_direction);
+        assert_eq!(params.wind_direction, defaults.wind_direction);
+        assert_eq!(params.flash_color, defaults.flash_color);
+        assert_eq!(params.sub_params[1].tint, PixelArtSubParams::default().tint);
+        assert_eq!(
+            fields(&issues),
+            [
+                "toon_softness",
+                "reveal_origin",
+                "stylize_matrix",
+                "dither_direction",
+                "wind_direction",
+                "flash_color",
+                "sub_params.tint",
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitized_params_are_stable() {
+        let mut params = PixelArtShaderParams {
+            toon_bands: f32::NAN,
+            palette_b_offset: PALETTE_CAPACITY,
+            palette_b_count: 5,
+            ..default()
+        };
+        assert!(!params.sanitize().is_empty());
+        assert!(params.sanitize().is_empty());
+    }
+}
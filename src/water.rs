@@ -0,0 +1,160 @@
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
+use bevy::render::storage::ShaderStorageBuffer;
+use bevy::shader::ShaderRef;
+
+use crate::{DEFAULT_PALETTE_BUFFER, default_pixel_art_palette};
+
+// ──────────────────────────────────────────────
+//  Material
+// ──────────────────────────────────────────────
+
+/// Material type alias: StandardMaterial + PixelArtWaterExtension.
+pub type PixelArtWaterMaterial = ExtendedMaterial<StandardMaterial, PixelArtWaterExtension>;
+
+/// Pixel art water surface: scrolling banded waves, depth-banded shallow to
+/// deep color, Bayer-dithered shoreline foam and stepped specular glints, all
+/// snapped to the palette.
+///
+/// Water depth and foam come from the view's depth prepass (`DepthPrepass` on
+/// the camera, which the low-res camera already has), so the water itself
+/// must stay out of the prepass: use `AlphaMode::Blend` on the base material
+/// (`PixelArtWaterExtension::material` does). Without a depth prepass the
+/// surface is drawn in `deep_color` without foam. Waves are computed from
+/// world XZ, so any flat mesh works; no UVs needed.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default)]
+pub struct PixelArtWaterExtension {
+    #[uniform(100)]
+    pub params: PixelArtWaterParams,
+    /// Palette storage buffer, as `PixelArtExtension::palette`. Unbound with
+    /// the `webgl2` feature, where `params.palette_colors` is used instead.
+    #[cfg_attr(not(feature = "webgl2"), storage(101, read_only))]
+    pub palette: Handle<ShaderStorageBuffer>,
+}
+
+impl Default for PixelArtWaterExtension {
+    fn default() -> Self {
+        Self {
+            params: PixelArtWaterParams::default(),
+            palette: DEFAULT_PALETTE_BUFFER,
+        }
+    }
+}
+
+impl PixelArtWaterExtension {
+    /// Water material: this extension on a translucent, matte, non-reflective
+    /// base (the colors come from the extension params).
+    pub fn material(self) -> PixelArtWaterMaterial {
+        ExtendedMaterial {
+            base: StandardMaterial {
+                base_color: Color::WHITE,
+                perceptual_roughness: 1.0,
+                reflectance: 0.0,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            },
+            extension: self,
+        }
+    }
+}
+
+impl MaterialExtension for PixelArtWaterExtension {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_pixel_art_shader/water.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+            && !cfg!(feature = "webgl2")
+        {
+            fragment.shader_defs.push("PALETTE_STORAGE_BUFFER".into());
+        }
+        Ok(())
+    }
+}
+
+/// GPU-side water parameters. Must match the WGSL struct layout exactly.
+#[derive(Clone, Debug, PartialEq, ShaderType, Reflect)]
+#[reflect(Default)]
+pub struct PixelArtWaterParams {
+    /// Color where the water is shallow (linear RGBA; alpha is the opacity).
+    pub shallow_color: Vec4,
+    /// Color at `depth_range` and beyond (linear RGBA).
+    pub deep_color: Vec4,
+    /// Shoreline foam color (linear RGB, drawn opaque).
+    pub foam_color: Vec4,
+    /// Specular glint color (linear RGB).
+    pub glint_color: Vec4,
+    /// World XZ direction the waves travel in (default: +X).
+    pub wave_direction: Vec2,
+    /// Wave speed in world units per second (default: 0.5).
+    pub wave_speed: f32,
+    /// Wavelength in world units (default: 2.0).
+    pub wave_length: f32,
+    /// Brightness steps of the wave pattern (default: 3.0).
+    pub wave_bands: f32,
+    /// Brightness variation of the wave bands (0 = flat, default: 0.15).
+    pub wave_strength: f32,
+    /// Water depth (distance from the surface to the ground behind it, along
+    /// the view ray) at which `deep_color` is reached (default: 3.0).
+    pub depth_range: f32,
+    /// Color steps between shallow and deep (default: 4.0).
+    pub depth_bands: f32,
+    /// Water depth under which foam appears (0 = no foam, default: 0.4).
+    pub foam_width: f32,
+    /// Specular level (0..1) above which glints show; the upper half of the
+    /// range draws the full glint color, the lower half a half-strength one
+    /// (default: 0.85).
+    pub glint_threshold: f32,
+    /// Specular exponent of the glints (default: 64.0).
+    pub glint_sharpness: f32,
+    /// Bayer dither strength between the two nearest palette colors
+    /// (default: 0.3).
+    pub dither_strength: f32,
+    /// Number of active palette colors (0 = no quantization, default: the
+    /// default palette's count).
+    pub palette_count: u32,
+    /// Palette colors for the `webgl2` feature (unused otherwise).
+    pub palette_colors: [Vec4; 64],
+}
+
+impl Default for PixelArtWaterParams {
+    fn default() -> Self {
+        let (palette, count) = default_pixel_art_palette();
+        let linear = |r: u8, g: u8, b: u8, a: f32| {
+            let c = Color::srgb_u8(r, g, b).to_linear();
+            Vec4::new(c.red, c.green, c.blue, a)
+        };
+        Self {
+            shallow_color: linear(41, 173, 255, 0.6),
+            deep_color: linear(29, 43, 83, 0.9),
+            foam_color: linear(255, 241, 232, 1.0),
+            glint_color: linear(255, 255, 255, 1.0),
+            wave_direction: Vec2::X,
+            wave_speed: 0.5,
+            wave_length: 2.0,
+            wave_bands: 3.0,
+            wave_strength: 0.15,
+            depth_range: 3.0,
+            depth_bands: 4.0,
+            foam_width: 0.4,
+            glint_threshold: 0.85,
+            glint_sharpness: 64.0,
+            dither_strength: 0.3,
+            palette_count: count,
+            palette_colors: palette,
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Pixel art water: scrolling banded waves, depth-banded color, dithered
+//! shoreline foam and stepped specular glints, snapped to the palette.
+//!
+//! Forward only; the material is translucent and stays out of the prepass, so
+//! the depth prepass holds the ground under the water.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+    mesh_view_bindings::{view, globals, lights},
+    prepass_utils,
+}
+
+struct PixelArtWaterParams {
+    shallow_color: vec4<f32>,
+    deep_color: vec4<f32>,
+    foam_color: vec4<f32>,
+    glint_color: vec4<f32>,
+    wave_direction: vec2<f32>,
+    wave_speed: f32,
+    wave_length: f32,
+    wave_bands: f32,
+    wave_strength: f32,
+    depth_range: f32,
+    depth_bands: f32,
+    foam_width: f32,
+    glint_threshold: f32,
+    glint_sharpness: f32,
+    dither_strength: f32,
+    palette_count: u32,
+    palette_colors: array<vec4<f32>, 64>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> water: PixelArtWaterParams;
+
+#ifdef PALETTE_STORAGE_BUFFER
+@group(#{MATERIAL_BIND_GROUP}) @binding(101)
+var<storage, read> palette_storage: array<vec4<f32>>;
+#endif
+
+fn palette_len() -> u32 {
+#ifdef PALETTE_STORAGE_BUFFER
+    return arrayLength(&palette_storage);
+#else
+    return 64u;
+#endif
+}
+
+fn palette_color(i: u32) -> vec3<f32> {
+#ifdef PALETTE_STORAGE_BUFFER
+    return palette_storage[i].rgb;
+#else
+    return water.palette_colors[i].rgb;
+#endif
+}
+
+// 4x4 Bayer threshold in [0, 1).
+fn bayer4(pos: vec2<f32>) -> f32 {
+    let x = u32(pos.x) & 3u;
+    let y = u32(pos.y) & 3u;
+    var index = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let xb = (x >> i) & 1u;
+        let yb = (y >> i) & 1u;
+        index = index * 4u + 2u * (xb ^ yb) + yb;
+    }
+    return f32(index) / 16.0;
+}
+
+fn linear_rgb_to_oklab(rgb: vec3<f32>) -> vec3<f32> {
+    let l = dot(vec3<f32>(0.4122214708, 0.5363325363, 0.0514459929), rgb);
+    let m = dot(vec3<f32>(0.2119034982, 0.6806995451, 0.1073969566), rgb);
+    let s = dot(vec3<f32>(0.0883024619, 0.2817188376, 0.6299787005), rgb);
+    let lms = pow(max(vec3<f32>(l, m, s), vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        dot(vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468), lms),
+        dot(vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099), lms),
+        dot(vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660), lms),
+    );
+}
+
+// Nearest palette entry in OKLab, dithered toward the second nearest.
+fn quantize(color: vec3<f32>, cell: vec2<f32>) -> vec3<f32> {
+    let lab = linear_rgb_to_oklab(color);
+    var d1 = 1e10;
+    var d2 = 1e10;
+    var c1 = color;
+    var c2 = color;
+    let n = min(water.palette_count, palette_len());
+    for (var i = 0u; i < n; i++) {
+        let pal_rgb = palette_color(i);
+        let d = distance(lab, linear_rgb_to_oklab(pal_rgb));
+        if (d < d1) {
+            d2 = d1;
+            c2 = c1;
+            d1 = d;
+            c1 = pal_rgb;
+        } else if (d < d2) {
+            d2 = d;
+            c2 = pal_rgb;
+        }
+    }
+    let blend = select(0.0, d1 / (d1 + d2), d1 + d2 > 0.001);
+    if (bayer4(cell) < blend * water.dither_strength) {
+        return c2;
+    }
+    return c1;
+}
+
+struct Waves {
+    // Wave height in [0, 1].
+    height: f32,
+    // Height gradient over world XZ.
+    gradient: vec2<f32>,
+}
+
+// Two crossing sine waves scrolling along `wave_direction`.
+fn waves(p: vec2<f32>) -> Waves {
+    var dir = water.wave_direction;
+    dir = select(vec2<f32>(1.0, 0.0), normalize(dir), length(dir) > 1e-5);
+    let cross_dir = normalize(dir + 0.6 * vec2<f32>(-dir.y, dir.x));
+    let k = 6.2831853 / max(water.wave_length, 1e-3);
+    let t = globals.time * water.wave_speed * k;
+
+    let a = k * dot(p, dir) - t;
+    let b = 1.7 * k * dot(p, cross_dir) - 1.3 * t;
+    var w: Waves;
+    w.height = (sin(a) + 0.5 * sin(b)) / 3.0 + 0.5;
+    w.gradient = (k * cos(a) * dir + 0.85 * k * cos(b) * cross_dir) / 3.0;
+    return w;
+}
+
+// View-space z of the prepass depth at this fragment.
+fn scene_view_z(frag_coord: vec4<f32>, depth: f32) -> f32 {
+    let uv = (frag_coord.xy - view.viewport.xy) / view.viewport.zw;
+    let clip = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let p = view.view_from_clip * clip;
+    return p.z / p.w;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    let cell = floor(in.position.xy);
+
+    // --- Water depth below the surface along the view ray ---
+    var water_depth = 1e10;
+#ifdef DEPTH_PREPASS
+    let depth = prepass_utils::prepass_depth(in.position, 0u);
+    if (depth > 0.0) {
+        let surface_z = (view.view_from_world * in.world_position).z;
+        water_depth = max(surface_z - scene_view_z(in.position, depth), 0.0);
+    }
+#endif
+
+    // --- Depth-banded shallow → deep color ---
+    let bands = max(water.depth_bands, 1.0);
+    let depth_t = min(floor(saturate(water_depth / max(water.depth_range, 1e-3)) * bands) / max(bands - 1.0, 1.0), 1.0);
+    var base = mix(water.shallow_color, water.deep_color, depth_t);
+
+    // --- Scrolling banded waves ---
+    let w = waves(in.world_position.xz);
+    let wave_steps = max(water.wave_bands, 1.0);
+    let banded = floor(w.height * wave_steps) / max(wave_steps - 1.0, 1.0);
+    base = vec4<f32>(base.rgb * (1.0 + (banded - 0.5) * 2.0 * water.wave_strength), base.a);
+
+    pbr_input.material.base_color *= base;
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    var color = out.color.rgb;
+    var alpha = out.color.a;
+
+    // --- Stepped specular glints from the first directional light ---
+    if (lights.n_directional_lights > 0u) {
+        let amplitude = 0.05 * water.wave_length;
+        let n = normalize(vec3<f32>(-w.gradient.x * amplitude, 1.0, -w.gradient.y * amplitude));
+        let v = normalize(view.world_position - in.world_position.xyz);
+        let h = normalize(lights.directional_lights[0].direction_to_light + v);
+        let spec = pow(saturate(dot(n, h)), water.glint_sharpness);
+        let t = water.glint_threshold;
+        if (spec >= t) {
+            let level = select(0.5, 1.0, spec >= (1.0 + t) * 0.5);
+            color = mix(color, water.glint_color.rgb, level);
+            alpha = max(alpha, level);
+        }
+    }
+
+    // --- Dithered shoreline foam, pushed around by the waves ---
+    if (water.foam_width > 0.0) {
+        let foam = 1.0 - water_depth / water.foam_width + (w.height - 0.5) * 0.3;
+        if (bayer4(cell) < foam) {
+            color = water.foam_color.rgb;
+            alpha = 1.0;
+        }
+    }
+
+    // --- Palette snap ---
+#ifdef TONEMAP_IN_SHADER
+    color = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    if (water.palette_count > 0u) {
+        color = quantize(color, cell);
+    }
+#else
+    let hdr_scale = max(max(color.r, max(color.g, color.b)), 1.0);
+    if (water.palette_count > 0u) {
+        color = quantize(color / hdr_scale, cell) * hdr_scale;
+    }
+#endif
+
+    out.color = main_pass_post_lighting_processing(pbr_input, vec4<f32>(color, alpha));
+    return out;
+}